@@ -0,0 +1,114 @@
+//! Heap allocations of a solve with each strategy, counted by this benchmark's
+//! global allocator so that the command line tool doesn't pay for counting.
+//! Evaluating a candidate allocates nothing, so the allocations left should be
+//! about those of the result rather than one per examined word.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use nyt_spelling_bee_solver::{
+    dictionary::Dictionary,
+    game::{
+        BruteForce, ComplementIndex, Game, GameSolver, LetterMap, PuzzleSpec, RarestLetterMap,
+        SolveStrategy,
+    },
+};
+
+const WORDS: usize = 200_000;
+const REPEAT: usize = 10;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations (and reallocations).
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded with the caller's guarantees.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Pseudo-random uppercase words of 4 to 12 letters, mostly made of common
+/// letters so that puzzles have answers.
+fn words() -> Vec<String> {
+    const LETTERS: &[u8] = b"EEEAAAIIOOTTNNRRSSLLCCUDPMHGBFYWKVXZJQ";
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..WORDS)
+        .map(|_| {
+            let len = 4 + (next() % 9) as usize;
+            (0..len)
+                .map(|_| LETTERS[(next() % LETTERS.len() as u64) as usize] as char)
+                .collect()
+        })
+        .collect()
+}
+
+// the mean allocations of a solve on a single thread, with the words examined
+// and the answers found.
+fn count<'a, S: SolveStrategy<'a> + Send + Sync>(
+    name: &str,
+    dict: &'a Dictionary,
+    puzzle: &PuzzleSpec,
+) {
+    let solver = GameSolver::<S>::new(dict, 1).unwrap();
+    let mut allocations = 0;
+    let mut examined = 0;
+    let mut answers = 0;
+    for _ in 0..REPEAT {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let (result, stats) = solver.solve_with_stats(puzzle).unwrap();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+        examined = stats.examined;
+        answers = result.entries().len();
+    }
+    println!(
+        "{:<20} {:>12} {:>10} {:>8}",
+        name,
+        allocations / REPEAT,
+        examined,
+        answers
+    );
+}
+
+fn main() {
+    let dict: Dictionary = words().into_iter().collect();
+    let puzzle = PuzzleSpec::from(Game::parse("E", "ARTNIS").unwrap());
+    println!(
+        "{:<20} {:>12} {:>10} {:>8}",
+        "strategy", "solve allocs", "examined", "answers"
+    );
+    count::<BruteForce>("brute force", &dict, &puzzle);
+    count::<LetterMap>("letter map", &dict, &puzzle);
+    count::<RarestLetterMap>("rarest letter map", &dict, &puzzle);
+    count::<ComplementIndex>("complement index", &dict, &puzzle);
+}
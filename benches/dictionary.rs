@@ -0,0 +1,77 @@
+//! Loading and lookups of a plain word list compared to a memory-mapped one.
+
+use std::{hint::black_box, path::PathBuf};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use nyt_spelling_bee_solver::{
+    dictionary::{Dictionary, ParseOptions, WordList},
+    mapped::MappedDictionary,
+};
+
+const WORDS: usize = 500_000;
+
+/// Pseudo-random uppercase words of 4 to 12 letters, sorted and deduplicated.
+fn words() -> Vec<String> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut words: Vec<String> = (0..WORDS)
+        .map(|_| {
+            let len = 4 + (next() % 9) as usize;
+            (0..len)
+                .map(|_| (b'A' + (next() % 26) as u8) as char)
+                .collect()
+        })
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+fn write_word_list(words: &[String]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("bench-words-{}.txt", std::process::id()));
+    std::fs::write(&path, words.join("\n")).unwrap();
+    path
+}
+
+fn bench_dictionary(c: &mut Criterion) {
+    let words = words();
+    let path = write_word_list(&words);
+    let options = ParseOptions::default();
+
+    let mut load = c.benchmark_group("load");
+    load.sample_size(10);
+    load.bench_function("hash set", |b| {
+        b.iter(|| Dictionary::load(black_box(&path), options).unwrap())
+    });
+    load.bench_function("mapped", |b| {
+        b.iter(|| MappedDictionary::open(black_box(&path), options).unwrap())
+    });
+    load.finish();
+
+    let dict = Dictionary::load(&path, options).unwrap();
+    let mapped = MappedDictionary::open(&path, options).unwrap();
+    // every other lookup misses.
+    let queries: Vec<String> = words
+        .iter()
+        .step_by(1000)
+        .flat_map(|word| [word.clone(), format!("{word}Q")])
+        .collect();
+    let mut contains = c.benchmark_group("contains");
+    contains.bench_function("hash set", |b| {
+        b.iter(|| queries.iter().filter(|word| dict.contains(word)).count())
+    });
+    contains.bench_function("mapped", |b| {
+        b.iter(|| queries.iter().filter(|word| mapped.contains(word)).count())
+    });
+    contains.finish();
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, bench_dictionary);
+criterion_main!(benches);
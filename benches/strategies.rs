@@ -0,0 +1,69 @@
+//! Solving with the complement index and the rarest letter map compared to the
+//! letter map.
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use nyt_spelling_bee_solver::{
+    dictionary::Dictionary,
+    game::{ComplementIndex, Game, GameSolver, LetterMap, PuzzleSpec, RarestLetterMap},
+};
+
+const WORDS: usize = 200_000;
+
+/// Pseudo-random uppercase words of 4 to 12 letters, mostly made of common
+/// letters so that puzzles have answers.
+fn words() -> Vec<String> {
+    const LETTERS: &[u8] = b"EEEAAAIIOOTTNNRRSSLLCCUDPMHGBFYWKVXZJQ";
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..WORDS)
+        .map(|_| {
+            let len = 4 + (next() % 9) as usize;
+            (0..len)
+                .map(|_| LETTERS[(next() % LETTERS.len() as u64) as usize] as char)
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_strategies(c: &mut Criterion) {
+    let dict: Dictionary = words().into_iter().collect();
+    let puzzle = PuzzleSpec::from(Game::parse("E", "ARTNIS").unwrap());
+
+    let mut build = c.benchmark_group("build");
+    build.sample_size(10);
+    build.bench_function("letter map", |b| {
+        b.iter(|| GameSolver::<LetterMap>::new(black_box(&dict), 1).unwrap())
+    });
+    build.bench_function("rarest letter map", |b| {
+        b.iter(|| GameSolver::<RarestLetterMap>::new(black_box(&dict), 1).unwrap())
+    });
+    build.bench_function("complement index", |b| {
+        b.iter(|| GameSolver::<ComplementIndex>::new(black_box(&dict), 1).unwrap())
+    });
+    build.finish();
+
+    let letter_map = GameSolver::<LetterMap>::new(&dict, 1).unwrap();
+    let rarest_letter_map = GameSolver::<RarestLetterMap>::new(&dict, 1).unwrap();
+    let complement_index = GameSolver::<ComplementIndex>::new(&dict, 1).unwrap();
+    let mut solve = c.benchmark_group("solve");
+    solve.bench_function("letter map", |b| {
+        b.iter(|| letter_map.solve(black_box(&puzzle)).unwrap())
+    });
+    solve.bench_function("rarest letter map", |b| {
+        b.iter(|| rarest_letter_map.solve(black_box(&puzzle)).unwrap())
+    });
+    solve.bench_function("complement index", |b| {
+        b.iter(|| complement_index.solve(black_box(&puzzle)).unwrap())
+    });
+    solve.finish();
+}
+
+criterion_group!(benches, bench_strategies);
+criterion_main!(benches);
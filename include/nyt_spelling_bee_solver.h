@@ -0,0 +1,59 @@
+#ifndef NYT_SPELLING_BEE_SOLVER_H
+#define NYT_SPELLING_BEE_SOLVER_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The puzzle was solved and `out_json` holds the solution.
+ */
+#define BEE_OK 0
+
+/**
+ * An argument was null or not valid UTF-8, `out_json` is left untouched.
+ */
+#define BEE_INVALID_ARGUMENT 1
+
+/**
+ * The dictionary couldn't be loaded or the puzzle is invalid, `out_json`
+ * holds `{"error": "..."}`.
+ */
+#define BEE_ERROR 2
+
+/**
+ * The solver panicked, `out_json` is left untouched.
+ */
+#define BEE_PANIC 3
+
+/**
+ * Solve the puzzle with the center letter `center` (e.g. `"c"`) and outer
+ * letters `letters` (e.g. `"altefi"`) using the word list at `dict_path`.
+ * Returns one of the `BEE_*` codes and stores a JSON string like
+ * `{"answers":[{"word":"FACET","points":2,"breakdown":{"base":1,"length_bonus":1,"pangram_bonus":0},"pangram":false}],"total_points":2}`
+ * in `*out_json`.
+ *
+ * # Safety
+ *
+ * `center`, `letters` and `dict_path` must be null or valid NUL-terminated
+ * strings, and `out_json` must be null or valid for writing a pointer.
+ */
+int32_t bee_solve(const char *center,
+                  const char *letters,
+                  const char *dict_path,
+                  char **out_json);
+
+/**
+ * Free a string returned by this library. Null is ignored.
+ *
+ * # Safety
+ *
+ * `s` must be null or a string returned by this library that wasn't freed yet.
+ */
+void bee_string_free(char *s);
+
+#endif  /* NYT_SPELLING_BEE_SOLVER_H */
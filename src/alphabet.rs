@@ -0,0 +1,158 @@
+//! The letters puzzles can consist of. Every supported letter has a fixed
+//! index below 64, so that sets of letters are bitmasks ([`LetterSet`]) for any
+//! language, and each language uses a subset of them ([`Alphabet`]).
+
+use std::fmt;
+
+use crate::game::Letter;
+
+/// The most letters a [`LetterSet`] can hold.
+pub const MAX_LETTERS: usize = 64;
+
+/// All supported letters in index order: A to Z, followed by the letters of
+/// language packs that aren't spelled with those. At most `MAX_LETTERS`.
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÅÆÇÑÖØÜ";
+
+/// The letters of a language, e.g. A to Z and `Ñ` for Spanish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet(LetterSet);
+
+impl Alphabet {
+    /// A to Z.
+    pub const LATIN: Alphabet = Alphabet(LetterSet((1 << 26) - 1));
+
+    /// The alphabet of `letters`, which must all be supported.
+    pub fn new(letters: &str) -> Result<Alphabet, crate::game::InvalidLetter> {
+        letters
+            .chars()
+            .map(Letter::new)
+            .collect::<Result<LetterSet, _>>()
+            .map(Alphabet)
+    }
+
+    /// `self` with `letter`.
+    pub fn with(self, letter: Letter) -> Alphabet {
+        let mut letters = self.0;
+        letters.insert(letter);
+        Alphabet(letters)
+    }
+
+    pub fn letters(self) -> LetterSet {
+        self.0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `word` consists only of (uppercase) letters of the alphabet.
+    pub fn spells(self, word: &str) -> bool {
+        word.chars()
+            .all(|c| letter_index(c).is_some_and(|index| self.0.0 & 1 << index != 0))
+    }
+}
+
+/// e.g. `ABCDEFGHIJKLMNOPQRSTUVWXYZÑ`
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A set of letters as a bitmask, bit i standing for the letter of index i.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LetterSet(u64);
+
+impl LetterSet {
+    pub const EMPTY: LetterSet = LetterSet(0);
+
+    /// The letters of `word`, if it consists only of letters.
+    pub fn of_word(word: &str) -> Option<LetterSet> {
+        word.chars()
+            .try_fold(0, |set, c| Some(set | 1 << Letter::new(c).ok()?.index()))
+            .map(LetterSet)
+    }
+
+    pub fn insert(&mut self, letter: Letter) {
+        self.0 |= 1 << letter.index();
+    }
+
+    pub fn contains(self, letter: Letter) -> bool {
+        self.0 & 1 << letter.index() != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_subset(self, other: LetterSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn union(self, other: LetterSet) -> LetterSet {
+        LetterSet(self.0 | other.0)
+    }
+
+    pub fn difference(self, other: LetterSet) -> LetterSet {
+        LetterSet(self.0 & !other.0)
+    }
+
+    /// The letters in index order, i.e. A to Z first.
+    pub fn iter(self) -> impl Iterator<Item = Letter> {
+        (0..MAX_LETTERS as u8)
+            .filter(move |&index| self.0 & 1 << index != 0)
+            .filter_map(Letter::from_index)
+    }
+
+    /// Every subset, from `self` down to the empty set.
+    pub fn subsets(self) -> impl Iterator<Item = LetterSet> {
+        let mut next = Some(self.0);
+        std::iter::from_fn(move || {
+            let subset = next?;
+            next = (subset != 0).then(|| (subset - 1) & self.0);
+            Some(LetterSet(subset))
+        })
+    }
+}
+
+impl FromIterator<Letter> for LetterSet {
+    fn from_iter<I: IntoIterator<Item = Letter>>(letters: I) -> Self {
+        let mut set = LetterSet::EMPTY;
+        for letter in letters {
+            set.insert(letter);
+        }
+        set
+    }
+}
+
+/// e.g. `ACEFILT`
+impl fmt::Display for LetterSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|letter| write!(f, "{letter}"))
+    }
+}
+
+/// The index of an uppercase supported letter.
+pub(crate) fn letter_index(c: char) -> Option<u8> {
+    if c.is_ascii_uppercase() {
+        Some(c as u8 - b'A')
+    } else {
+        LETTERS
+            .chars()
+            .position(|letter| letter == c)
+            .map(|i| i as u8)
+    }
+}
+
+/// The letter of an index, see [`letter_index`].
+pub(crate) fn index_letter(index: u8) -> Option<char> {
+    LETTERS.chars().nth(usize::from(index))
+}
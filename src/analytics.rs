@@ -0,0 +1,235 @@
+//! Aggregate statistics over archived puzzles.
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    archive::ArchivedPuzzle,
+    dictionary::WordList,
+    game::{GameResult, Word},
+};
+
+pub struct ArchiveStats<'a> {
+    puzzle_count: usize,
+    answer_count: usize,
+    /// Pangrams by how many puzzles they were a pangram of, most common first.
+    common_pangrams: Vec<(&'a Word, usize)>,
+    /// Answers by how many puzzles they were an answer of, most common first.
+    reused_answers: Vec<(&'a Word, usize)>,
+    /// Puzzles by total points, most first.
+    hardest_puzzles: Vec<(&'a ArchivedPuzzle, usize)>,
+}
+
+impl<'a> ArchiveStats<'a> {
+    /// Compute the stats of `puzzles`, keeping the `top` entries of each ranking.
+    pub fn new(puzzles: impl IntoIterator<Item = &'a ArchivedPuzzle>, top: usize) -> Self {
+        let mut puzzle_count = 0;
+        let mut answer_count = 0;
+        let mut pangram_counts: HashMap<&Word, usize> = HashMap::new();
+        let mut answer_counts: HashMap<&Word, usize> = HashMap::new();
+        let mut hardest_puzzles = Vec::new();
+        for puzzle in puzzles {
+            puzzle_count += 1;
+            answer_count += puzzle.answers.len();
+            for pangram in &puzzle.pangrams {
+                *pangram_counts.entry(pangram).or_default() += 1;
+            }
+            for answer in &puzzle.answers {
+                *answer_counts.entry(answer).or_default() += 1;
+            }
+            hardest_puzzles.push((puzzle, puzzle.total_points()));
+        }
+
+        hardest_puzzles.sort_by(|(a, a_points), (b, b_points)| {
+            b_points.cmp(a_points).then(a.print_date.cmp(&b.print_date))
+        });
+        hardest_puzzles.truncate(top);
+
+        ArchiveStats {
+            puzzle_count,
+            answer_count,
+            common_pangrams: most_common(pangram_counts, top),
+            reused_answers: most_common(answer_counts, top),
+            hardest_puzzles,
+        }
+    }
+}
+
+// words occurring only once are not interesting here, ties are broken alphabetically.
+fn most_common(counts: HashMap<&Word, usize>, top: usize) -> Vec<(&Word, usize)> {
+    let mut counts: Vec<_> = counts.into_iter().filter(|&(_, count)| count > 1).collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts.truncate(top);
+    counts
+}
+
+impl fmt::Display for ArchiveStats<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} puzzles", self.puzzle_count)?;
+        if self.puzzle_count == 0 {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{:.1} answers per puzzle",
+            self.answer_count as f64 / self.puzzle_count as f64
+        )?;
+
+        writeln!(f, "\nMost common pangrams:")?;
+        for (word, count) in &self.common_pangrams {
+            writeln!(f, "  {word} ({count} puzzles)")?;
+        }
+        writeln!(f, "\nMost reused answers:")?;
+        for (word, count) in &self.reused_answers {
+            writeln!(f, "  {word} ({count} puzzles)")?;
+        }
+        writeln!(f, "\nHardest puzzles:")?;
+        for (puzzle, points) in &self.hardest_puzzles {
+            let letters: String = puzzle.game().letters().map(char::from).collect();
+            writeln!(
+                f,
+                "  {} {letters}: {} answers, {points} points",
+                puzzle.print_date,
+                puzzle.answers.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// How well a dictionary matches NYT's official answers: how many of them it
+/// contains, and which answers it suggests that NYT didn't accept.
+pub struct DictionaryCoverage<'a> {
+    puzzle_count: usize,
+    /// Distinct official answers, and how many of them the dictionary contains.
+    answers: usize,
+    covered_answers: usize,
+    /// Official answers counted once per puzzle, and how many of them the
+    /// dictionary contains.
+    occurrences: usize,
+    covered_occurrences: usize,
+    /// Answers found with the dictionary, and how many of them were official.
+    suggestions: usize,
+    official_suggestions: usize,
+    /// Official answers missing from the dictionary, most common first.
+    missing: Vec<(&'a Word, usize)>,
+    /// Answers found with the dictionary that weren't official, most common first.
+    wrong: Vec<(&'a Word, usize)>,
+}
+
+impl<'a> DictionaryCoverage<'a> {
+    /// Compare the official answers of archived puzzles with `dict` and the
+    /// results of solving the puzzles with it, keeping the `top` entries of
+    /// each ranking.
+    pub fn new(
+        dict: &impl WordList,
+        solved: impl IntoIterator<Item = (&'a ArchivedPuzzle, &'a GameResult<'a>)>,
+        top: usize,
+    ) -> Self {
+        let mut coverage = DictionaryCoverage {
+            puzzle_count: 0,
+            answers: 0,
+            covered_answers: 0,
+            occurrences: 0,
+            covered_occurrences: 0,
+            suggestions: 0,
+            official_suggestions: 0,
+            missing: Vec::new(),
+            wrong: Vec::new(),
+        };
+        let mut answer_counts: HashMap<&Word, usize> = HashMap::new();
+        let mut wrong_counts: HashMap<&Word, usize> = HashMap::new();
+        for (puzzle, result) in solved {
+            coverage.puzzle_count += 1;
+            for answer in &puzzle.answers {
+                *answer_counts.entry(answer).or_default() += 1;
+                coverage.occurrences += 1;
+                if dict.contains(answer) {
+                    coverage.covered_occurrences += 1;
+                }
+            }
+            for (word, _) in result.entries() {
+                coverage.suggestions += 1;
+                if puzzle.answers.contains(word) {
+                    coverage.official_suggestions += 1;
+                } else {
+                    *wrong_counts.entry(word).or_default() += 1;
+                }
+            }
+        }
+
+        coverage.answers = answer_counts.len();
+        answer_counts.retain(|answer, _| !dict.contains(answer));
+        coverage.covered_answers = coverage.answers - answer_counts.len();
+        coverage.missing = ranked(answer_counts, top);
+        coverage.wrong = ranked(wrong_counts, top);
+        coverage
+    }
+
+    /// The share of distinct official answers the dictionary contains, from 0 to 1.
+    pub fn coverage(&self) -> f64 {
+        ratio(self.covered_answers, self.answers)
+    }
+}
+
+// the `top` most common words, ties broken alphabetically.
+fn ranked(counts: HashMap<&Word, usize>, top: usize) -> Vec<(&Word, usize)> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts.truncate(top);
+    counts
+}
+
+// 0 for no parts of nothing.
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// A scorecard, e.g.
+/// ```text
+/// 412 puzzles
+/// coverage: 97.3% of 9120 official answers (99.1% of 12440 occurrences)
+/// precision: 68.2% of 18240 suggested answers were official
+/// ```
+/// followed by the rankings of missing and wrong answers.
+impl fmt::Display for DictionaryCoverage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} puzzles", self.puzzle_count)?;
+        if self.puzzle_count == 0 {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "coverage: {:.1}% of {} official answers ({:.1}% of {} occurrences)",
+            100.0 * self.coverage(),
+            self.answers,
+            100.0 * ratio(self.covered_occurrences, self.occurrences),
+            self.occurrences
+        )?;
+        writeln!(
+            f,
+            "precision: {:.1}% of {} suggested answers were official",
+            100.0 * ratio(self.official_suggestions, self.suggestions),
+            self.suggestions
+        )?;
+
+        writeln!(
+            f,
+            "\nMost common official answers missing from the dictionary:"
+        )?;
+        write_ranking(f, &self.missing)?;
+        writeln!(f, "\nMost common suggestions NYT didn't accept:")?;
+        write_ranking(f, &self.wrong)
+    }
+}
+
+fn write_ranking(f: &mut fmt::Formatter<'_>, ranking: &[(&Word, usize)]) -> fmt::Result {
+    for (word, count) in ranking {
+        let plural = if *count == 1 { "" } else { "s" };
+        writeln!(f, "  {word} ({count} puzzle{plural})")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! Tags attached to the answers of a result, like "found" or "looked up", for
+//! tracking a player's progress.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameResult, Score, Word};
+
+/// The tag of answers the player found.
+pub const FOUND: &str = "found";
+
+/// Tags by word. Words without tags aren't stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Annotations {
+    tags: BTreeMap<Word, BTreeSet<String>>,
+}
+
+impl Annotations {
+    /// Tag `word`, returning whether it wasn't tagged with `tag` yet.
+    pub fn tag(&mut self, word: &str, tag: &str) -> bool {
+        self.tags
+            .entry(normalize(word))
+            .or_default()
+            .insert(tag.to_string())
+    }
+
+    /// Remove `tag` from `word`, returning whether it was tagged with it.
+    pub fn untag(&mut self, word: &str, tag: &str) -> bool {
+        let word = normalize(word);
+        let Some(tags) = self.tags.get_mut(&word) else {
+            return false;
+        };
+        let removed = tags.remove(tag);
+        if tags.is_empty() {
+            self.tags.remove(&word);
+        }
+        removed
+    }
+
+    pub fn has_tag(&self, word: &str, tag: &str) -> bool {
+        self.tags
+            .get(&normalize(word))
+            .is_some_and(|tags| tags.contains(tag))
+    }
+
+    /// The tags of `word`, sorted alphabetically.
+    pub fn tags(&self, word: &str) -> impl Iterator<Item = &str> {
+        self.tags
+            .get(&normalize(word))
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// The words tagged with `tag`, sorted alphabetically.
+    pub fn tagged<'s>(&'s self, tag: &'s str) -> impl Iterator<Item = &'s Word> {
+        self.tags
+            .iter()
+            .filter(move |(_, tags)| tags.contains(tag))
+            .map(|(word, _)| word)
+    }
+}
+
+// words are stored uppercased like our dictionary.
+fn normalize(word: &str) -> Word {
+    word.trim().to_uppercase()
+}
+
+/// The answers of a result with their tags.
+pub struct AnnotatedResult<'r, 'a> {
+    result: &'r GameResult<'a>,
+    annotations: Annotations,
+}
+
+impl<'r, 'a> AnnotatedResult<'r, 'a> {
+    pub fn new(result: &'r GameResult<'a>, annotations: Annotations) -> Self {
+        AnnotatedResult {
+            result,
+            annotations,
+        }
+    }
+
+    pub fn result(&self) -> &'r GameResult<'a> {
+        self.result
+    }
+
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+
+    pub fn into_annotations(self) -> Annotations {
+        self.annotations
+    }
+
+    /// Tag an answer, returning whether it wasn't tagged with `tag` yet.
+    pub fn tag(&mut self, word: &str, tag: &str) -> anyhow::Result<bool> {
+        let normalized = normalize(word);
+        anyhow::ensure!(
+            self.result
+                .entries()
+                .iter()
+                .any(|(answer, _)| **answer == normalized),
+            "{word} is not an answer"
+        );
+        Ok(self.annotations.tag(word, tag))
+    }
+
+    /// Remove `tag` from `word`, returning whether it was tagged with it.
+    pub fn untag(&mut self, word: &str, tag: &str) -> bool {
+        self.annotations.untag(word, tag)
+    }
+
+    /// The answers tagged with `tag`, in the result's order.
+    pub fn with_tag(&self, tag: &str) -> Vec<(&'a Word, Score)> {
+        self.entries_where(|word| self.annotations.has_tag(word, tag))
+    }
+
+    /// The answers not tagged with `tag`, in the result's order.
+    pub fn without_tag(&self, tag: &str) -> Vec<(&'a Word, Score)> {
+        self.entries_where(|word| !self.annotations.has_tag(word, tag))
+    }
+
+    /// The answers the player found.
+    pub fn found(&self) -> Vec<(&'a Word, Score)> {
+        self.with_tag(FOUND)
+    }
+
+    /// The answers the player didn't find yet.
+    pub fn remaining(&self) -> Vec<(&'a Word, Score)> {
+        self.without_tag(FOUND)
+    }
+
+    fn entries_where(&self, keep: impl Fn(&str) -> bool) -> Vec<(&'a Word, Score)> {
+        self.result
+            .entries()
+            .into_iter()
+            .filter(|(word, _)| keep(word))
+            .collect()
+    }
+}
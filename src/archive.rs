@@ -0,0 +1,219 @@
+//! Local store of official NYT puzzles and their answers.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    date::PuzzleDate,
+    fetch::HttpFetcher,
+    game::{Game, Letter, PuzzleMetadata, Rules, Word},
+    nyt::{NytClient, NytPuzzle},
+    scoring::{NytScoring, Scoring},
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchivedPuzzle {
+    pub id: u64,
+    pub print_date: PuzzleDate,
+    /// Missing in puzzles archived before editors were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    pub center_letter: Letter,
+    pub outer_letters: Vec<Letter>,
+    /// The official answers, uppercased like our dictionary.
+    pub answers: Vec<Word>,
+    pub pangrams: Vec<Word>,
+}
+
+impl ArchivedPuzzle {
+    pub fn game(&self) -> Game {
+        Game::new(self.center_letter, self.outer_letters.clone()).with_metadata(
+            PuzzleMetadata::new(self.print_date, self.id, self.editor.clone()),
+        )
+    }
+
+    pub fn is_pangram(&self, word: &str) -> bool {
+        self.pangrams.iter().any(|pangram| pangram == word)
+    }
+
+    /// Total points of all official answers.
+    pub fn total_points(&self) -> usize {
+        self.answers
+            .iter()
+            .map(|word| {
+                NytScoring
+                    .points(word, self.is_pangram(word), &Rules::SPELLING_BEE)
+                    .total()
+            })
+            .sum()
+    }
+}
+
+impl TryFrom<&NytPuzzle> for ArchivedPuzzle {
+    type Error = anyhow::Error;
+
+    fn try_from(puzzle: &NytPuzzle) -> Result<Self, Self::Error> {
+        let game = puzzle.game()?;
+        let mut letters = game.letters();
+        let center_letter = letters.next().context("puzzle has no letters")?;
+        let uppercase = |words: &[Word]| words.iter().map(|word| word.to_uppercase()).collect();
+        Ok(ArchivedPuzzle {
+            id: puzzle.id,
+            print_date: puzzle.print_date,
+            editor: puzzle.editor.clone(),
+            center_letter,
+            outer_letters: letters.collect(),
+            answers: uppercase(&puzzle.answers),
+            pangrams: uppercase(&puzzle.pangrams),
+        })
+    }
+}
+
+/// The words NYT has accepted as official answers, with how many puzzles
+/// accepted them.
+#[derive(Debug, Default)]
+pub struct AnswerCorpus {
+    counts: HashMap<Word, usize>,
+}
+
+impl AnswerCorpus {
+    pub fn new<'a>(puzzles: impl IntoIterator<Item = &'a ArchivedPuzzle>) -> AnswerCorpus {
+        let mut counts: HashMap<Word, usize> = HashMap::new();
+        for puzzle in puzzles {
+            for answer in &puzzle.answers {
+                *counts.entry(answer.clone()).or_default() += 1;
+            }
+        }
+        AnswerCorpus { counts }
+    }
+
+    /// How many archived puzzles had `word` as an official answer.
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.get(word).copied().unwrap_or(0)
+    }
+
+    /// The number of distinct official answers.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// Puzzles by publication date, stored as JSON Lines.
+pub struct Archive {
+    path: PathBuf,
+    puzzles: BTreeMap<PuzzleDate, ArchivedPuzzle>,
+}
+
+impl Archive {
+    /// Open the archive at `path`, which is empty if the file doesn't exist yet.
+    pub fn open(path: &Path) -> anyhow::Result<Archive> {
+        let mut puzzles = BTreeMap::new();
+        match File::open(path) {
+            Ok(file) => {
+                for (line_number, line) in BufReader::new(file).lines().enumerate() {
+                    let line =
+                        line.with_context(|| format!("failed to read {}", path.display()))?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let puzzle: ArchivedPuzzle =
+                        serde_json::from_str(&line).with_context(|| {
+                            format!(
+                                "invalid puzzle in line {} of {}",
+                                line_number + 1,
+                                path.display()
+                            )
+                        })?;
+                    puzzles.insert(puzzle.print_date, puzzle);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to open {}", path.display()));
+            }
+        }
+        Ok(Archive {
+            path: path.to_path_buf(),
+            puzzles,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    /// Add a puzzle, returning whether it wasn't archived yet.
+    pub fn insert(&mut self, puzzle: ArchivedPuzzle) -> bool {
+        self.puzzles.insert(puzzle.print_date, puzzle).is_none()
+    }
+
+    pub fn get(&self, date: PuzzleDate) -> Option<&ArchivedPuzzle> {
+        self.puzzles.get(&date)
+    }
+
+    /// Puzzles published between `from` and `to` (both inclusive), oldest first.
+    pub fn range(
+        &self,
+        from: Option<PuzzleDate>,
+        to: Option<PuzzleDate>,
+    ) -> impl Iterator<Item = &ArchivedPuzzle> {
+        self.puzzles
+            .values()
+            .filter(move |puzzle| from.is_none_or(|from| puzzle.print_date >= from))
+            .filter(move |puzzle| to.is_none_or(|to| puzzle.print_date <= to))
+    }
+
+    /// The puzzle of `date`, which is fetched from NYT and archived if it
+    /// isn't archived yet. NYT only publishes the current and previous puzzle.
+    pub fn get_or_fetch(
+        &mut self,
+        date: PuzzleDate,
+        client: &NytClient<impl HttpFetcher>,
+    ) -> anyhow::Result<&ArchivedPuzzle> {
+        if !self.puzzles.contains_key(&date) {
+            let current = PuzzleDate::current()?;
+            anyhow::ensure!(
+                date <= current,
+                "the puzzle of {date} isn't published yet, the current one is from {current}"
+            );
+            anyhow::ensure!(
+                date >= current.previous()?,
+                "the puzzle of {date} isn't archived, and NYT only publishes the puzzles of {} and {current}",
+                current.previous()?
+            );
+            for puzzle in client.puzzles()? {
+                self.insert(ArchivedPuzzle::try_from(&puzzle)?);
+            }
+            self.save()?;
+        }
+        self.get(date)
+            .with_context(|| format!("NYT doesn't publish a puzzle of {date} anymore"))
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let file = File::create(&self.path)
+            .with_context(|| format!("failed to create {}", self.path.display()))?;
+        let mut out = BufWriter::new(file);
+        for puzzle in self.puzzles.values() {
+            serde_json::to_writer(&mut out, puzzle)?;
+            writeln!(out)?;
+        }
+        out.flush()
+            .with_context(|| format!("failed to write {}", self.path.display()))
+    }
+}
@@ -0,0 +1,252 @@
+//! A small shareable image of a puzzle's hints, e.g. for a blog or Discord:
+//! the number of words, points and pangrams, without any answers.
+
+use std::{fmt, io, str::FromStr};
+
+use crate::{date::PuzzleDate, hints::Hints};
+
+const BACKGROUND: [u8; 3] = [0xf7, 0xda, 0x21];
+const FOREGROUND: [u8; 3] = [0x00, 0x00, 0x00];
+
+const SVG_WIDTH: usize = 240;
+const SVG_LINE_HEIGHT: usize = 28;
+const SVG_PADDING: usize = 20;
+
+// the PNG is drawn with a 5x7 pixel font, each pixel scaled to a square of this size.
+const PNG_SCALE: usize = 3;
+const PNG_PADDING: usize = 4 * PNG_SCALE;
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+// the space between glyphs and between lines.
+const GLYPH_SPACING: usize = 1;
+const LINE_SPACING: usize = 3;
+
+// the rows of each glyph from top to bottom, separated by `|`, with `#` for a set pixel.
+const GLYPHS: [(char, &str); 39] = [
+    ('A', " ### |#   #|#   #|#####|#   #|#   #|#   #"),
+    ('B', "#### |#   #|#   #|#### |#   #|#   #|#### "),
+    ('C', " ### |#   #|#    |#    |#    |#   #| ### "),
+    ('D', "#### |#   #|#   #|#   #|#   #|#   #|#### "),
+    ('E', "#####|#    |#    |#### |#    |#    |#####"),
+    ('F', "#####|#    |#    |#### |#    |#    |#    "),
+    ('G', " ### |#   #|#    |# ###|#   #|#   #| ####"),
+    ('H', "#   #|#   #|#   #|#####|#   #|#   #|#   #"),
+    ('I', " ### |  #  |  #  |  #  |  #  |  #  | ### "),
+    ('J', "  ###|   # |   # |   # |   # |#  # | ##  "),
+    ('K', "#   #|#  # |# #  |##   |# #  |#  # |#   #"),
+    ('L', "#    |#    |#    |#    |#    |#    |#####"),
+    ('M', "#   #|## ##|# # #|# # #|#   #|#   #|#   #"),
+    ('N', "#   #|#   #|##  #|# # #|#  ##|#   #|#   #"),
+    ('O', " ### |#   #|#   #|#   #|#   #|#   #| ### "),
+    ('P', "#### |#   #|#   #|#### |#    |#    |#    "),
+    ('Q', " ### |#   #|#   #|#   #|# # #|#  # | ## #"),
+    ('R', "#### |#   #|#   #|#### |# #  |#  # |#   #"),
+    ('S', " ####|#    |#    | ### |    #|    #|#### "),
+    ('T', "#####|  #  |  #  |  #  |  #  |  #  |  #  "),
+    ('U', "#   #|#   #|#   #|#   #|#   #|#   #| ### "),
+    ('V', "#   #|#   #|#   #|#   #|#   #| # # |  #  "),
+    ('W', "#   #|#   #|#   #|# # #|# # #|# # #| # # "),
+    ('X', "#   #|#   #| # # |  #  | # # |#   #|#   #"),
+    ('Y', "#   #|#   #| # # |  #  |  #  |  #  |  #  "),
+    ('Z', "#####|    #|   # |  #  | #   |#    |#####"),
+    ('0', " ### |#   #|#  ##|# # #|##  #|#   #| ### "),
+    ('1', "  #  | ##  |  #  |  #  |  #  |  #  | ### "),
+    ('2', " ### |#   #|    #|   # |  #  | #   |#####"),
+    ('3', "#####|   # |  #  |   # |    #|#   #| ### "),
+    ('4', "   # |  ## | # # |#  # |#####|   # |   # "),
+    ('5', "#####|#    |#### |    #|    #|#   #| ### "),
+    ('6', "  ## | #   |#    |#### |#   #|#   #| ### "),
+    ('7', "#####|    #|   # |  #  | #   | #   | #   "),
+    ('8', " ### |#   #|#   #| ### |#   #|#   #| ### "),
+    ('9', " ### |#   #|#   #| ####|    #|   # | ##  "),
+    ('-', "     |     |     |#####|     |     |     "),
+    (':', "     | ##  | ##  |     | ##  | ##  |     "),
+    (' ', ""),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BadgeFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+impl BadgeFormat {
+    pub const ALL: [BadgeFormat; 2] = [BadgeFormat::Svg, BadgeFormat::Png];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BadgeFormat::Svg => "svg",
+            BadgeFormat::Png => "png",
+        }
+    }
+}
+
+impl FromStr for BadgeFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BadgeFormat::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow::anyhow!("unknown badge format '{s}', expected svg or png"))
+    }
+}
+
+/// e.g. `svg`
+impl fmt::Display for BadgeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The counts of [`Hints::write_summary`], with the puzzle's date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    date: Option<PuzzleDate>,
+    word_count: usize,
+    total_points: usize,
+    pangram_count: usize,
+}
+
+impl Badge {
+    pub fn new(hints: &Hints, date: Option<PuzzleDate>) -> Badge {
+        Badge {
+            date,
+            word_count: hints.word_count(),
+            total_points: hints.total_points(),
+            pangram_count: hints.pangram_count(),
+        }
+    }
+
+    pub fn write(&self, format: BadgeFormat, out: &mut impl io::Write) -> io::Result<()> {
+        match format {
+            BadgeFormat::Svg => out.write_all(self.svg().as_bytes()),
+            BadgeFormat::Png => out.write_all(&self.png()),
+        }
+    }
+
+    pub fn svg(&self) -> String {
+        let lines = self.lines();
+        let height = 2 * SVG_PADDING + lines.len() * SVG_LINE_HEIGHT;
+        let mut svg = format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{height}" viewBox="0 0 {SVG_WIDTH} {height}">
+  <rect width="{SVG_WIDTH}" height="{height}" rx="12" fill="{}"/>
+"##,
+            hex(BACKGROUND)
+        );
+        for (i, line) in lines.iter().enumerate() {
+            // the first line is the title.
+            let weight = if i == 0 { "bold" } else { "normal" };
+            svg.push_str(&format!(
+                r#"  <text x="{SVG_PADDING}" y="{}" font-family="sans-serif" font-size="20" font-weight="{weight}" fill="{}">{line}</text>
+"#,
+                SVG_PADDING + (i + 1) * SVG_LINE_HEIGHT - 8,
+                hex(FOREGROUND)
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn png(&self) -> Vec<u8> {
+        let lines = self.lines();
+        let columns = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width =
+            2 * PNG_PADDING + columns * (GLYPH_WIDTH + GLYPH_SPACING) * PNG_SCALE - PNG_SCALE;
+        let height = 2 * PNG_PADDING + lines.len() * (GLYPH_HEIGHT + LINE_SPACING) * PNG_SCALE
+            - LINE_SPACING * PNG_SCALE;
+
+        let mut pixels = vec![BACKGROUND; width * height];
+        for (row, line) in lines.iter().enumerate() {
+            let top = PNG_PADDING + row * (GLYPH_HEIGHT + LINE_SPACING) * PNG_SCALE;
+            for (column, c) in line.chars().enumerate() {
+                let left = PNG_PADDING + column * (GLYPH_WIDTH + GLYPH_SPACING) * PNG_SCALE;
+                for (y, row) in glyph(c).split('|').enumerate() {
+                    for (x, pixel) in row.chars().enumerate() {
+                        if pixel != '#' {
+                            continue;
+                        }
+                        for dy in 0..PNG_SCALE {
+                            let start = (top + y * PNG_SCALE + dy) * width + left + x * PNG_SCALE;
+                            pixels[start..start + PNG_SCALE].fill(FOREGROUND);
+                        }
+                    }
+                }
+            }
+        }
+        encode_png(width, height, &pixels)
+    }
+
+    // e.g. `SPELLING BEE`, `2024-06-01`, `WORDS: 25`, `POINTS: 78`, `PANGRAMS: 1`.
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec!["SPELLING BEE".to_string()];
+        lines.extend(self.date.map(|date| date.to_string()));
+        lines.push(format!("WORDS: {}", self.word_count));
+        lines.push(format!("POINTS: {}", self.total_points));
+        lines.push(format!("PANGRAMS: {}", self.pangram_count));
+        lines
+    }
+}
+
+// characters without a glyph are left blank.
+fn glyph(c: char) -> &'static str {
+    GLYPHS
+        .iter()
+        .find(|(glyph, _)| *glyph == c.to_ascii_uppercase())
+        .map_or("", |(_, rows)| rows)
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// An 8-bit RGB PNG of `pixels`, row by row.
+fn encode_png(width: usize, height: usize, pixels: &[[u8; 3]]) -> Vec<u8> {
+    // each row is prefixed with its filter type, 0 for none.
+    let mut data = Vec::with_capacity(height * (1 + 3 * width));
+    for row in pixels.chunks(width) {
+        data.push(0);
+        data.extend(row.iter().flatten());
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // bit depth 8, color type RGB, default compression, filtering and no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(
+        &mut png,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&data, 9),
+    );
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+// the CRC-32 of PNG chunks, bit by bit since badges are tiny.
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    !bytes.into_iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
@@ -0,0 +1,100 @@
+use std::{
+    io::Write,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use nyt_spelling_bee_solver::{
+    dictionary::Dictionary, game::PuzzleSpec, registry::RegisteredStrategy, scoring::NytScoring,
+};
+
+/// Durations of repeated runs of one step.
+#[derive(Default)]
+struct Timings(Vec<Duration>);
+
+impl Timings {
+    fn mean(&self) -> Duration {
+        let runs = u32::try_from(self.0.len()).unwrap_or(u32::MAX).max(1);
+        self.0.iter().sum::<Duration>() / runs
+    }
+
+    fn min(&self) -> Duration {
+        self.0.iter().copied().min().unwrap_or_default()
+    }
+
+    fn max(&self) -> Duration {
+        self.0.iter().copied().max().unwrap_or_default()
+    }
+}
+
+/// How long building a strategy's index and solving with it took.
+pub struct StrategyTimings {
+    name: &'static str,
+    build: Timings,
+    solve: Timings,
+}
+
+/// Build each strategy's index and solve `puzzle` with it `repeat` times.
+pub fn run(
+    strategies: &[&RegisteredStrategy],
+    dict: &Dictionary,
+    puzzle: &PuzzleSpec,
+    repeat: usize,
+    threads: usize,
+) -> anyhow::Result<Vec<StrategyTimings>> {
+    strategies
+        .iter()
+        .map(|strategy| {
+            let mut timings = StrategyTimings {
+                name: strategy.name,
+                build: Timings::default(),
+                solve: Timings::default(),
+            };
+            for _ in 0..repeat {
+                let start = Instant::now();
+                let solver = (strategy.build)(dict, threads, Arc::new(NytScoring))?;
+                timings.build.0.push(start.elapsed());
+
+                let start = Instant::now();
+                solver.solve(puzzle)?;
+                timings.solve.0.push(start.elapsed());
+            }
+            log::info!("benchmarked {}", strategy.name);
+            Ok(timings)
+        })
+        .collect()
+}
+
+/// A table with the mean, minimum and maximum durations of each step.
+pub fn write_table(timings: &[StrategyTimings], out: &mut impl Write) -> anyhow::Result<()> {
+    let width = timings
+        .iter()
+        .map(|timings| timings.name.len())
+        .chain(["strategy".len()])
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        out,
+        "{:<width$}  {:>10} {:>10} {:>10}  {:>10} {:>10} {:>10}",
+        "strategy", "build mean", "min", "max", "solve mean", "min", "max"
+    )?;
+    for timings in timings {
+        writeln!(
+            out,
+            "{:<width$}  {:>10} {:>10} {:>10}  {:>10} {:>10} {:>10}",
+            timings.name,
+            millis(timings.build.mean()),
+            millis(timings.build.min()),
+            millis(timings.build.max()),
+            millis(timings.solve.mean()),
+            millis(timings.solve.min()),
+            millis(timings.solve.max()),
+        )?;
+    }
+    Ok(())
+}
+
+// e.g. `12.345ms`.
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
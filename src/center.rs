@@ -0,0 +1,63 @@
+//! Choosing the center letter of a custom puzzle by solving it once with each
+//! of its letters as the center.
+
+use std::fmt;
+
+use crate::game::{Game, GameSolver, Letter, Points, PuzzleSpec, SolveStrategy};
+
+/// The solution of a puzzle with `center` as its center letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CenterOption {
+    pub center: Letter,
+    pub answers: usize,
+    pub total_points: Points,
+    pub pangrams: usize,
+}
+
+/// Solve the puzzle of `letters` with each of them as the center, reusing
+/// the solver's index. The options are sorted by the number of answers, most
+/// first, then by center letter.
+pub fn suggest_centers<'a, S>(
+    solver: &GameSolver<S>,
+    letters: &[Letter],
+) -> anyhow::Result<Vec<CenterOption>>
+where
+    S: SolveStrategy<'a> + Send + Sync,
+{
+    let puzzles: Vec<PuzzleSpec> = letters
+        .iter()
+        .map(|&center| {
+            let others = letters.iter().copied().filter(|&l| l != center).collect();
+            PuzzleSpec::from(Game::new(center, others))
+        })
+        .collect();
+
+    let mut options = letters
+        .iter()
+        .zip(solver.solve_many(&puzzles))
+        .map(|(&center, result)| {
+            let result = result?;
+            let entries = result.entries();
+            Ok(CenterOption {
+                center,
+                answers: entries.len(),
+                total_points: result.total_points(),
+                pangrams: entries.iter().filter(|(_, score)| score.is_pangram).count(),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    options.sort_by(|a, b| b.answers.cmp(&a.answers).then(a.center.cmp(&b.center)));
+    Ok(options)
+}
+
+/// e.g. `C: 25 answers, 78 points, 1 pangram`.
+impl fmt::Display for CenterOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.pangrams == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{}: {} answers, {} points, {} pangram{plural}",
+            self.center, self.answers, self.total_points, self.pangrams
+        )
+    }
+}
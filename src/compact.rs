@@ -0,0 +1,84 @@
+//! Compact binary word list format.
+//!
+//! Layout: the magic bytes, followed by all words in sorted order. Each word
+//! is front-coded against its predecessor as `[header][suffix bytes]`, which
+//! exploits the long common prefixes of neighbouring words in a sorted list.
+//! The header packs the shared prefix length into the high and the suffix
+//! length into the low nibble; a nibble of `0xF` means the actual length
+//! follows in an extra byte.
+
+use anyhow::Context;
+
+const MAGIC: &[u8; 8] = b"BEEDICT1";
+const ESCAPE: u8 = 0xF;
+
+pub fn is_compact(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn encode<'a>(words: impl IntoIterator<Item = &'a str>) -> anyhow::Result<Vec<u8>> {
+    let mut words: Vec<&str> = words.into_iter().collect();
+    words.sort_unstable();
+    words.dedup();
+
+    let mut bytes = MAGIC.to_vec();
+    let mut prev: &[u8] = &[];
+    for word in words {
+        let word = word.as_bytes();
+        let shared = prev
+            .iter()
+            .zip(word)
+            .take_while(|(a, b)| a == b)
+            .count()
+            .min(u8::MAX as usize);
+        let suffix = &word[shared..];
+        let suffix_len = u8::try_from(suffix.len())
+            .with_context(|| format!("word '{}' is too long", String::from_utf8_lossy(word)))?;
+        let shared = shared as u8;
+
+        bytes.push((shared.min(ESCAPE) << 4) | suffix_len.min(ESCAPE));
+        if shared >= ESCAPE {
+            bytes.push(shared);
+        }
+        if suffix_len >= ESCAPE {
+            bytes.push(suffix_len);
+        }
+        bytes.extend_from_slice(suffix);
+        prev = word;
+    }
+    Ok(bytes)
+}
+
+pub fn decode(bytes: &[u8]) -> anyhow::Result<Vec<String>> {
+    let mut rest = bytes
+        .strip_prefix(MAGIC)
+        .context("missing compact dictionary header")?;
+
+    let mut words = Vec::new();
+    let mut word: Vec<u8> = Vec::new();
+    while let Some((&header, tail)) = rest.split_first() {
+        rest = tail;
+        let shared = read_len(header >> 4, &mut rest)?;
+        let suffix_len = read_len(header & ESCAPE, &mut rest)?;
+        anyhow::ensure!(
+            shared <= word.len() && suffix_len <= rest.len(),
+            "corrupt compact dictionary entry after {} words",
+            words.len()
+        );
+        word.truncate(shared);
+        word.extend_from_slice(&rest[..suffix_len]);
+        words.push(String::from_utf8(word.clone()).context("word is not valid UTF-8")?);
+        rest = &rest[suffix_len..];
+    }
+
+    Ok(words)
+}
+
+fn read_len(nibble: u8, rest: &mut &[u8]) -> anyhow::Result<usize> {
+    if nibble < ESCAPE {
+        return Ok(nibble as usize);
+    }
+    let (&len, tail) = rest.split_first().context("truncated compact dictionary")?;
+    *rest = tail;
+    Ok(len as usize)
+}
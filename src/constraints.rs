@@ -0,0 +1,145 @@
+//! Requirements on generated puzzles, composed from simple predicates. Those on
+//! the letters alone rule out pangrams to build a puzzle around before any
+//! puzzle is solved, the others are checked on the solution.
+
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    difficulty::Rating,
+    game::{GameResult, Letter, PuzzleSpec},
+    validate::ValidationOptions,
+};
+
+const VOWELS: &str = "AEIOU";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    /// None of the letters may be among these, like `S` in NYT puzzles.
+    ExcludeLetters(Vec<Letter>),
+    /// The number of vowels (A, E, I, O and U) among the seven letters.
+    Vowels(RangeInclusive<usize>),
+    /// The number of answers.
+    Answers(RangeInclusive<usize>),
+    /// A pangram uses each letter exactly once.
+    PerfectPangram,
+    /// The estimated difficulty, without word frequencies.
+    Difficulty(Rating),
+}
+
+impl Constraint {
+    /// Whether a puzzle built around `pangram`, with the distinct `letters`,
+    /// can meet the constraint.
+    pub fn allows_pangram(&self, pangram: &str, letters: &[Letter]) -> bool {
+        match self {
+            Constraint::ExcludeLetters(excluded) => {
+                !letters.iter().any(|letter| excluded.contains(letter))
+            }
+            Constraint::Vowels(range) => range.contains(&vowels(letters)),
+            Constraint::PerfectPangram => pangram.chars().count() == letters.len(),
+            Constraint::Answers(_) | Constraint::Difficulty(_) => true,
+        }
+    }
+
+    pub fn is_satisfied_by(&self, puzzle: &PuzzleSpec, result: &GameResult) -> bool {
+        let letters: Vec<Letter> = puzzle.game().letters().collect();
+        match self {
+            Constraint::ExcludeLetters(_) | Constraint::Vowels(_) => {
+                self.allows_pangram("", &letters)
+            }
+            Constraint::Answers(range) => range.contains(&result.entries().len()),
+            Constraint::PerfectPangram => result
+                .entries()
+                .iter()
+                .any(|(word, score)| score.is_pangram && word.chars().count() == letters.len()),
+            Constraint::Difficulty(rating) => result.difficulty(None).rating == *rating,
+        }
+    }
+}
+
+/// e.g. `no letters S`, `1-2 vowels`, `20-80 answers`, `perfect pangram`,
+/// `medium difficulty`
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::ExcludeLetters(letters) => {
+                let letters: String = letters.iter().map(|&letter| char::from(letter)).collect();
+                write!(f, "no letters {letters}")
+            }
+            Constraint::Vowels(range) => write!(f, "{}-{} vowels", range.start(), range.end()),
+            Constraint::Answers(range) => write!(f, "{}-{} answers", range.start(), range.end()),
+            Constraint::PerfectPangram => write!(f, "perfect pangram"),
+            Constraint::Difficulty(rating) => write!(f, "{rating} difficulty"),
+        }
+    }
+}
+
+/// All constraints a generated puzzle has to meet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints(Vec<Constraint>);
+
+impl Constraints {
+    pub fn new() -> Self {
+        Constraints::default()
+    }
+
+    pub fn with(mut self, constraint: Constraint) -> Self {
+        self.0.push(constraint);
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Constraint> {
+        self.0.iter()
+    }
+
+    /// Whether a puzzle built around `pangram` can meet all constraints.
+    pub fn allows_pangram(&self, pangram: &str, letters: &[Letter]) -> bool {
+        self.0
+            .iter()
+            .all(|constraint| constraint.allows_pangram(pangram, letters))
+    }
+
+    /// How many of the constraints the solution meets.
+    pub fn satisfied(&self, puzzle: &PuzzleSpec, result: &GameResult) -> usize {
+        self.0
+            .iter()
+            .filter(|constraint| constraint.is_satisfied_by(puzzle, result))
+            .count()
+    }
+
+    pub fn are_satisfied_by(&self, puzzle: &PuzzleSpec, result: &GameResult) -> bool {
+        self.satisfied(puzzle, result) == self.0.len()
+    }
+}
+
+/// The constraints of NYT puzzles, see [`ValidationOptions::default`].
+impl From<&ValidationOptions> for Constraints {
+    fn from(options: &ValidationOptions) -> Self {
+        Constraints::new()
+            .with(Constraint::ExcludeLetters(
+                options.forbidden_letters.clone(),
+            ))
+            .with(Constraint::Answers(
+                options.min_answers..=options.max_answers,
+            ))
+    }
+}
+
+/// e.g. `no letters S, 20-80 answers`
+impl fmt::Display for Constraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, constraint) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{constraint}")?;
+        }
+        Ok(())
+    }
+}
+
+fn vowels(letters: &[Letter]) -> usize {
+    letters
+        .iter()
+        .filter(|&&letter| VOWELS.contains(char::from(letter)))
+        .count()
+}
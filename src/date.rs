@@ -0,0 +1,64 @@
+//! Dates of puzzles.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Context;
+use jiff::{Timestamp, ToSpan, civil::Date, tz::TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// Time zone NYT publishes puzzles in.
+const PUBLISHING_TIME_ZONE: &str = "America/New_York";
+/// Hour of the day (in the publishing time zone) at which the next puzzle is released.
+const ROLLOVER_HOUR: i8 = 3;
+
+/// The date a puzzle is published for, e.g. `2024-06-01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PuzzleDate(Date);
+
+impl PuzzleDate {
+    /// The date of the puzzle that is current at `now`. Puzzles roll over at
+    /// 3 a.m. US Eastern time, so before that it is still the previous day's.
+    pub fn at(now: Timestamp) -> anyhow::Result<PuzzleDate> {
+        let time_zone = TimeZone::get(PUBLISHING_TIME_ZONE)
+            .with_context(|| format!("failed to load the {PUBLISHING_TIME_ZONE} time zone"))?;
+        let local = now.to_zoned(time_zone).datetime();
+        let date = local
+            .checked_sub(ROLLOVER_HOUR.hours())
+            .context("date out of range")?
+            .date();
+        Ok(PuzzleDate(date))
+    }
+
+    /// The date of the puzzle that is current right now.
+    pub fn current() -> anyhow::Result<PuzzleDate> {
+        PuzzleDate::at(Timestamp::now())
+    }
+
+    /// The weekday, e.g. `Thursday`.
+    pub fn weekday(self) -> String {
+        self.0.strftime("%A").to_string()
+    }
+
+    /// The date of the previous day's puzzle.
+    pub fn previous(self) -> anyhow::Result<PuzzleDate> {
+        Ok(PuzzleDate(self.0.yesterday().context("date out of range")?))
+    }
+}
+
+impl FromStr for PuzzleDate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let date: Date = s
+            .parse()
+            .with_context(|| format!("expected a date like 2024-06-01, got '{s}'"))?;
+        Ok(PuzzleDate(date))
+    }
+}
+
+impl fmt::Display for PuzzleDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,49 @@
+//! Word definitions from the free dictionary API (dictionaryapi.dev).
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::fetch::{HttpFetcher, HttpRequest};
+
+const DEFINITIONS_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en/";
+
+#[derive(Deserialize)]
+struct Entry {
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meaning {
+    part_of_speech: String,
+    definitions: Vec<Definition>,
+}
+
+#[derive(Deserialize)]
+struct Definition {
+    definition: String,
+}
+
+/// The first definition of `word`, e.g. `(noun) One of the flat surfaces of a cut gem.`,
+/// or none if the API doesn't know the word.
+pub fn lookup(http: &impl HttpFetcher, word: &str) -> anyhow::Result<Option<String>> {
+    let url = format!("{DEFINITIONS_URL}{}", word.to_lowercase());
+    let response = http.fetch(&HttpRequest::get(&url))?;
+    // not found.
+    if response.status == 404 {
+        return Ok(None);
+    }
+    let body = response.error_for_status(&url)?.body;
+    let entries: Vec<Entry> = serde_json::from_str(&body).context("failed to parse definitions")?;
+
+    Ok(entries
+        .into_iter()
+        .flat_map(|entry| entry.meanings)
+        .find_map(|meaning| {
+            let definition = meaning.definitions.into_iter().next()?;
+            Some(format!(
+                "({}) {}",
+                meaning.part_of_speech, definition.definition
+            ))
+        }))
+}
@@ -0,0 +1,861 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use anyhow::Context;
+use jiff::Timestamp;
+use memmap2::Mmap;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Serialize;
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::{
+    compact,
+    fetch::HttpFetcher,
+    game::{Letter, Rules, Word},
+    language::Language,
+};
+
+/// The word list [`Dictionary::scrape`] downloads.
+pub const WORD_LIST_URL: &str =
+    "https://raw.githubusercontent.com/rressler/data_raw_courses/main/scrabble_words.txt";
+
+// with the `parallel` feature, word lists are parsed in chunks of about this
+// many bytes at once.
+#[cfg(feature = "parallel")]
+const CHUNK_SIZE: usize = 256 * 1024;
+
+// downloaded word lists are read and parsed in batches of about this many
+// bytes, instead of holding the whole body in memory.
+const BATCH_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Default)]
+pub struct Dictionary {
+    words: HashSet<Word>,
+    overlay: WordOverlay,
+    metadata: DictionaryMetadata,
+}
+
+/// Words to treat as valid or invalid on top of a dictionary's word list,
+/// without changing the list itself, e.g. words NYT is known to accept.
+/// Excluded words win over included ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordOverlay {
+    included: HashSet<Word>,
+    excluded: HashSet<Word>,
+}
+
+impl WordOverlay {
+    pub fn new() -> Self {
+        WordOverlay::default()
+    }
+
+    /// Treat `word`, uppercased, as valid even if the word list lacks it.
+    pub fn include(&mut self, word: &str) {
+        self.included.insert(word.trim().to_uppercase());
+    }
+
+    /// Treat `word`, uppercased, as invalid even if the word list has it.
+    pub fn exclude(&mut self, word: &str) {
+        self.excluded.insert(word.trim().to_uppercase());
+    }
+
+    /// Include the words of a file, see [`read_word_file`].
+    pub fn include_file(mut self, path: &Path) -> anyhow::Result<Self> {
+        self.included.extend(read_word_file(path)?);
+        Ok(self)
+    }
+
+    /// Exclude the words of a file, see [`read_word_file`].
+    pub fn exclude_file(mut self, path: &Path) -> anyhow::Result<Self> {
+        self.excluded.extend(read_word_file(path)?);
+        Ok(self)
+    }
+
+    pub fn included(&self) -> impl Iterator<Item = &Word> {
+        self.included.iter()
+    }
+
+    pub fn excluded(&self) -> impl Iterator<Item = &Word> {
+        self.excluded.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.included.is_empty() && self.excluded.is_empty()
+    }
+
+    /// `words` without the excluded words, followed by the included ones, like
+    /// [`Dictionary::iter`] for words that aren't loaded into a dictionary.
+    /// Included words may occur twice.
+    pub fn apply<'a>(
+        &'a self,
+        words: impl Iterator<Item = Word> + 'a,
+    ) -> impl Iterator<Item = Word> + 'a {
+        let excluded = &self.excluded;
+        words.filter(move |word| !excluded.contains(word)).chain(
+            self.included
+                .iter()
+                .filter(move |word| !excluded.contains(*word))
+                .cloned(),
+        )
+    }
+}
+
+/// Where the words of a dictionary came from, so that consumers of a solution
+/// know which word list produced it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DictionaryMetadata {
+    /// The URL or path of the word list, `None` for dictionaries built in memory.
+    pub source: Option<String>,
+    /// When the word list was downloaded, `None` for local files.
+    pub fetched_at: Option<Timestamp>,
+    /// Under which terms the word list may be used, if known.
+    pub license: Option<String>,
+    /// The number of words, including later insertions and removals.
+    pub word_count: usize,
+}
+
+/// Membership queries shared by the dictionary representations.
+pub trait WordList {
+    fn contains(&self, word: &str) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl WordList for Dictionary {
+    fn contains(&self, word: &str) -> bool {
+        Dictionary::contains(self, word)
+    }
+
+    fn len(&self) -> usize {
+        Dictionary::len(self)
+    }
+}
+
+/// Lazy filters over words, e.g.
+/// `dict.iter().with_min_len(4).filter_letters(&letters)`.
+pub trait WordFilters<'a>: Iterator<Item = &'a Word> + Sized {
+    /// The words consisting only of the `allowed` letters.
+    fn filter_letters(self, allowed: &[Letter]) -> impl Iterator<Item = &'a Word> {
+        self.filter(move |word| {
+            word.chars()
+                .all(|c| Letter::new(c).is_ok_and(|letter| allowed.contains(&letter)))
+        })
+    }
+
+    /// The words with at least `min_len` letters.
+    fn with_min_len(self, min_len: usize) -> impl Iterator<Item = &'a Word> {
+        self.filter(move |word| word.chars().count() >= min_len)
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a Word>> WordFilters<'a> for I {}
+
+/// Controls how raw word lists are normalized. Words are always uppercased.
+/// [`Language::parse_options`] are the defaults for other languages.
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions {
+    /// Language of the word list, which decides how its other letters are spelled.
+    pub language: Language,
+    /// Remove apostrophes (`don't` -> `DONT`) instead of dropping the word.
+    pub strip_apostrophes: bool,
+    /// Remove diacritics (`café` -> `CAFE`) instead of dropping the word.
+    pub strip_diacritics: bool,
+    /// Drop capitalized words like `Paris`, which are most likely proper nouns.
+    pub drop_proper_nouns: bool,
+    /// Drop all uppercase words without vowels like `HTML`, which are most
+    /// likely abbreviations. `Y` counts as a vowel, to keep words like `GYPSY`.
+    /// Only applies to lists with lowercase words, like `drop_roman_numerals`.
+    pub drop_abbreviations: bool,
+    /// Drop Roman numerals like `XLIV`. Like abbreviations, they are only told
+    /// apart from words in lists with lowercase words, so that all uppercase
+    /// lists like the scraped one keep words like `PSST` and `CRWTH`.
+    pub drop_roman_numerals: bool,
+    /// Drop words with fewer letters, which can't be answers. The minimum
+    /// length of the rules (see [`Rules::min_length`]), 4 by default.
+    pub min_length: usize,
+}
+
+impl ParseOptions {
+    // the options for a list with or without lowercase words, which the case
+    // heuristics only apply to, the same way capitalization marks proper nouns.
+    fn for_case(mut self, mixed_case: bool) -> Self {
+        if !mixed_case {
+            self.drop_abbreviations = false;
+            self.drop_roman_numerals = false;
+        }
+        self
+    }
+}
+
+// whether a list has lowercase words, e.g. `/usr/share/dict/words`.
+fn has_lowercase(text: &str) -> bool {
+    text.chars().any(char::is_lowercase)
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            language: Language::English,
+            strip_apostrophes: false,
+            strip_diacritics: false,
+            drop_proper_nouns: true,
+            drop_abbreviations: true,
+            drop_roman_numerals: true,
+            min_length: Rules::SPELLING_BEE.min_length,
+        }
+    }
+}
+
+/// How many lines of a raw word list were kept or dropped by each filter.
+#[derive(Debug, Default)]
+pub struct ParseStats {
+    pub kept: usize,
+    pub empty: usize,
+    pub proper_noun: usize,
+    pub abbreviation: usize,
+    pub roman_numeral: usize,
+    pub non_alphabetic: usize,
+    pub too_short: usize,
+    pub duplicate: usize,
+}
+
+impl ParseStats {
+    #[cfg(feature = "parallel")]
+    fn merge(&mut self, other: ParseStats) {
+        self.kept += other.kept;
+        self.empty += other.empty;
+        self.proper_noun += other.proper_noun;
+        self.abbreviation += other.abbreviation;
+        self.roman_numeral += other.roman_numeral;
+        self.non_alphabetic += other.non_alphabetic;
+        self.too_short += other.too_short;
+        self.duplicate += other.duplicate;
+    }
+}
+
+impl fmt::Display for ParseStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "kept {} words, dropped {} empty, {} proper noun, {} abbreviation, {} Roman numeral, {} non-alphabetic, {} too short and {} duplicate lines",
+            self.kept,
+            self.empty,
+            self.proper_noun,
+            self.abbreviation,
+            self.roman_numeral,
+            self.non_alphabetic,
+            self.too_short,
+            self.duplicate
+        )
+    }
+}
+
+/// Words only contained in one of two dictionaries, sorted alphabetically.
+pub struct DictionaryDiff<'a> {
+    pub added: Vec<&'a Word>,
+    pub removed: Vec<&'a Word>,
+}
+
+impl Dictionary {
+    /// The words in arbitrary order, with those of the overlay.
+    pub fn iter(&self) -> impl Iterator<Item = &Word> {
+        let excluded = &self.overlay.excluded;
+        self.words
+            .iter()
+            .filter(move |word| excluded.is_empty() || !excluded.contains(*word))
+            .chain(self.added())
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &Word> {
+        let excluded = &self.overlay.excluded;
+        self.words
+            .par_iter()
+            .filter(move |word| excluded.is_empty() || !excluded.contains(*word))
+            .chain(self.added().collect::<Vec<_>>())
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        (self.words.contains(word) || self.overlay.included.contains(word))
+            && !self.overlay.excluded.contains(word)
+    }
+
+    pub fn len(&self) -> usize {
+        let hidden = self
+            .overlay
+            .excluded
+            .iter()
+            .filter(|word| self.words.contains(*word))
+            .count();
+        self.words.len() - hidden + self.added().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // the included words missing from the word list.
+    fn added(&self) -> impl Iterator<Item = &Word> {
+        self.overlay
+            .included
+            .iter()
+            .filter(|word| !self.words.contains(*word) && !self.overlay.excluded.contains(*word))
+    }
+
+    pub fn overlay(&self) -> &WordOverlay {
+        &self.overlay
+    }
+
+    /// Replace the words treated as valid or invalid on top of the word list.
+    pub fn with_overlay(mut self, overlay: WordOverlay) -> Dictionary {
+        self.overlay = overlay;
+        self
+    }
+
+    /// The words consisting only of the `allowed` letters, see [`WordFilters`].
+    pub fn filter_letters(&self, allowed: &[Letter]) -> impl Iterator<Item = &Word> {
+        self.iter().filter_letters(allowed)
+    }
+
+    /// The words with at least `min_len` letters, see [`WordFilters`].
+    pub fn with_min_len(&self, min_len: usize) -> impl Iterator<Item = &Word> {
+        self.iter().with_min_len(min_len)
+    }
+
+    /// Keep only the words of the word list for which `keep` returns true.
+    pub fn retain(&mut self, keep: impl FnMut(&Word) -> bool) {
+        self.words.retain(keep);
+    }
+
+    /// Add `word`, uppercased, returning whether it wasn't contained yet. An
+    /// excluded word stops being excluded.
+    pub fn insert(&mut self, word: &str) -> bool {
+        let word = word.trim().to_uppercase();
+        let contained = self.contains(&word);
+        self.overlay.excluded.remove(&word);
+        self.words.insert(word);
+        !contained
+    }
+
+    /// Remove `word`, returning whether it was contained. An included word
+    /// stops being included.
+    pub fn remove(&mut self, word: &str) -> bool {
+        let word = word.trim().to_uppercase();
+        let contained = self.contains(&word);
+        self.overlay.included.remove(&word);
+        self.words.remove(&word);
+        contained
+    }
+
+    /// Where the words came from.
+    pub fn metadata(&self) -> DictionaryMetadata {
+        DictionaryMetadata {
+            word_count: self.len(),
+            ..self.metadata.clone()
+        }
+    }
+
+    /// Replace where the words came from, e.g. with the URL and license of a
+    /// downloaded word list. The word count is ignored.
+    pub fn with_metadata(mut self, metadata: DictionaryMetadata) -> Dictionary {
+        self.metadata = metadata;
+        self
+    }
+
+    /// The words added and removed when going from this dictionary to `new`.
+    pub fn diff<'a>(&'a self, new: &'a Dictionary) -> DictionaryDiff<'a> {
+        let mut added: Vec<_> = new.iter().filter(|word| !self.contains(word)).collect();
+        let mut removed: Vec<_> = self.iter().filter(|word| !new.contains(word)).collect();
+        added.sort_unstable();
+        removed.sort_unstable();
+        DictionaryDiff { added, removed }
+    }
+
+    pub fn scrape(http: &impl HttpFetcher, options: ParseOptions) -> anyhow::Result<Dictionary> {
+        anyhow::ensure!(
+            options.language == Language::English,
+            "there is no word list to download for language {}, use a local one",
+            options.language
+        );
+        let body = http.get_reader(WORD_LIST_URL)?;
+        let dict = Self::parse_stream(BufReader::new(body), options, BATCH_SIZE)
+            .with_context(|| format!("failed to read {WORD_LIST_URL}"))?;
+        Ok(dict.with_metadata(DictionaryMetadata {
+            source: Some(WORD_LIST_URL.to_string()),
+            fetched_at: Some(Timestamp::now()),
+            ..DictionaryMetadata::default()
+        }))
+    }
+
+    /// Load a dictionary from a file, which is either a plain word list or
+    /// in the compact binary format (see `solver dict compact`).
+    pub fn load(path: &Path, options: ParseOptions) -> anyhow::Result<Dictionary> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        // SAFETY: the mapping is only read during this function, and we accept
+        // garbage results if another process truncates the file meanwhile.
+        let bytes = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("failed to memory-map {}", path.display()))?;
+
+        let dict = if compact::is_compact(&bytes) {
+            let words = compact::decode(&bytes)
+                .with_context(|| format!("failed to decode {}", path.display()))?;
+            words.into_iter().collect()
+        } else {
+            let text = std::str::from_utf8(&bytes)
+                .with_context(|| format!("{} is not valid UTF-8", path.display()))?;
+            // some editors start UTF-8 files with a byte order mark.
+            Self::parse(text.trim_start_matches('\u{feff}'), options)
+        };
+        Ok(dict.with_metadata(DictionaryMetadata {
+            source: Some(path.display().to_string()),
+            ..DictionaryMetadata::default()
+        }))
+    }
+
+    /// Parse a raw word list that may not be valid UTF-8, dropping the words
+    /// with invalid bytes as non-alphabetic.
+    pub fn parse_lossy(bytes: &[u8], options: ParseOptions) -> Dictionary {
+        let text = String::from_utf8_lossy(bytes);
+        Self::parse(text.trim_start_matches('\u{feff}'), options)
+    }
+
+    fn parse(text: &str, options: ParseOptions) -> Dictionary {
+        let mut stats = ParseStats::default();
+        let mut words = HashSet::new();
+        parse_into(
+            text,
+            options.for_case(has_lowercase(text)),
+            &mut words,
+            &mut stats,
+        );
+
+        log::info!("parsed word list: {stats}");
+        Dictionary {
+            words,
+            ..Dictionary::default()
+        }
+    }
+
+    /// Parse a word list batch of about `batch_size` bytes by batch as it is
+    /// read, keeping the same words as [`parse_lossy`](Self::parse_lossy).
+    fn parse_stream(
+        mut reader: impl BufRead,
+        options: ParseOptions,
+        batch_size: usize,
+    ) -> anyhow::Result<Dictionary> {
+        let mut stats = ParseStats::default();
+        let mut words = HashSet::new();
+        let mut batch = Vec::with_capacity(batch_size);
+        // decided by the batches read so far.
+        let mut mixed_case = false;
+        loop {
+            batch.clear();
+            (&mut reader)
+                .take(batch_size as u64)
+                .read_to_end(&mut batch)?;
+            // complete the last line, which the batch may have cut off.
+            reader.read_until(b'\n', &mut batch)?;
+            if batch.is_empty() {
+                break;
+            }
+            let text = String::from_utf8_lossy(&batch);
+            if !mixed_case && has_lowercase(&text) {
+                mixed_case = true;
+                // the words of the all uppercase batches before are only now
+                // told apart from abbreviations and Roman numerals.
+                drop_case_heuristics(&mut words, options, &mut stats);
+            }
+            parse_into(&text, options.for_case(mixed_case), &mut words, &mut stats);
+        }
+
+        log::info!("parsed word list: {stats}");
+        Ok(Dictionary {
+            words,
+            ..Dictionary::default()
+        })
+    }
+}
+
+/// Drop the kept `words` that `parse_line` drops from lists with lowercase
+/// words, as abbreviations or Roman numerals. Their duplicates stay counted as
+/// such in `stats`.
+fn drop_case_heuristics(words: &mut HashSet<Word>, options: ParseOptions, stats: &mut ParseStats) {
+    words.retain(|word| {
+        let dropped = if options.drop_abbreviations && is_abbreviation(word) {
+            &mut stats.abbreviation
+        } else if options.drop_roman_numerals && is_roman_numeral(word) {
+            &mut stats.roman_numeral
+        } else {
+            return true;
+        };
+        *dropped += 1;
+        stats.kept -= 1;
+        false
+    });
+}
+
+/// Parse the lines of `text` into `words`, counting them in `stats`. Large
+/// texts are split into chunks of whole lines parsed in parallel, whose words
+/// are merged in the end.
+#[cfg(feature = "parallel")]
+fn parse_into(
+    text: &str,
+    options: ParseOptions,
+    words: &mut HashSet<Word>,
+    stats: &mut ParseStats,
+) {
+    if text.len() <= CHUNK_SIZE {
+        return parse_lines(text, options, words, stats);
+    }
+
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.len() > CHUNK_SIZE {
+        // a newline byte is always a char boundary.
+        let end = rest.as_bytes()[CHUNK_SIZE..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(rest.len(), |i| CHUNK_SIZE + i + 1);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks.push(rest);
+
+    let parsed: Vec<(HashSet<Word>, ParseStats)> = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            let mut words = HashSet::new();
+            let mut stats = ParseStats::default();
+            parse_lines(chunk, options, &mut words, &mut stats);
+            (words, stats)
+        })
+        .collect();
+    for (chunk_words, chunk_stats) in parsed {
+        stats.merge(chunk_stats);
+        words.reserve(chunk_words.len());
+        for word in chunk_words {
+            // duplicates across chunks.
+            if !words.insert(word) {
+                stats.kept -= 1;
+                stats.duplicate += 1;
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_into(
+    text: &str,
+    options: ParseOptions,
+    words: &mut HashSet<Word>,
+    stats: &mut ParseStats,
+) {
+    parse_lines(text, options, words, stats);
+}
+
+fn parse_lines(
+    text: &str,
+    options: ParseOptions,
+    words: &mut HashSet<Word>,
+    stats: &mut ParseStats,
+) {
+    for line in text.lines() {
+        let Some(word) = parse_line(line, options, stats) else {
+            continue;
+        };
+        if words.insert(word) {
+            stats.kept += 1;
+        } else {
+            stats.duplicate += 1;
+        }
+    }
+}
+
+/// The normalized word of a line, unless one of the filters drops it, which is
+/// counted in `stats`. Duplicates and kept words are left to the caller.
+fn parse_line(line: &str, options: ParseOptions, stats: &mut ParseStats) -> Option<Word> {
+    let line = line.trim();
+    if line.is_empty() {
+        stats.empty += 1;
+        return None;
+    }
+    if options.drop_proper_nouns && is_capitalized(line) {
+        stats.proper_noun += 1;
+        return None;
+    }
+    if options.drop_abbreviations && is_abbreviation(line) {
+        stats.abbreviation += 1;
+        return None;
+    }
+
+    let word = normalize(line, options);
+    if !options.language.alphabet().spells(&word) {
+        stats.non_alphabetic += 1;
+        None
+    } else if options.drop_roman_numerals && is_roman_numeral(&word) {
+        stats.roman_numeral += 1;
+        None
+    } else if word.chars().count() < options.min_length {
+        stats.too_short += 1;
+        None
+    } else {
+        Some(word)
+    }
+}
+
+/// The words of a plain word list, read and normalized line by line like by
+/// [`Dictionary::load`], without holding the list in memory. Duplicates are
+/// therefore not removed, and abbreviations and Roman numerals are only dropped
+/// after the first lowercase word. Reading stops at the first I/O error, which
+/// [`finish`](Self::finish) returns.
+pub struct WordStream<R> {
+    reader: R,
+    options: ParseOptions,
+    mixed_case: bool,
+    line: Vec<u8>,
+    stats: ParseStats,
+    error: Option<io::Error>,
+}
+
+impl WordStream<BufReader<File>> {
+    pub fn open(path: &Path, options: ParseOptions) -> anyhow::Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let start = reader
+            .fill_buf()
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        anyhow::ensure!(
+            !compact::is_compact(start),
+            "{} is in the compact format, which can't be read line by line",
+            path.display()
+        );
+        Ok(WordStream::new(reader, options))
+    }
+}
+
+impl<R: BufRead> WordStream<R> {
+    pub fn new(reader: R, options: ParseOptions) -> Self {
+        WordStream {
+            reader,
+            options,
+            mixed_case: false,
+            line: Vec::new(),
+            stats: ParseStats::default(),
+            error: None,
+        }
+    }
+
+    /// How many lines were kept or dropped, or the error that stopped reading.
+    pub fn finish(self) -> io::Result<ParseStats> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.stats),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for WordStream<R> {
+    type Item = Word;
+
+    fn next(&mut self) -> Option<Word> {
+        loop {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
+            // invalid bytes are dropped as non-alphabetic, like `parse_lossy`.
+            let line = String::from_utf8_lossy(&self.line);
+            let line = line.trim_start_matches('\u{feff}');
+            self.mixed_case = self.mixed_case || has_lowercase(line);
+            let options = self.options.for_case(self.mixed_case);
+            if let Some(word) = parse_line(line, options, &mut self.stats) {
+                self.stats.kept += 1;
+                return Some(word);
+            }
+        }
+    }
+}
+
+/// A dictionary of exactly `words`, which are expected to be uppercase.
+impl FromIterator<Word> for Dictionary {
+    fn from_iter<I: IntoIterator<Item = Word>>(words: I) -> Self {
+        Dictionary {
+            words: words.into_iter().collect(),
+            ..Dictionary::default()
+        }
+    }
+}
+
+/// Read a file with one word per line, ignoring empty lines and lines starting
+/// with `#`. Words are uppercased.
+pub fn read_word_file(path: &Path) -> anyhow::Result<HashSet<Word>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_uppercase)
+        .collect())
+}
+
+/// Whether the word starts with an uppercase letter but isn't all uppercase
+/// (all uppercase lists like the scrabble one don't distinguish proper nouns).
+fn is_capitalized(word: &str) -> bool {
+    word.chars().next().is_some_and(char::is_uppercase) && word.chars().any(char::is_lowercase)
+}
+
+/// Whether the word is all uppercase without a vowel (or `Y`), ignoring
+/// diacritics. All uppercase lists like the scrabble one rarely have such words.
+fn is_abbreviation(word: &str) -> bool {
+    let is_vowel = |c: char| c.nfd().next().is_some_and(|base| "AEIOUY".contains(base));
+    word.chars().any(char::is_uppercase)
+        && !word.chars().any(char::is_lowercase)
+        && !word.chars().any(is_vowel)
+}
+
+/// Whether the uppercase word is a Roman numeral from 1 to 3999 in its usual
+/// form, so that words made of the same letters like `LIVID` aren't mistaken
+/// for one: its value is written as a numeral again and compared.
+fn is_roman_numeral(word: &str) -> bool {
+    const NUMERALS: [(&str, usize); 13] = [
+        ("M", 1000),
+        ("CM", 900),
+        ("D", 500),
+        ("CD", 400),
+        ("C", 100),
+        ("XC", 90),
+        ("L", 50),
+        ("XL", 40),
+        ("X", 10),
+        ("IX", 9),
+        ("V", 5),
+        ("IV", 4),
+        ("I", 1),
+    ];
+    let mut value = 0;
+    let mut rest = word;
+    while !rest.is_empty() {
+        let Some((numeral, numeral_value)) = NUMERALS
+            .iter()
+            .find(|(numeral, _)| rest.starts_with(numeral))
+        else {
+            return false;
+        };
+        value += numeral_value;
+        rest = &rest[numeral.len()..];
+    }
+    if !(1..=3999).contains(&value) {
+        return false;
+    }
+
+    // the numeral of the value, which has to be the word again.
+    let mut usual = String::new();
+    for (numeral, numeral_value) in NUMERALS {
+        while value >= numeral_value {
+            usual.push_str(numeral);
+            value -= numeral_value;
+        }
+    }
+    usual == word
+}
+
+fn normalize(line: &str, options: ParseOptions) -> Word {
+    let language = options.language;
+    let mut word = String::with_capacity(line.len());
+    for c in line.chars().flat_map(char::to_uppercase) {
+        if options.strip_apostrophes && (c == '\'' || c == '’') {
+            continue;
+        }
+        match language.transliterate(c) {
+            Some(spelling) => word.push_str(spelling),
+            None if options.strip_diacritics && !language.keeps_diacritic(c) => {
+                word.extend(c.nfd().filter(|&c| !is_combining_mark(c)))
+            }
+            None => word.push(c),
+        }
+    }
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Dictionary {
+        Dictionary::parse_lossy(text.as_bytes(), ParseOptions::default())
+    }
+
+    #[test]
+    fn all_uppercase_lists_keep_words_without_vowels() {
+        let dict = parse("PSST\nCRWTH\nTSKTSK\nPFFT\nMCMXC\n");
+        for word in ["PSST", "CRWTH", "TSKTSK", "PFFT", "MCMXC"] {
+            assert!(dict.contains(word), "{word} was dropped");
+        }
+    }
+
+    #[test]
+    fn mixed_case_lists_drop_abbreviations_and_roman_numerals() {
+        let dict = parse("psst\ncrwth\nHTML\nXLIV\nlivid\n");
+        assert!(dict.contains("PSST"));
+        assert!(dict.contains("CRWTH"));
+        assert!(dict.contains("LIVID"));
+        assert!(!dict.contains("HTML"));
+        assert!(!dict.contains("XLIV"));
+    }
+
+    #[test]
+    fn words_shorter_than_the_minimum_length_are_dropped() {
+        assert!(!parse("CAT\n").contains("CAT"));
+        let options = ParseOptions {
+            min_length: 3,
+            ..ParseOptions::default()
+        };
+        let dict = Dictionary::parse_lossy(b"AT\nCAT\n", options);
+        assert!(dict.contains("CAT"));
+        assert!(!dict.contains("AT"));
+    }
+
+    #[test]
+    fn lists_parse_the_same_whether_streamed_or_not() {
+        // the first batches are all uppercase.
+        let text = "PSST\nHTML\nXLIV\nCRWTH\nHTML\nlivid\nNASA\npfft\n";
+        let streamed =
+            Dictionary::parse_stream(text.as_bytes(), ParseOptions::default(), 8).unwrap();
+        let parsed = parse(text);
+        let mut streamed: Vec<&Word> = streamed.iter().collect();
+        let mut parsed: Vec<&Word> = parsed.iter().collect();
+        streamed.sort_unstable();
+        parsed.sort_unstable();
+        assert_eq!(streamed, parsed);
+        assert_eq!(parsed, ["LIVID", "NASA", "PFFT"]);
+    }
+
+    #[test]
+    fn streamed_lists_drop_abbreviations_after_the_first_lowercase_word() {
+        let stream = WordStream::new(
+            "PFFT\nHTML\npsst\nNASA\nHTTP\n".as_bytes(),
+            ParseOptions::default(),
+        );
+        let words: Vec<Word> = stream.collect();
+        assert_eq!(words, ["PFFT", "HTML", "PSST", "NASA"]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Estimates of how hard a puzzle is for a human solver.
+
+use std::{fmt, str::FromStr};
+
+use serde::Serialize;
+
+use crate::{frequency::WordFrequencies, game::GameResult};
+
+// weights of the individual factors, which are each scaled to 0 (easy) - 1 (hard).
+const ANSWERS_WEIGHT: f64 = 1.0;
+const OBSCURITY_WEIGHT: f64 = 2.0;
+const PANGRAM_WEIGHT: f64 = 1.0;
+const LETTERS_WEIGHT: f64 = 1.0;
+// puzzles with this many answers or more are considered hardest to complete.
+const MANY_ANSWERS: usize = 80;
+// letters most answers are made of, which make it easy to come up with words.
+const FRIENDLY_LETTERS: &str = "AEILNORST";
+const MEDIUM_THRESHOLD: f64 = 0.4;
+const HARD_THRESHOLD: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rating {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 3] = [Rating::Easy, Rating::Medium, Rating::Hard];
+}
+
+impl FromStr for Rating {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rating::ALL
+            .into_iter()
+            .find(|rating| rating.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("unknown difficulty '{s}', expected easy, medium or hard")
+            })
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rating::Easy => write!(f, "easy"),
+            Rating::Medium => write!(f, "medium"),
+            Rating::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Difficulty {
+    pub rating: Rating,
+    /// The weighted factors, from 0 for the easiest to 1 for the hardest puzzles.
+    pub score: f64,
+    pub answers: usize,
+    /// The average obscurity of the answers, if word frequencies are known.
+    pub average_obscurity: Option<f64>,
+    pub pangrams: usize,
+    /// How many of the puzzle's letters are among the most common ones.
+    pub friendly_letters: usize,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:.2}): {} answers, {} pangrams, {} friendly letters",
+            self.rating, self.score, self.answers, self.pangrams, self.friendly_letters
+        )?;
+        if let Some(obscurity) = self.average_obscurity {
+            write!(f, ", average obscurity {obscurity:.2}")?;
+        }
+        Ok(())
+    }
+}
+
+impl GameResult<'_> {
+    /// Estimate the difficulty from the number of answers, how obscure they are
+    /// (if `frequencies` are given), how many pangrams there are and how
+    /// common the puzzle's letters are.
+    pub fn difficulty(&self, frequencies: Option<&WordFrequencies>) -> Difficulty {
+        let entries = self.entries();
+        let answers = entries.len();
+        let pangrams = entries.iter().filter(|(_, score)| score.is_pangram).count();
+        let letters = self.starting_letter_counts();
+        let friendly_letters = letters
+            .keys()
+            .filter(|&&letter| FRIENDLY_LETTERS.contains(char::from(letter)))
+            .count();
+        let average_obscurity = frequencies.filter(|_| answers > 0).map(|frequencies| {
+            let total: f64 = entries
+                .iter()
+                .map(|(word, _)| frequencies.obscurity(word))
+                .sum();
+            total / answers as f64
+        });
+
+        let mut factors = vec![
+            (
+                ANSWERS_WEIGHT,
+                answers.min(MANY_ANSWERS) as f64 / MANY_ANSWERS as f64,
+            ),
+            // a single pangram is hardest to find, many are given away by each other.
+            (PANGRAM_WEIGHT, 1.0 / pangrams.max(1) as f64),
+            (
+                LETTERS_WEIGHT,
+                1.0 - friendly_letters as f64 / letters.len().max(1) as f64,
+            ),
+        ];
+        if let Some(obscurity) = average_obscurity {
+            factors.push((OBSCURITY_WEIGHT, obscurity));
+        }
+        let total_weight: f64 = factors.iter().map(|(weight, _)| weight).sum();
+        let score = factors
+            .iter()
+            .map(|(weight, factor)| weight * factor)
+            .sum::<f64>()
+            / total_weight;
+
+        let rating = if score >= HARD_THRESHOLD {
+            Rating::Hard
+        } else if score >= MEDIUM_THRESHOLD {
+            Rating::Medium
+        } else {
+            Rating::Easy
+        };
+        Difficulty {
+            rating,
+            score,
+            answers,
+            average_obscurity,
+            pangrams,
+            friendly_letters,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! A Discord bot answering `!bee` commands in one channel, e.g. `!bee c altefi`
+//! or `!bee today hints`. It polls the channel through Discord's REST API
+//! instead of holding a gateway connection, so that it runs on the same
+//! blocking HTTP client as everything else.
+
+use std::{thread, time::Duration};
+
+use anyhow::Context;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::Deserialize;
+use serde_json::json;
+
+use nyt_spelling_bee_solver::{
+    dictionary::Dictionary,
+    fetch::{HttpFetcher, HttpRequest, HttpStatusError},
+    game::{Game, GameResult, GameSolver, PuzzleSpec, RarestLetterMap},
+    hints::Hints,
+    http::HttpClient,
+    nyt::NytClient,
+};
+
+use crate::{
+    output::{self, OutputFormat},
+    style::Theme,
+};
+
+const API_URL: &str = "https://discord.com/api/v10";
+
+// Discord rejects longer messages.
+const MAX_MESSAGE_LEN: usize = 2000;
+
+const HELP: &str = "\
+`!bee <center> <letters>` solves a puzzle, e.g. `!bee c altefi`
+`!bee today` solves today's NYT puzzle
+add `hints` for the spoiler-free hints instead of the answers, e.g. `!bee today hints`";
+
+#[derive(Deserialize)]
+struct Message {
+    id: String,
+    content: String,
+    author: Author,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    #[serde(default)]
+    bot: bool,
+}
+
+pub struct DiscordBot<'a> {
+    http: &'a HttpClient,
+    // the `Authorization` header of the bot.
+    authorization: String,
+    channel: String,
+    dict: &'a Dictionary,
+    solver: GameSolver<RarestLetterMap<'a>>,
+    // the newest message answered or skipped, as replies only go to newer ones.
+    last_seen: Option<u64>,
+}
+
+impl<'a> DiscordBot<'a> {
+    pub fn new(
+        http: &'a HttpClient,
+        token: &str,
+        channel: &str,
+        dict: &'a Dictionary,
+        threads: usize,
+    ) -> anyhow::Result<Self> {
+        Ok(DiscordBot {
+            http,
+            authorization: format!("Bot {token}"),
+            channel: channel.to_string(),
+            dict,
+            solver: GameSolver::new(dict, threads)?,
+            last_seen: None,
+        })
+    }
+
+    /// Answer the commands posted from now on, checking for new ones every
+    /// `interval`. Only fails if Discord rejects the token or channel.
+    pub fn run(&mut self, interval: Duration) -> anyhow::Result<()> {
+        // commands posted before the bot started were answered by an earlier run.
+        self.last_seen = self.messages(1)?.iter().map(message_id).max();
+        log::info!("listening for !bee commands in channel {}", self.channel);
+        loop {
+            thread::sleep(interval);
+            let messages = match self.messages(50) {
+                Ok(messages) => messages,
+                Err(e) if is_unauthorized(&e) => return Err(e),
+                Err(e) => {
+                    log::warn!("failed to read new messages: {e:#}");
+                    continue;
+                }
+            };
+            // oldest first, to reply in order.
+            for message in messages.iter().rev() {
+                self.last_seen = self.last_seen.max(Some(message_id(message)));
+                if message.author.bot {
+                    continue;
+                }
+                if let Some(reply) = self.reply(&message.content) {
+                    for part in split_message(&reply) {
+                        if let Err(e) = self.send(&message.id, &part) {
+                            log::warn!("failed to reply to message {}: {e:#}", message.id);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The reply to a message, if it is a `!bee` command.
+    pub fn reply(&self, content: &str) -> Option<String> {
+        let mut args = content.split_whitespace();
+        if args.next() != Some("!bee") {
+            return None;
+        }
+        let mut args: Vec<&str> = args.collect();
+        let hints = args.last() == Some(&"hints");
+        if hints {
+            args.pop();
+        }
+        let game = match args.as_slice() {
+            [] | ["help"] => return Some(HELP.to_string()),
+            ["today"] => NytClient::new(self.http, None)
+                .today()
+                .and_then(|puzzle| puzzle.game()),
+            [center, letters @ ..] => Game::parse(center, &letters.concat()),
+        };
+        let reply = game.and_then(|game| {
+            let puzzle = PuzzleSpec::from(game);
+            let result = self.solver.solve(&puzzle)?;
+            if hints {
+                Ok(format!("```\n{}```", Hints::new(&result)))
+            } else {
+                self.answers(&puzzle, &result)
+            }
+        });
+        Some(reply.unwrap_or_else(|e| format!("error: {e:#}")))
+    }
+
+    // the answers in the `forum-discord` format, each behind a spoiler.
+    fn answers(&self, puzzle: &PuzzleSpec, result: &GameResult) -> anyhow::Result<String> {
+        let mut out = Vec::new();
+        output::write_result(
+            &result.into(),
+            puzzle,
+            &self.dict.metadata(),
+            OutputFormat::ForumDiscord,
+            &Theme::default(),
+            None,
+            &mut out,
+        )?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    // the newest `limit` messages after the last seen one, newest first.
+    fn messages(&self, limit: usize) -> anyhow::Result<Vec<Message>> {
+        let url = format!("{API_URL}/channels/{}/messages", self.channel);
+        let mut request = HttpRequest::get(&url)
+            .query("limit", limit)
+            .header(AUTHORIZATION.as_str(), &self.authorization);
+        if let Some(last_seen) = self.last_seen {
+            request = request.query("after", last_seen);
+        }
+        let response = self.http.fetch(&request)?.error_for_status(&url)?;
+        serde_json::from_str(&response.body).context("unexpected messages from Discord")
+    }
+
+    // post `content` in reply to the message `reply_to`, without pinging anyone.
+    fn send(&self, reply_to: &str, content: &str) -> anyhow::Result<()> {
+        let url = format!("{API_URL}/channels/{}/messages", self.channel);
+        let body = json!({
+            "content": content,
+            "message_reference": { "message_id": reply_to },
+            "allowed_mentions": { "parse": [] },
+        })
+        .to_string();
+        self.http
+            .send(|client| {
+                client
+                    .post(&url)
+                    .header(AUTHORIZATION, &self.authorization)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone())
+            })?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+// message ids are snowflakes, which grow over time.
+fn message_id(message: &Message) -> u64 {
+    message.id.parse().unwrap_or(0)
+}
+
+fn is_unauthorized(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<HttpStatusError>()
+        .is_some_and(|e| matches!(e.status, 401 | 403 | 404))
+}
+
+/// Split `text` into messages Discord accepts, at line breaks where possible,
+/// closing and reopening code blocks that span two messages.
+fn split_message(text: &str) -> Vec<String> {
+    const FENCE: &str = "```";
+    // room to close a code block.
+    let limit = MAX_MESSAGE_LEN - FENCE.len() - 1;
+    let mut messages = Vec::new();
+    let mut current = String::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        // too long lines, like the answers, are broken up at spaces.
+        let mut pieces = Vec::new();
+        let mut piece = String::new();
+        for word in line.split(' ') {
+            if !piece.is_empty() && piece.len() + 1 + word.len() > limit - FENCE.len() - 1 {
+                pieces.push(std::mem::take(&mut piece));
+            }
+            if !piece.is_empty() {
+                piece.push(' ');
+            }
+            piece.push_str(word);
+        }
+        pieces.push(piece);
+
+        for piece in pieces {
+            if !current.is_empty() && current.len() + piece.len() + 1 > limit {
+                if in_code {
+                    current.push_str(FENCE);
+                }
+                messages.push(std::mem::take(&mut current));
+                if in_code {
+                    current.push_str(FENCE);
+                    current.push('\n');
+                }
+            }
+            current.push_str(&piece);
+            current.push('\n');
+        }
+        if line.starts_with(FENCE) {
+            in_code = !in_code;
+        }
+    }
+    if !current.trim().is_empty() {
+        messages.push(current);
+    }
+    messages
+}
@@ -0,0 +1,173 @@
+//! Information about each answer looked up after solving, such as definitions,
+//! how obscure the word is and how often NYT accepted it before.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+};
+
+use serde::Serialize;
+
+use crate::{
+    archive::AnswerCorpus,
+    frequency::WordFrequencies,
+    game::{GameResult, Score, Word},
+};
+
+/// Looks up the definition of a word, e.g. with `definitions::lookup`.
+pub type DefinitionLookup<'a> = dyn Fn(&str) -> anyhow::Result<Option<String>> + Sync + 'a;
+
+/// What is known about an answer, which is none for lookups that weren't enabled.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Enrichment {
+    pub definition: Option<String>,
+    /// Between 0 for the most frequent and 1 for unknown words.
+    pub obscurity: Option<f64>,
+    /// How many archived puzzles had the word as an official answer.
+    pub official_count: Option<usize>,
+}
+
+/// Which lookups an [`EnrichedResult`] contains, e.g. to decide on table columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnrichedFields {
+    pub definition: bool,
+    pub obscurity: bool,
+    pub official_count: bool,
+}
+
+/// A result together with the information looked up for each of its answers.
+pub struct EnrichedResult<'r, 'a> {
+    pub result: &'r GameResult<'a>,
+    pub fields: EnrichedFields,
+    enrichments: HashMap<&'a Word, Enrichment>,
+}
+
+impl<'r, 'a> EnrichedResult<'r, 'a> {
+    pub fn get(&self, word: &Word) -> Option<&Enrichment> {
+        self.enrichments.get(word)
+    }
+
+    /// All answers with their scores and enrichments, in the result's order.
+    pub fn entries(&self) -> Vec<(&'a Word, Score, &Enrichment)> {
+        static NONE: Enrichment = Enrichment {
+            definition: None,
+            obscurity: None,
+            official_count: None,
+        };
+        self.result
+            .entries()
+            .into_iter()
+            .map(|(word, score)| (word, score, self.enrichments.get(word).unwrap_or(&NONE)))
+            .collect()
+    }
+}
+
+/// A result without any enrichment.
+impl<'r, 'a> From<&'r GameResult<'a>> for EnrichedResult<'r, 'a> {
+    fn from(result: &'r GameResult<'a>) -> Self {
+        EnrichedResult {
+            result,
+            fields: EnrichedFields::default(),
+            enrichments: HashMap::new(),
+        }
+    }
+}
+
+/// Runs the enabled lookups for every answer of a result. Definitions are looked
+/// up by up to `concurrency` threads at once, since each is a network request.
+pub struct Enricher<'a> {
+    concurrency: usize,
+    definitions: Option<&'a DefinitionLookup<'a>>,
+    frequencies: Option<&'a WordFrequencies>,
+    corpus: Option<&'a AnswerCorpus>,
+}
+
+impl<'a> Enricher<'a> {
+    pub fn new(concurrency: usize) -> Self {
+        Enricher {
+            concurrency: concurrency.max(1),
+            definitions: None,
+            frequencies: None,
+            corpus: None,
+        }
+    }
+
+    pub fn with_definitions(mut self, lookup: &'a DefinitionLookup<'a>) -> Self {
+        self.definitions = Some(lookup);
+        self
+    }
+
+    pub fn with_frequencies(mut self, frequencies: &'a WordFrequencies) -> Self {
+        self.frequencies = Some(frequencies);
+        self
+    }
+
+    pub fn with_corpus(mut self, corpus: &'a AnswerCorpus) -> Self {
+        self.corpus = Some(corpus);
+        self
+    }
+
+    pub fn enrich<'r, 'w>(&self, result: &'r GameResult<'w>) -> EnrichedResult<'r, 'w> {
+        let words: Vec<&'w Word> = result.entries().into_iter().map(|(word, _)| word).collect();
+        let mut definitions = match self.definitions {
+            Some(lookup) => self.look_up_definitions(&words, lookup),
+            None => HashMap::new(),
+        };
+
+        let enrichments = words
+            .iter()
+            .map(|&word| {
+                let enrichment = Enrichment {
+                    definition: definitions.remove(word).flatten(),
+                    obscurity: self
+                        .frequencies
+                        .map(|frequencies| frequencies.obscurity(word)),
+                    official_count: self.corpus.map(|corpus| corpus.count(word)),
+                };
+                (word, enrichment)
+            })
+            .collect();
+
+        EnrichedResult {
+            result,
+            fields: EnrichedFields {
+                definition: self.definitions.is_some(),
+                obscurity: self.frequencies.is_some(),
+                official_count: self.corpus.is_some(),
+            },
+            enrichments,
+        }
+    }
+
+    // workers take the next word until none are left and send back the definitions,
+    // failed lookups are logged and treated as unknown words.
+    fn look_up_definitions<'w>(
+        &self,
+        words: &[&'w Word],
+        lookup: &DefinitionLookup,
+    ) -> HashMap<&'w Word, Option<String>> {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(words.len()) {
+                let sender = sender.clone();
+                let next = &next;
+                scope.spawn(move || {
+                    while let Some(&word) = words.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let definition = lookup(word).unwrap_or_else(|e| {
+                            log::warn!("failed to look up the definition of {word}: {e:#}");
+                            None
+                        });
+                        // the receiver outlives the scope.
+                        let _ = sender.send((word, definition));
+                    }
+                });
+            }
+        });
+        drop(sender);
+        receiver.into_iter().collect()
+    }
+}
@@ -0,0 +1,185 @@
+//! How well the solver's answers match the official ones of archived puzzles,
+//! to measure the quality of a word list and its curation over time.
+
+use std::fmt;
+
+use crate::{
+    archive::ArchivedPuzzle,
+    game::{GameResult, Word},
+};
+
+/// How many answers a solution shares with the official ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnswerCounts {
+    /// Answers that are official.
+    pub correct: usize,
+    /// Answers that aren't official.
+    pub extra: usize,
+    /// Official answers missing from the solution.
+    pub missing: usize,
+}
+
+impl AnswerCounts {
+    /// The fraction of the answers that are official, 1 without answers.
+    pub fn precision(&self) -> f64 {
+        ratio(self.correct, self.correct + self.extra)
+    }
+
+    /// The fraction of the official answers that were found, 1 without any.
+    pub fn recall(&self) -> f64 {
+        ratio(self.correct, self.correct + self.missing)
+    }
+
+    /// The harmonic mean of precision and recall.
+    pub fn f1(&self) -> f64 {
+        let (precision, recall) = (self.precision(), self.recall());
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        1.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// The solution of one archived puzzle compared with its official answers.
+#[derive(Debug, Clone)]
+pub struct PuzzleEvaluation<'a> {
+    pub puzzle: &'a ArchivedPuzzle,
+    pub counts: AnswerCounts,
+    /// Official answers missing from the solution, alphabetically.
+    pub missing: Vec<&'a Word>,
+    /// Answers that aren't official, alphabetically.
+    pub extra: Vec<&'a Word>,
+}
+
+impl<'a> PuzzleEvaluation<'a> {
+    pub fn new(puzzle: &'a ArchivedPuzzle, result: &GameResult<'a>) -> Self {
+        let entries = result.entries();
+        let mut missing: Vec<&Word> = puzzle
+            .answers
+            .iter()
+            .filter(|answer| !entries.iter().any(|(word, _)| word == answer))
+            .collect();
+        missing.sort_unstable();
+        let mut extra: Vec<&Word> = entries
+            .iter()
+            .map(|(word, _)| *word)
+            .filter(|word| !puzzle.answers.contains(word))
+            .collect();
+        extra.sort_unstable();
+        PuzzleEvaluation {
+            puzzle,
+            counts: AnswerCounts {
+                correct: entries.len() - extra.len(),
+                extra: extra.len(),
+                missing: missing.len(),
+            },
+            missing,
+            extra,
+        }
+    }
+}
+
+/// e.g. `2024-06-01  precision  68.2%  recall  97.1%  F1  80.1%  (33 of 34
+/// official, 15 extra)`.
+impl fmt::Display for PuzzleEvaluation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = &self.counts;
+        write!(
+            f,
+            "{}  precision {:>5.1}%  recall {:>5.1}%  F1 {:>5.1}%  ({} of {} official, {} extra)",
+            self.puzzle.print_date,
+            100.0 * counts.precision(),
+            100.0 * counts.recall(),
+            100.0 * counts.f1(),
+            counts.correct,
+            counts.correct + counts.missing,
+            counts.extra
+        )
+    }
+}
+
+/// The evaluations of several puzzles, by date.
+#[derive(Debug, Clone)]
+pub struct EvaluationReport<'a> {
+    pub puzzles: Vec<PuzzleEvaluation<'a>>,
+}
+
+impl<'a> EvaluationReport<'a> {
+    pub fn new(solved: impl IntoIterator<Item = (&'a ArchivedPuzzle, &'a GameResult<'a>)>) -> Self {
+        let mut puzzles: Vec<_> = solved
+            .into_iter()
+            .map(|(puzzle, result)| PuzzleEvaluation::new(puzzle, result))
+            .collect();
+        puzzles.sort_by_key(|evaluation| evaluation.puzzle.print_date);
+        EvaluationReport { puzzles }
+    }
+
+    /// The counts of all puzzles together, weighting puzzles by their answers.
+    pub fn total(&self) -> AnswerCounts {
+        let mut total = AnswerCounts::default();
+        for evaluation in &self.puzzles {
+            total.correct += evaluation.counts.correct;
+            total.extra += evaluation.counts.extra;
+            total.missing += evaluation.counts.missing;
+        }
+        total
+    }
+
+    /// The average precision and recall of the puzzles, weighting every
+    /// puzzle equally.
+    pub fn average(&self) -> (f64, f64) {
+        let count = self.puzzles.len().max(1) as f64;
+        let sum = |metric: fn(&AnswerCounts) -> f64| {
+            self.puzzles
+                .iter()
+                .map(|evaluation| metric(&evaluation.counts))
+                .sum::<f64>()
+                / count
+        };
+        (sum(AnswerCounts::precision), sum(AnswerCounts::recall))
+    }
+}
+
+/// One line per puzzle followed by the totals, or for a single puzzle, the
+/// missing and extra answers instead.
+impl fmt::Display for EvaluationReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for evaluation in &self.puzzles {
+            writeln!(f, "{evaluation}")?;
+        }
+        if let [evaluation] = self.puzzles.as_slice() {
+            let join = |words: &[&Word]| {
+                if words.is_empty() {
+                    return "none".to_string();
+                }
+                let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+                words.join(", ")
+            };
+            writeln!(f, "missing: {}", join(&evaluation.missing))?;
+            return writeln!(f, "extra: {}", join(&evaluation.extra));
+        }
+
+        let total = self.total();
+        let (precision, recall) = self.average();
+        writeln!(
+            f,
+            "\n{} puzzles: precision {:.1}%, recall {:.1}%, F1 {:.1}% over all answers \
+             (per puzzle on average: precision {:.1}%, recall {:.1}%)",
+            self.puzzles.len(),
+            100.0 * total.precision(),
+            100.0 * total.recall(),
+            100.0 * total.f1(),
+            100.0 * precision,
+            100.0 * recall
+        )
+    }
+}
@@ -0,0 +1,67 @@
+//! Exit codes telling scripts what kind of failure occurred.
+
+use std::{fmt, process::ExitCode};
+
+use nyt_spelling_bee_solver::{
+    fetch::HttpStatusError,
+    game::{InvalidLetter, InvalidPuzzle},
+    http::DeadlineExceeded,
+};
+
+/// Any failure without a more specific code, like clap's usage errors.
+const GENERAL_FAILURE: u8 = 1;
+
+/// Failures with their own exit code that can't be recognized by the type of
+/// their cause, attached to errors as context where they occur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The word list couldn't be loaded or parsed.
+    Dictionary,
+    /// `validate` found the puzzle to be invalid.
+    Validation,
+    /// `validate` found no pangram.
+    NoPangram,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Dictionary => write!(f, "failed to load the dictionary"),
+            Failure::Validation => write!(f, "puzzle is invalid"),
+            Failure::NoPangram => write!(f, "puzzle has no pangram"),
+        }
+    }
+}
+
+/// The exit code of invalid command line arguments.
+pub fn usage_error() -> ExitCode {
+    ExitCode::from(GENERAL_FAILURE)
+}
+
+/// The exit code of a failed command:
+/// - 2: the puzzle is invalid
+/// - 3: a network request failed
+/// - 4: the dictionary couldn't be loaded
+/// - 5: `validate` found no pangram
+/// - 1: anything else
+pub fn exit_code(error: &anyhow::Error) -> ExitCode {
+    let caused_by = |is: fn(&(dyn std::error::Error + 'static)) -> bool| error.chain().any(is);
+    let failure = error.downcast_ref::<Failure>();
+    let code = if failure == Some(&Failure::NoPangram) {
+        5
+    } else if failure == Some(&Failure::Validation)
+        || caused_by(|e| e.is::<InvalidPuzzle>() || e.is::<InvalidLetter>())
+    {
+        2
+    } else if caused_by(|e| {
+        e.is::<reqwest::Error>() || e.is::<HttpStatusError>() || e.is::<DeadlineExceeded>()
+    }) {
+        // before the dictionary, which fails too if it couldn't be downloaded.
+        3
+    } else if failure == Some(&Failure::Dictionary) {
+        4
+    } else {
+        GENERAL_FAILURE
+    };
+    ExitCode::from(code)
+}
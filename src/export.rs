@@ -0,0 +1,248 @@
+//! Exports of solutions for use in other programs.
+
+use std::{
+    fmt::Write as _,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use anyhow::Context;
+use clap::ValueEnum;
+use jiff::Timestamp;
+
+use nyt_spelling_bee_solver::{
+    dictionary::DictionaryMetadata,
+    game::{GameResult, PuzzleSpec},
+    tricky::TrickyWord,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// Tab separated flashcards for Anki's text import.
+    Anki,
+    /// Rows appended to the SQLite database at `--database`.
+    Sqlite,
+}
+
+/// The tables of SQLite exports. Changes must bump `user_version` and only
+/// add columns, so that queries against older databases keep working, and add
+/// the migration of older databases to [`SQLITE_MIGRATIONS`].
+pub const SQLITE_SCHEMA: &str = "\
+PRAGMA user_version = 2;
+-- one row per exported solve.
+CREATE TABLE IF NOT EXISTS puzzles (
+    id INTEGER PRIMARY KEY,
+    -- when the puzzle was solved, e.g. '2024-06-06T12:00:00Z'.
+    solved_at TEXT NOT NULL,
+    -- the publication date and NYT id of official puzzles, else NULL.
+    date TEXT,
+    nyt_id INTEGER,
+    editor TEXT,
+    center_letter TEXT NOT NULL,
+    outer_letters TEXT NOT NULL,
+    answers INTEGER NOT NULL,
+    total_points INTEGER NOT NULL,
+    -- the word list the answers are from, NULL where unknown.
+    dictionary_source TEXT,
+    dictionary_fetched_at TEXT,
+    dictionary_license TEXT,
+    dictionary_words INTEGER
+);
+CREATE TABLE IF NOT EXISTS answers (
+    puzzle_id INTEGER NOT NULL REFERENCES puzzles (id),
+    word TEXT NOT NULL,
+    points INTEGER NOT NULL,
+    -- 1 for pangrams, else 0.
+    is_pangram INTEGER NOT NULL,
+    PRIMARY KEY (puzzle_id, word)
+);
+CREATE INDEX IF NOT EXISTS answers_word ON answers (word);
+";
+
+/// The statements upgrading a database from the previous `user_version` to
+/// each version.
+const SQLITE_MIGRATIONS: &[(u32, &str)] = &[(
+    2,
+    "\
+ALTER TABLE puzzles ADD COLUMN dictionary_source TEXT;
+ALTER TABLE puzzles ADD COLUMN dictionary_fetched_at TEXT;
+ALTER TABLE puzzles ADD COLUMN dictionary_license TEXT;
+ALTER TABLE puzzles ADD COLUMN dictionary_words INTEGER;
+PRAGMA user_version = 2;
+",
+)];
+
+/// Write one flashcard per word, asking for the word given the puzzle, its
+/// start, length and definition (if known). Words are tagged with the reasons
+/// they are tricky.
+pub fn write_anki(
+    words: &[(&TrickyWord, Option<String>)],
+    puzzle: &PuzzleSpec,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let letters: String = puzzle.game().letters().map(char::from).collect();
+    // e.g. `spelling-bee-2024-06-06`, for official puzzles.
+    let date_tag = puzzle
+        .game()
+        .metadata()
+        .map(|metadata| format!("spelling-bee-{}", metadata.date));
+
+    // header lines understood by Anki's text import.
+    writeln!(out, "#separator:tab")?;
+    writeln!(out, "#html:false")?;
+    writeln!(out, "#tags column:3")?;
+    for (tricky, definition) in words {
+        let start: String = tricky.word.chars().take(2).collect();
+        let mut front = format!(
+            "Spelling Bee {letters}: {start}... ({} letters)",
+            tricky.word.chars().count()
+        );
+        if let Some(definition) = definition {
+            front.push_str(": ");
+            front.push_str(definition);
+        }
+        let tags: Vec<String> = std::iter::once("spelling-bee".to_string())
+            .chain(date_tag.clone())
+            .chain(tricky.reasons.iter().map(|reason| reason.replace(' ', "-")))
+            .collect();
+        writeln!(
+            out,
+            "{}\t{}\t{}",
+            sanitize(&front),
+            tricky.word,
+            tags.join(" ")
+        )?;
+    }
+    Ok(())
+}
+
+// tabs and newlines would break the record.
+fn sanitize(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Append `result`, solved with `dictionary`, to the SQLite database at `path`
+/// (see [`SQLITE_SCHEMA`]), creating or migrating it if needed, with the
+/// `sqlite3` command line shell.
+pub fn write_sqlite(
+    result: &GameResult,
+    puzzle: &PuzzleSpec,
+    dictionary: &DictionaryMetadata,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let statements = sqlite_statements(result, puzzle, dictionary, sqlite_version(path)?);
+    run_sqlite(path, &statements)
+}
+
+// the `user_version` of the database at `path`, 0 for a new one.
+fn sqlite_version(path: &Path) -> anyhow::Result<u32> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let output = Command::new("sqlite3")
+        .arg(path)
+        .arg("PRAGMA user_version;")
+        .output()
+        .context("failed to run sqlite3, install the SQLite command line shell")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "failed to read {}: {}",
+        path.display(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    let version = String::from_utf8_lossy(&output.stdout);
+    version
+        .trim()
+        .parse()
+        .with_context(|| format!("unexpected user_version of {}: {version}", path.display()))
+}
+
+fn run_sqlite(path: &Path, statements: &str) -> anyhow::Result<()> {
+    let mut child = Command::new("sqlite3")
+        .arg("-bail")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run sqlite3, install the SQLite command line shell")?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(statements.as_bytes())
+        .context("failed to write to sqlite3")?;
+    let output = child.wait_with_output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "failed to export to {}: {}",
+        path.display(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(())
+}
+
+/// The schema, or the migrations of a database at `version`, and the insertion
+/// of `result`, in one transaction.
+fn sqlite_statements(
+    result: &GameResult,
+    puzzle: &PuzzleSpec,
+    dictionary: &DictionaryMetadata,
+    version: u32,
+) -> String {
+    let game = puzzle.game();
+    let mut letters = game.letters().map(char::from);
+    let center = letters.next().expect("game has no letters");
+    let outer: String = letters.collect();
+    let metadata = game.metadata();
+    let entries = result.entries();
+
+    let mut sql = "BEGIN;\n".to_string();
+    if version == 0 {
+        sql.push_str(SQLITE_SCHEMA);
+    } else {
+        for (_, migration) in SQLITE_MIGRATIONS.iter().filter(|(to, _)| *to > version) {
+            sql.push_str(migration);
+        }
+    }
+    let optional = |value: Option<&str>| value.map_or("NULL".to_string(), quote);
+    // writing to a `String` can't fail.
+    let _ = writeln!(
+        sql,
+        "INSERT INTO puzzles (solved_at, date, nyt_id, editor, center_letter, outer_letters, \
+         answers, total_points, dictionary_source, dictionary_fetched_at, dictionary_license, \
+         dictionary_words) VALUES ({}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {});",
+        quote(&Timestamp::now().to_string()),
+        metadata.map_or("NULL".to_string(), |m| quote(&m.date.to_string())),
+        metadata.map_or("NULL".to_string(), |m| m.id.to_string()),
+        metadata
+            .and_then(|m| m.editor.as_deref())
+            .map_or("NULL".to_string(), quote),
+        quote(&center.to_string()),
+        quote(&outer),
+        entries.len(),
+        result.total_points(),
+        optional(dictionary.source.as_deref()),
+        optional(dictionary.fetched_at.map(|at| at.to_string()).as_deref()),
+        optional(dictionary.license.as_deref()),
+        dictionary.word_count
+    );
+    for (word, score) in &entries {
+        let _ = writeln!(
+            sql,
+            "INSERT INTO answers (puzzle_id, word, points, is_pangram) \
+             VALUES ((SELECT max(id) FROM puzzles), {}, {}, {});",
+            quote(word),
+            score.points,
+            u8::from(score.is_pangram)
+        );
+    }
+    sql.push_str("COMMIT;\n");
+    sql
+}
+
+// an SQL string literal.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,85 @@
+//! Answers that extend other answers, like TALC -> TALCS or FACET -> FACETED.
+//! Finding one answer of a family often leads to the others.
+
+use std::fmt;
+
+use crate::game::{GameResult, Word};
+
+/// An answer together with the answers extending it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extensions<'a> {
+    pub word: &'a Word,
+    /// The answers whose closest shorter answer contained in them is `word`,
+    /// sorted alphabetically.
+    pub extensions: Vec<Extensions<'a>>,
+}
+
+impl<'a> Extensions<'a> {
+    /// The number of answers in this family, including `word`.
+    pub fn word_count(&self) -> usize {
+        1 + self
+            .extensions
+            .iter()
+            .map(Extensions::word_count)
+            .sum::<usize>()
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        writeln!(f, "{:indent$}{}", "", self.word, indent = 2 * depth)?;
+        for extension in &self.extensions {
+            extension.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> GameResult<'a> {
+    /// Group the answers into families of answers containing a shorter one,
+    /// omitting answers without any extension that don't extend another answer.
+    /// Each answer extends the longest shorter answer it contains (the
+    /// alphabetically first on ties), so families are trees.
+    pub fn extensions(&self) -> Vec<Extensions<'a>> {
+        let mut words: Vec<&'a Word> = self.entries().into_iter().map(|(word, _)| word).collect();
+        // shorter words first, so that parents are found before their extensions.
+        words.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+
+        let parents: Vec<Option<usize>> = words
+            .iter()
+            .map(|word| {
+                words
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, other)| other.len() < word.len() && word.contains(other.as_str()))
+                    .max_by(|(_, a), (_, b)| a.len().cmp(&b.len()).then(b.cmp(a)))
+                    .map(|(i, _)| i)
+            })
+            .collect();
+
+        fn build<'a>(i: usize, words: &[&'a Word], parents: &[Option<usize>]) -> Extensions<'a> {
+            let mut extensions: Vec<_> = (0..words.len())
+                .filter(|&child| parents[child] == Some(i))
+                .map(|child| build(child, words, parents))
+                .collect();
+            extensions.sort_by_key(|extension| extension.word);
+            Extensions {
+                word: words[i],
+                extensions,
+            }
+        }
+
+        let mut families: Vec<_> = (0..words.len())
+            .filter(|&i| parents[i].is_none())
+            .map(|i| build(i, &words, &parents))
+            .filter(|family| !family.extensions.is_empty())
+            .collect();
+        families.sort_by_key(|family| family.word);
+        families
+    }
+}
+
+/// The family as an indented tree, one answer per line.
+impl fmt::Display for Extensions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
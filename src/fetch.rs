@@ -0,0 +1,143 @@
+//! Fetching text from the web, abstracted so that scraping word lists and
+//! fetching puzzles work with any HTTP client. The `network` feature provides
+//! `http::HttpClient` based on reqwest, and [`MockFetcher`] serves canned
+//! responses without a network.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+};
+
+use thiserror::Error;
+
+/// A GET request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest<'a> {
+    pub url: &'a str,
+    /// Appended to the URL as the query string.
+    pub query: Vec<(&'a str, String)>,
+    pub headers: Vec<(&'a str, String)>,
+}
+
+impl<'a> HttpRequest<'a> {
+    pub fn get(url: &'a str) -> HttpRequest<'a> {
+        HttpRequest {
+            url,
+            query: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn query(mut self, name: &'a str, value: impl ToString) -> HttpRequest<'a> {
+        self.query.push((name, value.to_string()));
+        self
+    }
+
+    pub fn header(mut self, name: &'a str, value: impl ToString) -> HttpRequest<'a> {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// The response, or an error for the request of `url` unless the status
+    /// is 2xx.
+    pub fn error_for_status(self, url: &str) -> Result<HttpResponse, HttpStatusError> {
+        if self.is_success() {
+            Ok(self)
+        } else {
+            Err(HttpStatusError {
+                url: url.to_string(),
+                status: self.status,
+            })
+        }
+    }
+}
+
+/// A response with a status other than 2xx, where a successful one was needed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{url} responded with status {status}")]
+pub struct HttpStatusError {
+    pub url: String,
+    pub status: u16,
+}
+
+pub trait HttpFetcher {
+    /// Send `request`, returning the response whatever its status.
+    fn fetch(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse>;
+
+    /// The body of a GET of `url`, failing unless the status is 2xx.
+    fn get_text(&self, url: &str) -> anyhow::Result<String> {
+        Ok(self
+            .fetch(&HttpRequest::get(url))?
+            .error_for_status(url)?
+            .body)
+    }
+
+    /// The body of a GET of `url` as a stream, failing unless the status is
+    /// 2xx, to process large bodies without holding them in memory. By
+    /// default the whole body of [`get_text`](Self::get_text).
+    fn get_reader(&self, url: &str) -> anyhow::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(Cursor::new(self.get_text(url)?)))
+    }
+}
+
+impl<F: HttpFetcher + ?Sized> HttpFetcher for &F {
+    fn fetch(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse> {
+        (**self).fetch(request)
+    }
+
+    fn get_text(&self, url: &str) -> anyhow::Result<String> {
+        (**self).get_text(url)
+    }
+
+    fn get_reader(&self, url: &str) -> anyhow::Result<Box<dyn Read + '_>> {
+        (**self).get_reader(url)
+    }
+}
+
+/// Canned responses by URL, ignoring the query string and headers. Other URLs
+/// respond with 404.
+#[derive(Debug, Clone, Default)]
+pub struct MockFetcher {
+    responses: HashMap<String, HttpResponse>,
+}
+
+impl MockFetcher {
+    pub fn new() -> MockFetcher {
+        MockFetcher::default()
+    }
+
+    /// Respond to requests of `url` with `status` and `body`.
+    pub fn with_response(mut self, url: &str, status: u16, body: &str) -> MockFetcher {
+        let response = HttpResponse {
+            status,
+            body: body.to_string(),
+        };
+        self.responses.insert(url.to_string(), response);
+        self
+    }
+}
+
+impl HttpFetcher for MockFetcher {
+    fn fetch(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse> {
+        Ok(self
+            .responses
+            .get(request.url)
+            .cloned()
+            .unwrap_or(HttpResponse {
+                status: 404,
+                body: String::new(),
+            }))
+    }
+}
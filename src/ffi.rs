@@ -0,0 +1,146 @@
+//! C bindings of the solver, for embedding it in other languages. The header
+//! `include/nyt_spelling_bee_solver.h` is generated with
+//! `cbindgen --config cbindgen.toml --output include/nyt_spelling_bee_solver.h`.
+//!
+//! Strings passed in are borrowed for the duration of the call. Strings passed
+//! out are owned by the caller, who must free them with [`bee_string_free`].
+
+use std::{
+    ffi::{CStr, CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    dictionary::{Dictionary, ParseOptions},
+    game::{Game, GameSolver, PuzzleSpec, RarestLetterMap},
+    scoring::PointsBreakdown,
+};
+
+/// The puzzle was solved and `out_json` holds the solution.
+pub const BEE_OK: i32 = 0;
+/// An argument was null or not valid UTF-8, `out_json` is left untouched.
+pub const BEE_INVALID_ARGUMENT: i32 = 1;
+/// The dictionary couldn't be loaded or the puzzle is invalid, `out_json`
+/// holds `{"error": "..."}`.
+pub const BEE_ERROR: i32 = 2;
+/// The solver panicked, `out_json` is left untouched.
+pub const BEE_PANIC: i32 = 3;
+
+#[derive(Serialize)]
+struct FfiAnswer<'a> {
+    word: &'a str,
+    points: usize,
+    breakdown: PointsBreakdown,
+    pangram: bool,
+}
+
+#[derive(Serialize)]
+struct FfiSolution<'a> {
+    answers: Vec<FfiAnswer<'a>>,
+    total_points: usize,
+}
+
+#[derive(Serialize)]
+struct FfiError {
+    error: String,
+}
+
+/// Solve the puzzle with the center letter `center` (e.g. `"c"`) and outer
+/// letters `letters` (e.g. `"altefi"`) using the word list at `dict_path`.
+/// Returns one of the `BEE_*` codes and stores a JSON string like
+/// `{"answers":[{"word":"FACET","points":2,"breakdown":{"base":1,"length_bonus":1,"pangram_bonus":0},"pangram":false}],"total_points":2}`
+/// in `*out_json`.
+///
+/// # Safety
+///
+/// `center`, `letters` and `dict_path` must be null or valid NUL-terminated
+/// strings, and `out_json` must be null or valid for writing a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bee_solve(
+    center: *const c_char,
+    letters: *const c_char,
+    dict_path: *const c_char,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if out_json.is_null() {
+        return BEE_INVALID_ARGUMENT;
+    }
+    // SAFETY: guaranteed by the caller.
+    let (center, letters, dict_path) =
+        unsafe { (str_arg(center), str_arg(letters), str_arg(dict_path)) };
+    let (Some(center), Some(letters), Some(dict_path)) = (center, letters, dict_path) else {
+        return BEE_INVALID_ARGUMENT;
+    };
+
+    // unwinding into C is undefined behavior.
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| solve(center, letters, dict_path)));
+    let (code, json) = match solved {
+        Ok(Ok(json)) => (BEE_OK, json),
+        Ok(Err(e)) => {
+            let error = FfiError {
+                error: format!("{e:#}"),
+            };
+            match serde_json::to_string(&error) {
+                Ok(json) => (BEE_ERROR, json),
+                Err(_) => return BEE_PANIC,
+            }
+        }
+        Err(_) => return BEE_PANIC,
+    };
+    // JSON escapes NUL characters, so there are none.
+    let Ok(json) = CString::new(json) else {
+        return BEE_PANIC;
+    };
+    // SAFETY: checked for null above, validity guaranteed by the caller.
+    unsafe { *out_json = json.into_raw() };
+    code
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that wasn't freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bee_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: guaranteed by the caller, the string was created by `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+fn solve(center: &str, letters: &str, dict_path: &str) -> anyhow::Result<String> {
+    let dict = Dictionary::load(Path::new(dict_path), ParseOptions::default())?;
+    let puzzle = PuzzleSpec::from(Game::parse(center, letters)?);
+    // the calling application owns the threads, so the solver uses just one.
+    let result = GameSolver::<RarestLetterMap>::new(&dict, 1)?.solve(&puzzle)?;
+    let answers = result
+        .entries()
+        .into_iter()
+        .map(|(word, score)| FfiAnswer {
+            word,
+            points: score.points,
+            breakdown: score.breakdown,
+            pangram: score.is_pangram,
+        })
+        .collect();
+    let solution = FfiSolution {
+        answers,
+        total_points: result.total_points(),
+    };
+    Ok(serde_json::to_string(&solution)?)
+}
+
+/// # Safety
+///
+/// `s` must be null or a valid NUL-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
@@ -0,0 +1,49 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Context;
+
+use crate::game::Word;
+
+/// How often words occur in some corpus, as a proxy for how well known they are.
+pub struct WordFrequencies {
+    counts: HashMap<Word, u64>,
+    max_count: u64,
+}
+
+impl WordFrequencies {
+    /// Load a word frequency list with one `word count` pair per line, as
+    /// found in common unigram count lists. Words are uppercased.
+    pub fn load(path: &Path) -> anyhow::Result<WordFrequencies> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let mut counts: HashMap<Word, u64> = HashMap::new();
+        for (line_number, line) in text.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let (Some(word), Some(count)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let count: u64 = count.parse().with_context(|| {
+                format!(
+                    "invalid count '{count}' in line {} of {}",
+                    line_number + 1,
+                    path.display()
+                )
+            })?;
+            *counts.entry(word.to_uppercase()).or_default() += count;
+        }
+        let max_count = counts.values().copied().max().unwrap_or(0);
+
+        Ok(WordFrequencies { counts, max_count })
+    }
+
+    /// How obscure a word is on a logarithmic scale, from 0 for the most
+    /// frequent word to 1 for words that never occur.
+    pub fn obscurity(&self, word: &str) -> f64 {
+        let count = self.counts.get(word).copied().unwrap_or(0);
+        if self.max_count == 0 {
+            return 1.0;
+        }
+        1.0 - ((count + 1) as f64).ln() / ((self.max_count + 1) as f64).ln()
+    }
+}
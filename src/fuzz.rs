@@ -0,0 +1,266 @@
+//! Cross-checking the strategies against brute force on random puzzles, which
+//! catches optimized strategies that miss or wrongly accept answers.
+
+use std::{collections::HashSet, fmt, sync::Arc};
+
+use crate::{
+    dictionary::Dictionary,
+    game::{Game, GameResult, Letter, PuzzleSpec, ResultFilter, Rules, Word},
+    registry::{DynSolver, RegisteredStrategy, StrategyRegistry},
+    scoring::NytScoring,
+};
+
+/// The strategy every other strategy is compared to.
+const REFERENCE: &str = "brute-force";
+
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzOptions {
+    pub iterations: usize,
+    /// Seed of the random puzzles, which reproduces a run.
+    pub seed: u64,
+    /// Also generate puzzles with other rules than the Spelling Bee's.
+    pub vary_rules: bool,
+    pub threads: usize,
+}
+
+/// A strategy whose solution differs from brute force's.
+#[derive(Debug)]
+pub struct Divergence {
+    pub iteration: usize,
+    pub strategy: &'static str,
+    pub puzzle: PuzzleSpec,
+    /// Answers of brute force the strategy didn't find.
+    pub missing: Vec<Word>,
+    /// Answers the strategy found that brute force didn't, or scored differently.
+    pub unexpected: Vec<Word>,
+    /// A smallest found word list on which the strategy still diverges.
+    pub reproducer: Vec<Word>,
+}
+
+/// Solve `options.iterations` random puzzles with every registered strategy,
+/// returning the divergences from brute force.
+pub fn fuzz(
+    dict: &Dictionary,
+    registry: &StrategyRegistry,
+    options: &FuzzOptions,
+) -> anyhow::Result<Vec<Divergence>> {
+    let reference = build(registry.find(REFERENCE)?, dict, options.threads)?;
+    let candidates = registry
+        .iter()
+        .filter(|strategy| strategy.name != REFERENCE)
+        .map(|strategy| Ok((strategy, build(strategy, dict, options.threads)?)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let pangrams = pangrams(dict);
+    let mut rng = Rng::new(options.seed);
+    let mut divergences = Vec::new();
+    for iteration in 0..options.iterations {
+        let puzzle = random_puzzle(&mut rng, &pangrams, options.vary_rules);
+        let expected = reference.solve(&puzzle)?;
+        for (candidate, solver) in &candidates {
+            let (missing, unexpected) = compare(&expected, &solver.solve(&puzzle)?, candidate);
+            if missing.is_empty() && unexpected.is_empty() {
+                continue;
+            }
+            log::warn!(
+                "{} diverged on puzzle {iteration}, minimizing the word list",
+                candidate.name
+            );
+            let differing: Vec<Word> = missing.iter().chain(&unexpected).cloned().collect();
+            let reproducer = minimize(
+                dict,
+                &differing,
+                &puzzle,
+                registry.find(REFERENCE)?,
+                candidate,
+                options.threads,
+            )?;
+            divergences.push(Divergence {
+                iteration,
+                strategy: candidate.name,
+                puzzle: puzzle.clone(),
+                missing,
+                unexpected,
+                reproducer,
+            });
+        }
+    }
+    Ok(divergences)
+}
+
+fn build<'a>(
+    strategy: &RegisteredStrategy,
+    dict: &'a Dictionary,
+    threads: usize,
+) -> anyhow::Result<Box<dyn DynSolver<'a> + 'a>> {
+    (strategy.build)(dict, threads, Arc::new(NytScoring))
+}
+
+// the (missing, unexpected) answers of `actual` compared to `expected`.
+fn compare(
+    expected: &GameResult,
+    actual: &GameResult,
+    candidate: &RegisteredStrategy,
+) -> (Vec<Word>, Vec<Word>) {
+    let expected = if candidate.pangrams_only {
+        expected.filtered(&ResultFilter {
+            only_pangrams: true,
+            ..ResultFilter::default()
+        })
+    } else {
+        expected.clone()
+    };
+    let expected: HashSet<_> = expected.entries().into_iter().collect();
+    let actual: HashSet<_> = actual.entries().into_iter().collect();
+    let mut missing: Vec<Word> = expected
+        .difference(&actual)
+        .map(|(word, _)| (*word).clone())
+        .collect();
+    let mut unexpected: Vec<Word> = actual
+        .difference(&expected)
+        .map(|(word, _)| (*word).clone())
+        .collect();
+    missing.sort_unstable();
+    unexpected.sort_unstable();
+    (missing, unexpected)
+}
+
+/// Shrink the word list while the candidate still diverges on `puzzle`,
+/// starting from just the differing words if they suffice (they don't if the
+/// strategy's index depends on the other words).
+fn minimize(
+    dict: &Dictionary,
+    differing: &[Word],
+    puzzle: &PuzzleSpec,
+    reference: &RegisteredStrategy,
+    candidate: &RegisteredStrategy,
+    threads: usize,
+) -> anyhow::Result<Vec<Word>> {
+    let diverges = |words: &[Word]| -> anyhow::Result<bool> {
+        let dict: Dictionary = words.iter().cloned().collect();
+        let expected = build(reference, &dict, threads)?.solve(puzzle)?;
+        let actual = build(candidate, &dict, threads)?.solve(puzzle)?;
+        let (missing, unexpected) = compare(&expected, &actual, candidate);
+        Ok(!missing.is_empty() || !unexpected.is_empty())
+    };
+
+    let mut words = differing.to_vec();
+    if !diverges(&words)? {
+        words = dict.iter().cloned().collect();
+        words.sort_unstable();
+    }
+    // drop ever smaller chunks of words as long as the divergence remains.
+    let mut chunk_len = words.len().div_ceil(2);
+    while chunk_len > 0 {
+        let mut start = 0;
+        while start < words.len() {
+            let end = (start + chunk_len).min(words.len());
+            let rest: Vec<Word> = [&words[..start], &words[end..]].concat();
+            if diverges(&rest)? {
+                words = rest;
+            } else {
+                start = end;
+            }
+        }
+        chunk_len /= 2;
+    }
+    Ok(words)
+}
+
+/// The words of `dict` with seven distinct letters, sorted so that random
+/// picks are reproducible. Puzzles built around them have many answers.
+pub(crate) fn pangrams(dict: &Dictionary) -> Vec<&Word> {
+    let mut pangrams: Vec<&Word> = dict
+        .iter()
+        .filter(|word| distinct_letters(word).is_some_and(|letters| letters.len() == 7))
+        .collect();
+    pangrams.sort_unstable();
+    pangrams
+}
+
+/// Seven distinct letters, preferably those of a random pangram, with a random
+/// center letter.
+pub(crate) fn random_puzzle(rng: &mut Rng, pangrams: &[&Word], vary_rules: bool) -> PuzzleSpec {
+    let mut letters = match pangrams {
+        [] => {
+            let mut alphabet: Vec<Letter> =
+                ('A'..='Z').filter_map(|c| Letter::new(c).ok()).collect();
+            (0..7)
+                .map(|_| alphabet.swap_remove(rng.below(alphabet.len())))
+                .collect()
+        }
+        _ => distinct_letters(pangrams[rng.below(pangrams.len())]).unwrap_or_default(),
+    };
+    let center = letters.swap_remove(rng.below(letters.len()));
+    let game = Game::new(center, letters);
+
+    let rules = if vary_rules && rng.below(2) == 0 {
+        Rules {
+            min_length: 1 + rng.below(8),
+            require_center_letter: rng.below(2) == 0,
+            require_all_letters: rng.below(4) == 0,
+            require_adjacent_letters: rng.below(4) == 0,
+            max_letter_uses: (rng.below(4) == 0).then(|| 1 + rng.below(2)),
+        }
+    } else {
+        Rules::SPELLING_BEE
+    };
+    PuzzleSpec::with_rules(game, rules)
+}
+
+// the distinct letters of a word consisting only of letters.
+pub(crate) fn distinct_letters(word: &str) -> Option<Vec<Letter>> {
+    let mut letters = word
+        .chars()
+        .map(Letter::new)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    letters.sort_unstable();
+    letters.dedup();
+    Some(letters)
+}
+
+/// A small seedable random number generator (SplitMix64), so that runs can be
+/// reproduced from their seed.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // uniform enough for small `n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// The divergence and the `solve` invocation reproducing it.
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = self.puzzle.game();
+        let mut letters = game.letters();
+        let center = letters.next().map(char::from).unwrap_or_default();
+        let outer: String = letters.map(char::from).collect();
+        write!(
+            f,
+            "{} diverged from brute force on puzzle {} (center {center}, letters {outer}",
+            self.strategy, self.iteration
+        )?;
+        match &self.puzzle {
+            PuzzleSpec::SpellingBee(_) => writeln!(f, ")")?,
+            PuzzleSpec::Variant(_, rules) => writeln!(f, ", {rules:?})")?,
+        }
+        writeln!(f, "  missing: {}", self.missing.join(", "))?;
+        writeln!(f, "  unexpected: {}", self.unexpected.join(", "))?;
+        write!(f, "  reproducer word list: {}", self.reproducer.join(" "))
+    }
+}
@@ -1,50 +1,387 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+#[cfg(feature = "parallel")]
 use anyhow::Context;
 use derive_new::new;
-use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use rayon::{ThreadPool, ThreadPoolBuilder, prelude::*};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-type Letter = char;
-type Word = String;
-type Points = usize;
+use crate::{
+    alphabet::{self, LetterSet, MAX_LETTERS},
+    date::PuzzleDate,
+    dictionary::{Dictionary, WordList},
+    layout::Layout,
+    scoring::{NytScoring, PointsBreakdown, Scoring},
+    share,
+};
 
-#[derive(Debug, new)]
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::{ParallelBruteForce, ParallelLetterMap};
+
+/// An uppercase letter from A to Z, or of a language pack's alphabet, e.g. `Ñ`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "char", into = "char")]
+pub struct Letter(char);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("'{0}' is not a letter of any supported alphabet")]
+pub struct InvalidLetter(pub char);
+
+/// Why letters don't make a puzzle that can be solved.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidPuzzle {
+    #[error("center must be a single letter, got '{0}'")]
+    InvalidCenter(String),
+    #[error("center letter may not be part of non center letters")]
+    CenterInOuterLetters,
+    #[error("letters can only be required to be adjacent with six distinct outer letters")]
+    NoLayout,
+    #[error("the center letter is missing, expected it alone on one side of '/' or '+'")]
+    MissingCenter,
+    #[error("letter '{0}' occurs more than once")]
+    DuplicateLetter(Letter),
+    #[error("expected 6 letters besides the center letter, got {0}")]
+    LetterCount(usize),
+}
+
+impl Letter {
+    /// Uppercase `letter`, rejecting anything but the letters of
+    /// [`alphabet`](crate::alphabet): A to Z and those of language packs.
+    pub fn new(letter: char) -> Result<Letter, InvalidLetter> {
+        if letter.is_ascii_alphabetic() {
+            return Ok(Letter(letter.to_ascii_uppercase()));
+        }
+        let mut uppercase = letter.to_uppercase();
+        match (uppercase.next(), uppercase.next()) {
+            (Some(upper), None) if alphabet::letter_index(upper).is_some() => Ok(Letter(upper)),
+            _ => Err(InvalidLetter(letter)),
+        }
+    }
+
+    /// The index among all supported letters, from 0 for A to 25 for Z,
+    /// followed by the others, see [`LetterSet`].
+    pub fn index(self) -> u8 {
+        alphabet::letter_index(self.0).expect("letters are supported")
+    }
+
+    pub(crate) fn from_index(index: u8) -> Option<Letter> {
+        alphabet::index_letter(index).map(Letter)
+    }
+}
+
+impl TryFrom<char> for Letter {
+    type Error = InvalidLetter;
+
+    fn try_from(letter: char) -> Result<Self, Self::Error> {
+        Letter::new(letter)
+    }
+}
+
+impl From<Letter> for char {
+    fn from(letter: Letter) -> Self {
+        letter.0
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How often each letter occurs, e.g. in a word: a multiset of letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LetterCounts([usize; MAX_LETTERS]);
+
+impl LetterCounts {
+    pub fn new() -> Self {
+        LetterCounts([0; MAX_LETTERS])
+    }
+
+    /// The letters of `word`, skipping any other characters.
+    pub fn of(word: &str) -> Self {
+        let mut counts = LetterCounts::new();
+        for letter in letters(word) {
+            counts.add(letter);
+        }
+        counts
+    }
+
+    pub fn add(&mut self, letter: Letter) {
+        self.0[usize::from(letter.index())] += 1;
+    }
+
+    pub fn count(&self, letter: Letter) -> usize {
+        self.0[usize::from(letter.index())]
+    }
+
+    /// The first letter by index occurring more than `max` times.
+    pub fn exceeding(&self, max: usize) -> Option<Letter> {
+        (0..MAX_LETTERS as u8)
+            .find(|&index| self.0[usize::from(index)] > max)
+            .and_then(Letter::from_index)
+    }
+}
+
+impl Default for LetterCounts {
+    fn default() -> Self {
+        LetterCounts::new()
+    }
+}
+
+pub type Word = String;
+pub type Points = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct Score {
+    /// The total of `breakdown`.
+    pub points: Points,
+    pub breakdown: PointsBreakdown,
+    pub is_pangram: bool,
+}
+
+#[derive(Debug, Clone, new)]
 pub struct Game {
     center_letter: Letter,
     non_center_letters: Vec<Letter>,
+    /// Set for official puzzles.
+    #[new(default)]
+    metadata: Option<PuzzleMetadata>,
+}
+
+/// Where an official puzzle was published, carried into its solution so that
+/// exported solutions describe themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleMetadata {
+    pub date: PuzzleDate,
+    /// The NYT's id of the puzzle.
+    pub id: u64,
+    pub editor: Option<String>,
+    /// The weekday of `date` as displayed by NYT, e.g. `Thursday`.
+    pub weekday: String,
+}
+
+impl PuzzleMetadata {
+    pub fn new(date: PuzzleDate, id: u64, editor: Option<String>) -> PuzzleMetadata {
+        PuzzleMetadata {
+            date,
+            id,
+            editor,
+            weekday: date.weekday(),
+        }
+    }
+}
+
+/// e.g. `Thursday 2024-06-06 (#21934), edited by Sam Ezersky`.
+impl fmt::Display for PuzzleMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} (#{})", self.weekday, self.date, self.id)?;
+        if let Some(editor) = &self.editor {
+            write!(f, ", edited by {editor}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Build a game from user input such as `c` and `altefi`, ignoring case
+    /// and whitespace.
+    pub fn parse(center: &str, letters: &str) -> anyhow::Result<Game> {
+        let mut center_chars = center.trim().chars();
+        let (Some(center_letter), None) = (center_chars.next(), center_chars.next()) else {
+            return Err(InvalidPuzzle::InvalidCenter(center.to_string()).into());
+        };
+        Ok(Game::new(
+            Letter::new(center_letter)?,
+            letters
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(Letter::new)
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    /// All letters, the center letter first.
+    pub fn letters(&self) -> impl Iterator<Item = Letter> + '_ {
+        std::iter::once(self.center_letter).chain(self.non_center_letters.iter().copied())
+    }
+
+    /// Mark the game as the official puzzle described by `metadata`.
+    pub fn with_metadata(mut self, metadata: PuzzleMetadata) -> Game {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn metadata(&self) -> Option<&PuzzleMetadata> {
+        self.metadata.as_ref()
+    }
+}
+
+/// Parse a puzzle in any of the formats of [`share::parse_game`], e.g.
+/// `L A T E F I / C`, `c + altefi` or a pasted share text.
+impl FromStr for Game {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Game> {
+        share::parse_game(text)
+    }
+}
+
+/// The rules a puzzle is played by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// Minimum number of letters of an answer.
+    pub min_length: usize,
+    /// Whether answers must contain the center letter.
+    pub require_center_letter: bool,
+    /// Whether answers must use every letter, i.e. only pangrams are accepted.
+    pub require_all_letters: bool,
+    /// Whether consecutive letters of answers must be adjacent in the hive (see
+    /// [`Layout`]), like in Boggle.
+    pub require_adjacent_letters: bool,
+    /// How often each letter may occur in an answer, e.g. 1 for variants that
+    /// don't allow reusing letters. Unlimited in the Spelling Bee.
+    pub max_letter_uses: Option<usize>,
+}
+
+impl Rules {
+    /// The rules of the NYT Spelling Bee.
+    pub const SPELLING_BEE: Rules = Rules {
+        min_length: 4,
+        require_center_letter: true,
+        require_all_letters: false,
+        require_adjacent_letters: false,
+        max_letter_uses: None,
+    };
+}
+
+/// A puzzle together with the rules it is played by.
+#[derive(Debug, Clone)]
+pub enum PuzzleSpec {
+    SpellingBee(Game),
+    /// A related letter puzzle played by different rules.
+    Variant(Game, Rules),
+}
+
+impl PuzzleSpec {
+    /// Use the Spelling Bee rules unless `rules` differ from them.
+    pub fn with_rules(game: Game, rules: Rules) -> PuzzleSpec {
+        if rules == Rules::SPELLING_BEE {
+            PuzzleSpec::SpellingBee(game)
+        } else {
+            PuzzleSpec::Variant(game, rules)
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        match self {
+            PuzzleSpec::SpellingBee(game) | PuzzleSpec::Variant(game, _) => game,
+        }
+    }
+
+    pub fn rules(&self) -> Rules {
+        match self {
+            PuzzleSpec::SpellingBee(_) => Rules::SPELLING_BEE,
+            PuzzleSpec::Variant(_, rules) => *rules,
+        }
+    }
+
+    /// Evaluate a single guess, reporting the violated rule if it is invalid.
+    pub fn check<D: WordList + ?Sized>(
+        &self,
+        word: &str,
+        dict: &D,
+    ) -> anyhow::Result<Result<Score, GuessingError>> {
+        let processed: GameProcessed = self.try_into()?;
+        let word = word.trim().to_uppercase();
+        Ok(Guess::new(&word).eval_points(&processed, dict))
+    }
+}
+
+impl From<Game> for PuzzleSpec {
+    fn from(game: Game) -> Self {
+        PuzzleSpec::SpellingBee(game)
+    }
 }
 
 // invariant: center letter is not contained within non center letters.
 pub struct GameProcessed {
     center_letter: Letter,
     non_center_letters: HashSet<Letter>,
+    // all letters, for evaluating words without allocating.
+    letter_set: LetterSet,
+    rules: Rules,
+    scoring: Arc<dyn Scoring>,
+    // only needed for `Rules::require_adjacent_letters`.
+    layout: Option<Layout>,
+    metadata: Option<PuzzleMetadata>,
+    // when strategies stop examining candidates, see `GameSolver::solve_with_deadline`.
+    deadline: Option<Instant>,
 }
 
 impl GameProcessed {
     pub fn letter_count(&self) -> usize {
-        return self.non_center_letters.len() + 1;
+        self.letter_set.len()
+    }
+
+    pub fn center_letter(&self) -> Letter {
+        self.center_letter
+    }
+
+    /// The non center letters in alphabetical order.
+    pub fn non_center_letters(&self) -> Vec<Letter> {
+        let mut letters: Vec<_> = self.non_center_letters.iter().copied().collect();
+        letters.sort_unstable();
+        letters
+    }
+
+    pub fn metadata(&self) -> Option<&PuzzleMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Whether a strategy should stop examining candidates.
+    pub fn is_past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
     }
 }
 
-impl TryFrom<&Game> for GameProcessed {
+impl TryFrom<&PuzzleSpec> for GameProcessed {
     type Error = anyhow::Error;
 
-    fn try_from(game: &Game) -> Result<Self, Self::Error> {
-        anyhow::ensure!(
-            game.non_center_letters
-                .iter()
-                .find(|&&letter| letter == game.center_letter)
-                .is_none(),
-            "center letter may not be part of non center letters"
-        );
-        Ok(GameProcessed {
+    fn try_from(puzzle: &PuzzleSpec) -> Result<Self, Self::Error> {
+        let game = puzzle.game();
+        if game.non_center_letters.contains(&game.center_letter) {
+            return Err(InvalidPuzzle::CenterInOuterLetters.into());
+        }
+        let mut processed = GameProcessed {
             center_letter: game.center_letter,
             non_center_letters: game
                 .non_center_letters
                 .iter()
                 .copied()
                 .collect::<HashSet<_>>(),
-        })
+            letter_set: game.letters().collect(),
+            rules: puzzle.rules(),
+            scoring: Arc::new(NytScoring),
+            layout: None,
+            metadata: game.metadata.clone(),
+            deadline: None,
+        };
+        if processed.rules.require_adjacent_letters {
+            processed.layout = Some(Layout::new(&processed).ok_or(InvalidPuzzle::NoLayout)?);
+        }
+        Ok(processed)
     }
 }
 
@@ -52,11 +389,25 @@ struct Guess<'a> {
     guessed_word: &'a Word,
 }
 
-enum GuessingError {
-    TooShort,
+/// Why a guess is not a valid answer, serialized with its `kind` in snake case
+/// (e.g. `{"kind":"disallowed_letter","letter":"X"}`).
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GuessingError {
+    #[error("word is shorter than {min_length} letters")]
+    TooShort { min_length: usize },
+    #[error("word is not in the dictionary")]
     UnknownWord,
-    DisallowedLetter(Letter),
+    #[error("letter '{letter}' is not part of the puzzle")]
+    DisallowedLetter { letter: char },
+    #[error("word does not use the center letter")]
     MissingCenterLetter,
+    #[error("word does not use every letter")]
+    UnusedLetters,
+    #[error("letters '{first}' and '{second}' are not adjacent in the hive")]
+    NonAdjacentLetters { first: char, second: char },
+    #[error("letter '{letter}' is used too often, at most {max_uses} allowed")]
+    ReusedLetter { letter: char, max_uses: usize },
 }
 
 impl<'a> Guess<'a> {
@@ -64,111 +415,712 @@ impl<'a> Guess<'a> {
         Guess { guessed_word: word }
     }
 
-    fn eval_points(
+    fn eval_points<D: WordList + ?Sized>(
         &self,
         game: &GameProcessed,
-        dict: &Dictionary,
-    ) -> Result<Points, GuessingError> {
+        dict: &D,
+    ) -> Result<Score, GuessingError> {
         // Rules:
         // - Words must contain at least 4 letters.
         // - Words must include the center letter.
         // - Our word list does not include words that are obscure, hyphenated, or proper nouns.
         // - No cussing either, sorry.
         // - Letters can be used more than once.
-
-        if self.guessed_word.len() < 4 {
-            return Err(GuessingError::TooShort);
-        }
-        if !dict.words.contains(self.guessed_word) {
+        // Variants may change the minimum length, drop the center letter requirement, only
+        // accept words using every letter or limit how often letters are reused.
+        self.check_length(game)?;
+        if !dict.contains(self.guessed_word) {
             return Err(GuessingError::UnknownWord);
         }
+        self.eval_letters(game)
+    }
 
-        let mut center_letter_count = 0;
-        let mut guessed_letters = HashSet::new();
+    /// Like `eval_points`, for a word known to be in the dictionary.
+    fn eval_listed_points(&self, game: &GameProcessed) -> Result<Score, GuessingError> {
+        self.check_length(game)?;
+        self.eval_letters(game)
+    }
+
+    fn check_length(&self, game: &GameProcessed) -> Result<(), GuessingError> {
+        let min_length = game.rules.min_length;
+        if self.guessed_word.chars().count() < min_length {
+            return Err(GuessingError::TooShort { min_length });
+        }
+        Ok(())
+    }
+
+    fn eval_letters(&self, game: &GameProcessed) -> Result<Score, GuessingError> {
+        let rules = game.rules;
+
+        // the letters are tracked as a bitmask, so that evaluating a word doesn't allocate.
+        let mut guessed_letters = LetterSet::EMPTY;
         for c in self.guessed_word.chars() {
-            if c == game.center_letter {
-                guessed_letters.insert(c);
-                center_letter_count += 1;
-            } else if game.non_center_letters.contains(&c) {
-                guessed_letters.insert(c);
-            } else {
-                return Err(GuessingError::DisallowedLetter(c));
+            match Letter::new(c) {
+                Ok(letter) if game.letter_set.contains(letter) => guessed_letters.insert(letter),
+                _ => return Err(GuessingError::DisallowedLetter { letter: c }),
             }
         }
 
-        if center_letter_count == 0 {
+        if rules.require_center_letter && !guessed_letters.contains(game.center_letter) {
             return Err(GuessingError::MissingCenterLetter);
         }
 
-        let is_pangram = game.letter_count() == guessed_letters.len();
+        if let Some(max_uses) = rules.max_letter_uses
+            && let Some(letter) = LetterCounts::of(self.guessed_word).exceeding(max_uses)
+        {
+            return Err(GuessingError::ReusedLetter {
+                letter: letter.into(),
+                max_uses,
+            });
+        }
+
+        if let (true, Some(layout)) = (rules.require_adjacent_letters, &game.layout) {
+            let mut letters = letters(self.guessed_word);
+            let mut previous = letters.next();
+            for letter in letters {
+                if let Some(first) = previous
+                    && !layout.are_adjacent(first, letter)
+                {
+                    return Err(GuessingError::NonAdjacentLetters {
+                        first: first.into(),
+                        second: letter.into(),
+                    });
+                }
+                previous = Some(letter);
+            }
+        }
 
-        // How points are awarded:
-        // 4-letter words are worth 1 point each.
-        // Longer words earn 1 point per letter (this spec is slightly unclear here, but should be interpreted as: "earn 1 **extra** point for every letter other than the first 4").
-        // Each puzzle includes at least one "pangram" which uses every letter. These are worth 7 extra points!
-        let points = 1 + (self.guessed_word.len() - 4) + (if is_pangram { 7 } else { 0 });
+        let is_pangram = guessed_letters == game.letter_set;
+        if rules.require_all_letters && !is_pangram {
+            return Err(GuessingError::UnusedLetters);
+        }
 
-        Ok(points)
+        let breakdown = game.scoring.points(self.guessed_word, is_pangram, &rules);
+        Ok(Score {
+            points: breakdown.total(),
+            breakdown,
+            is_pangram,
+        })
     }
 }
 
-const WORD_LIST_URL: &str =
-    "https://raw.githubusercontent.com/rressler/data_raw_courses/main/scrabble_words.txt";
-
-pub struct Dictionary {
-    // TODO: remove pub
-    pub words: HashSet<Word>,
+// words are kept in alphabetical order, so that results are printed and compared deterministically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult<'a> {
+    word_to_score: BTreeMap<&'a Word, Score>,
+    // all letters of the solved puzzle.
+    letters: BTreeSet<Letter>,
+    // of the rules the puzzle was solved with.
+    min_length: usize,
+    metadata: Option<PuzzleMetadata>,
+    order: AnswerOrder,
 }
 
-impl Dictionary {
-    pub fn scrape() -> anyhow::Result<Dictionary> {
-        let response = reqwest::blocking::get(WORD_LIST_URL)
-            .with_context(|| format!("failed to GET {}", WORD_LIST_URL))?
-            .error_for_status()?
-            .text()
-            .context("failed to read response body as text")?;
+impl<'a> GameResult<'a> {
+    fn new(word_to_score: BTreeMap<&'a Word, Score>, game: &GameProcessed) -> Self {
+        let letters = std::iter::once(game.center_letter)
+            .chain(game.non_center_letters.iter().copied())
+            .collect();
+        GameResult {
+            word_to_score,
+            letters,
+            min_length: game.rules.min_length,
+            metadata: game.metadata.clone(),
+            order: AnswerOrder::default(),
+        }
+    }
+
+    /// The minimum length of answers of the rules the puzzle was solved with.
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Where the solved puzzle was published, if it is an official one.
+    pub fn metadata(&self) -> Option<&PuzzleMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// List the answers in `order` from now on.
+    pub fn with_order(mut self, order: AnswerOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn order(&self) -> AnswerOrder {
+        self.order
+    }
+
+    /// All found words with their scores, in the result's order, which is
+    /// alphabetical by default.
+    pub fn entries(&self) -> Vec<(&'a Word, Score)> {
+        let mut entries: Vec<_> = self
+            .word_to_score
+            .iter()
+            .map(|(&word, &score)| (word, score))
+            .collect();
+        self.order.sort(&mut entries);
+        entries
+    }
+
+    pub fn total_points(&self) -> Points {
+        self.word_to_score.values().map(|score| score.points).sum()
+    }
 
-        let words: HashSet<String> = response
-            .lines()
-            // filter out non-word lines: only keep non-empty lines with only uppercase chars.
-            .filter(|line| !line.is_empty() && line.chars().all(char::is_uppercase))
-            // filter out short words.
-            .filter(|line| line.len() >= 4)
-            .map(str::trim)
-            .map(|line| line.to_string())
+    /// Only keep the words for which `keep` returns true.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Word) -> bool) {
+        self.word_to_score.retain(|word, _| keep(word));
+    }
+
+    /// The words matching `filter`, paginated in the result's order.
+    pub fn filtered(&self, filter: &ResultFilter) -> GameResult<'a> {
+        let word_to_score = self
+            .entries()
+            .into_iter()
+            .filter(|(word, score)| filter.matches(word, score))
+            .skip(filter.offset)
+            .take(filter.limit.unwrap_or(usize::MAX))
             .collect();
+        GameResult {
+            word_to_score,
+            letters: self.letters.clone(),
+            min_length: self.min_length,
+            metadata: self.metadata.clone(),
+            order: self.order,
+        }
+    }
 
-        Ok(Dictionary { words })
+    /// The number of words starting with each letter of the puzzle.
+    pub fn starting_letter_counts(&self) -> BTreeMap<Letter, usize> {
+        let mut counts: BTreeMap<Letter, usize> =
+            self.letters.iter().map(|&letter| (letter, 0)).collect();
+        for word in self.word_to_score.keys() {
+            let first = word
+                .chars()
+                .next()
+                .and_then(|first| Letter::new(first).ok());
+            if let Some(count) = first.and_then(|first| counts.get_mut(&first)) {
+                *count += 1;
+            }
+        }
+        counts
+    }
+
+    /// How many words use and start with each letter of the puzzle.
+    pub fn letter_usage(&self) -> BTreeMap<Letter, LetterUsage> {
+        let mut usage: BTreeMap<Letter, LetterUsage> = self
+            .letters
+            .iter()
+            .map(|&letter| (letter, LetterUsage::default()))
+            .collect();
+        for word in self.word_to_score.keys() {
+            for letter in letters(word).collect::<BTreeSet<_>>() {
+                if let Some(usage) = usage.get_mut(&letter) {
+                    usage.words += 1;
+                }
+            }
+        }
+        for (letter, starting) in self.starting_letter_counts() {
+            if let Some(usage) = usage.get_mut(&letter) {
+                usage.starting = starting;
+            }
+        }
+        usage
+    }
+
+    /// Whether every letter of the puzzle starts at least one word.
+    pub fn has_bingo(&self) -> bool {
+        self.starting_letter_counts()
+            .values()
+            .all(|&count| count > 0)
     }
 }
 
-#[derive(Debug)]
-pub struct GameResult<'a> {
-    word_to_points: HashMap<&'a Word, Points>,
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct LetterUsage {
+    /// The number of words containing the letter at least once.
+    pub words: usize,
+    /// The number of words starting with the letter.
+    pub starting: usize,
+}
+
+/// Which words of a result to keep. The default keeps every word.
+#[derive(Debug, Clone, Default)]
+pub struct ResultFilter {
+    pub min_points: Points,
+    pub min_length: usize,
+    pub only_pangrams: bool,
+    /// Number of matching words to skip.
+    pub offset: usize,
+    /// Maximum number of words to keep after skipping.
+    pub limit: Option<usize>,
+}
+
+impl ResultFilter {
+    /// Whether the answer is kept, regardless of `offset` and `limit`.
+    pub fn matches(&self, word: &Word, score: &Score) -> bool {
+        score.points >= self.min_points
+            && word.chars().count() >= self.min_length
+            && (score.is_pangram || !self.only_pangrams)
+    }
+}
+
+/// The order in which a result lists its answers, in every output format.
+/// Ties are broken alphabetically, so that each order is deterministic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerOrder {
+    #[default]
+    Alpha,
+    /// The highest scoring answers first.
+    PointsDesc,
+    /// The shortest answers first.
+    LengthThenAlpha,
+    /// The pangrams first, then the other answers.
+    PangramsFirst,
+}
+
+impl AnswerOrder {
+    pub const ALL: [AnswerOrder; 4] = [
+        AnswerOrder::Alpha,
+        AnswerOrder::PointsDesc,
+        AnswerOrder::LengthThenAlpha,
+        AnswerOrder::PangramsFirst,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AnswerOrder::Alpha => "alpha",
+            AnswerOrder::PointsDesc => "points_desc",
+            AnswerOrder::LengthThenAlpha => "length_then_alpha",
+            AnswerOrder::PangramsFirst => "pangrams_first",
+        }
+    }
+
+    // `entries` are sorted alphabetically, which the stable sorts keep for ties.
+    fn sort(self, entries: &mut [(&Word, Score)]) {
+        match self {
+            AnswerOrder::Alpha => {}
+            AnswerOrder::PointsDesc => {
+                entries.sort_by_key(|(_, score)| std::cmp::Reverse(score.points))
+            }
+            AnswerOrder::LengthThenAlpha => entries.sort_by_key(|(word, _)| word.chars().count()),
+            AnswerOrder::PangramsFirst => entries.sort_by_key(|(_, score)| !score.is_pangram),
+        }
+    }
+}
+
+/// Accepts the names of [`AnswerOrder::name`], with `-` or `_`.
+impl FromStr for AnswerOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().replace('-', "_");
+        AnswerOrder::ALL
+            .into_iter()
+            .find(|order| order.name().eq_ignore_ascii_case(&name))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown order '{s}', expected alpha, points_desc, length_then_alpha or pangrams_first"
+                )
+            })
+    }
+}
+
+impl fmt::Display for AnswerOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// How many candidate words a strategy examined, and why the rejected ones were rejected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SolveStats {
+    pub examined: usize,
+    pub too_short: usize,
+    pub unknown_word: usize,
+    pub disallowed_letter: usize,
+    pub missing_center_letter: usize,
+    pub unused_letters: usize,
+    pub non_adjacent_letters: usize,
+    pub reused_letter: usize,
+    /// Candidates left unexamined because the deadline passed, see
+    /// [`GameSolver::solve_with_deadline`].
+    pub skipped: usize,
+}
+
+impl SolveStats {
+    fn record(&mut self, outcome: &Result<Score, GuessingError>) {
+        self.examined += 1;
+        match outcome {
+            Ok(_) => {}
+            Err(GuessingError::TooShort { .. }) => self.too_short += 1,
+            Err(GuessingError::UnknownWord) => self.unknown_word += 1,
+            Err(GuessingError::DisallowedLetter { .. }) => self.disallowed_letter += 1,
+            Err(GuessingError::MissingCenterLetter) => self.missing_center_letter += 1,
+            Err(GuessingError::UnusedLetters) => self.unused_letters += 1,
+            Err(GuessingError::NonAdjacentLetters { .. }) => self.non_adjacent_letters += 1,
+            Err(GuessingError::ReusedLetter { .. }) => self.reused_letter += 1,
+        }
+    }
+
+    /// Combine the stats of solving two disjoint sets of candidates.
+    pub fn merge(self, other: SolveStats) -> SolveStats {
+        SolveStats {
+            examined: self.examined + other.examined,
+            too_short: self.too_short + other.too_short,
+            unknown_word: self.unknown_word + other.unknown_word,
+            disallowed_letter: self.disallowed_letter + other.disallowed_letter,
+            missing_center_letter: self.missing_center_letter + other.missing_center_letter,
+            unused_letters: self.unused_letters + other.unused_letters,
+            non_adjacent_letters: self.non_adjacent_letters + other.non_adjacent_letters,
+            reused_letter: self.reused_letter + other.reused_letter,
+            skipped: self.skipped + other.skipped,
+        }
+    }
+
+    pub fn accepted(&self) -> usize {
+        self.examined
+            - self.too_short
+            - self.unknown_word
+            - self.disallowed_letter
+            - self.missing_center_letter
+            - self.unused_letters
+            - self.non_adjacent_letters
+            - self.reused_letter
+    }
+}
+
+impl fmt::Display for SolveStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "examined {} words, accepted {}, rejected {} as too short, {} as unknown, {} for a disallowed letter, {} for a missing center letter, {} for unused letters, {} for non adjacent letters and {} for a reused letter",
+            self.examined,
+            self.accepted(),
+            self.too_short,
+            self.unknown_word,
+            self.disallowed_letter,
+            self.missing_center_letter,
+            self.unused_letters,
+            self.non_adjacent_letters,
+            self.reused_letter
+        )?;
+        if self.skipped > 0 {
+            write!(f, ", skipped {} after the deadline", self.skipped)?;
+        }
+        Ok(())
+    }
+}
+
+/// A result found within a time budget, which lacks any answers among the
+/// candidates left when the budget ran out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialResult<'a> {
+    pub result: GameResult<'a>,
+    pub stats: SolveStats,
+}
+
+impl PartialResult<'_> {
+    /// Whether the deadline passed before every candidate was examined.
+    pub fn is_partial(&self) -> bool {
+        self.stats.skipped > 0
+    }
+
+    /// The fraction of the candidates that were examined, from 0 to 1.
+    pub fn completion(&self) -> f64 {
+        let candidates = self.stats.examined + self.stats.skipped;
+        if candidates == 0 {
+            1.0
+        } else {
+            self.stats.examined as f64 / candidates as f64
+        }
+    }
+}
+
+/// Evaluate every candidate word, collecting the valid ones.
+fn evaluate<'a, D: WordList + ?Sized>(
+    candidates: impl Iterator<Item = &'a Word>,
+    game: &GameProcessed,
+    dict: &D,
+) -> (GameResult<'a>, SolveStats) {
+    let mut word_to_score = BTreeMap::new();
+    let mut stats = SolveStats::default();
+    for word in candidates {
+        if game.is_past_deadline() {
+            stats.skipped += 1;
+            continue;
+        }
+        let outcome = Guess::new(word).eval_points(game, dict);
+        stats.record(&outcome);
+        if let Ok(score) = outcome {
+            word_to_score.insert(word, score);
+        }
+    }
+    (GameResult::new(word_to_score, game), stats)
+}
+
+/// The answers among words read one at a time, e.g. from a word list too large
+/// to load, in the order they are found. Only the answers are kept, to skip
+/// words that are read again.
+pub struct AnswerStream<I> {
+    words: I,
+    game: GameProcessed,
+    found: HashSet<Word>,
+    stats: SolveStats,
+}
+
+impl<I: Iterator<Item = Word>> AnswerStream<I> {
+    pub fn new(
+        puzzle: &PuzzleSpec,
+        words: impl IntoIterator<IntoIter = I>,
+    ) -> anyhow::Result<Self> {
+        Ok(AnswerStream {
+            words: words.into_iter(),
+            game: GameProcessed::try_from(puzzle)?,
+            found: HashSet::new(),
+            stats: SolveStats::default(),
+        })
+    }
+
+    /// Score answers with `scoring` instead of the NYT scoring.
+    pub fn with_scoring(mut self, scoring: Arc<dyn Scoring>) -> Self {
+        self.game.scoring = scoring;
+        self
+    }
+
+    /// The words examined so far, and why the rejected ones were rejected.
+    pub fn stats(&self) -> SolveStats {
+        self.stats
+    }
+}
+
+impl<I: Iterator<Item = Word>> Iterator for AnswerStream<I> {
+    type Item = (Word, Score);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let word = self.words.next()?;
+            let outcome = Guess::new(&word).eval_listed_points(&self.game);
+            self.stats.record(&outcome);
+            if let Ok(score) = outcome
+                && self.found.insert(word.clone())
+            {
+                return Some((word, score));
+            }
+        }
+    }
+}
+
+/// The letters of a dictionary word for indexing, skipping any other characters
+/// (such words are rejected as answers anyway).
+fn letters(word: &str) -> impl Iterator<Item = Letter> + '_ {
+    word.chars().filter_map(|c| Letter::new(c).ok())
+}
+
+/// Like `letters`, but each letter only once, by index.
+fn distinct_letters(word: &str) -> impl Iterator<Item = Letter> {
+    letters(word).collect::<LetterSet>().iter()
 }
 
 pub trait SolveStrategy<'a> {
     fn new(dict: &'a Dictionary) -> Self;
 
-    fn solve(&self, game: &GameProcessed) -> GameResult<'a>;
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats);
+
+    /// Index `word` as if it had been in the dictionary the strategy was built
+    /// from. Returns false if the strategy doesn't support incremental updates
+    /// and has to be rebuilt instead.
+    fn insert(&mut self, _word: &'a Word) -> bool {
+        false
+    }
+
+    /// Stop finding `word` as if it hadn't been in the dictionary. Returns
+    /// false if the strategy has to be rebuilt instead.
+    fn remove(&mut self, _word: &Word) -> bool {
+        false
+    }
+}
+
+/// The words of the dictionary an index was built from, with the words
+/// inserted into and removed from the index since.
+struct IndexedWords<'a> {
+    dict: &'a Dictionary,
+    // keyed by `&str` for `WordList::contains`.
+    inserted: HashMap<&'a str, &'a Word>,
+    /// Words of `dict` that were removed.
+    removed: HashSet<Word>,
+}
+
+impl<'a> IndexedWords<'a> {
+    fn new(dict: &'a Dictionary) -> Self {
+        IndexedWords {
+            dict,
+            inserted: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Add `word`, returning whether it wasn't indexed yet.
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.dict.contains(word) {
+            self.removed.remove(word)
+        } else {
+            self.inserted.insert(word, word).is_none()
+        }
+    }
+
+    /// Remove `word`, returning whether it was indexed.
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.dict.contains(word) {
+            self.removed.insert(word.clone())
+        } else {
+            self.inserted.remove(word.as_str()).is_some()
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &'a Word> + '_ {
+        let dict: &'a Dictionary = self.dict;
+        dict.iter()
+            .filter(|word| !self.removed.contains(*word))
+            .chain(self.inserted.values().copied())
+    }
+
+    #[cfg(feature = "parallel")]
+    fn par_iter(&self) -> impl ParallelIterator<Item = &'a Word> + '_ {
+        let dict: &'a Dictionary = self.dict;
+        dict.par_iter()
+            .filter(|word| !self.removed.contains(*word))
+            .chain(self.inserted.par_iter().map(|(_, &word)| word))
+    }
+}
+
+impl WordList for IndexedWords<'_> {
+    fn contains(&self, word: &str) -> bool {
+        if self.dict.contains(word) {
+            !self.removed.contains(word)
+        } else {
+            self.inserted.contains_key(word)
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.dict.len() - self.removed.len() + self.inserted.len()
+    }
 }
 
 pub struct GameSolver<S> {
     strategy: S,
+    scoring: Arc<dyn Scoring>,
+    // index construction and solving run inside this pool rather than rayon's global one.
+    #[cfg(feature = "parallel")]
+    pool: ThreadPool,
 }
 
 impl<'a, S> GameSolver<S>
 where
-    S: SolveStrategy<'a>,
+    S: SolveStrategy<'a> + Send + Sync,
 {
-    pub fn new(dict: &'a Dictionary) -> Self {
-        let strategy = S::new(dict);
-        GameSolver { strategy }
+    /// Create a solver whose strategy runs on `threads` threads, where 0
+    /// picks rayon's default (one per CPU).
+    #[cfg(feature = "parallel")]
+    pub fn new(dict: &'a Dictionary, threads: usize) -> anyhow::Result<Self> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .context("failed to build thread pool")?;
+        let strategy = pool.install(|| S::new(dict));
+        Ok(GameSolver {
+            strategy,
+            scoring: Arc::new(NytScoring),
+            pool,
+        })
     }
 
-    pub fn solve(&self, game: &Game) -> anyhow::Result<GameResult<'a>> {
-        let processed: GameProcessed = game.try_into()?;
-        Ok(self.strategy.solve(&processed))
+    /// Create a solver. Without the `parallel` feature everything runs on the
+    /// calling thread, so `threads` is ignored.
+    #[cfg(not(feature = "parallel"))]
+    pub fn new(dict: &'a Dictionary, _threads: usize) -> anyhow::Result<Self> {
+        Ok(GameSolver {
+            strategy: S::new(dict),
+            scoring: Arc::new(NytScoring),
+        })
+    }
+
+    /// Score answers with `scoring` instead of the NYT scoring.
+    pub fn with_scoring(mut self, scoring: Arc<dyn Scoring>) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Update the index after `word` was added to the dictionary, see
+    /// [`SolveStrategy::insert`].
+    pub fn insert(&mut self, word: &'a Word) -> bool {
+        self.strategy.insert(word)
+    }
+
+    /// Update the index after `word` was removed from the dictionary, see
+    /// [`SolveStrategy::remove`].
+    pub fn remove(&mut self, word: &Word) -> bool {
+        self.strategy.remove(word)
+    }
+
+    pub fn solve(&self, puzzle: &PuzzleSpec) -> anyhow::Result<GameResult<'a>> {
+        Ok(self.solve_with_stats(puzzle)?.0)
+    }
+
+    pub fn solve_with_stats(
+        &self,
+        puzzle: &PuzzleSpec,
+    ) -> anyhow::Result<(GameResult<'a>, SolveStats)> {
+        let processed = self.process(puzzle)?;
+        Ok(self.install(|| self.strategy.solve(&processed)))
+    }
+
+    /// Solve within `budget`, returning the answers found when it runs out.
+    /// Candidates left by then are counted in [`SolveStats::skipped`].
+    /// Building the index isn't part of the budget.
+    pub fn solve_with_deadline(
+        &self,
+        puzzle: &PuzzleSpec,
+        budget: Duration,
+    ) -> anyhow::Result<PartialResult<'a>> {
+        let mut processed = self.process(puzzle)?;
+        processed.deadline = Some(Instant::now() + budget);
+        let (result, stats) = self.install(|| self.strategy.solve(&processed));
+        Ok(PartialResult { result, stats })
+    }
+
+    /// Solve several puzzles with the same index. With the `parallel` feature,
+    /// the puzzles are solved in parallel to each other.
+    pub fn solve_many(&self, puzzles: &[PuzzleSpec]) -> Vec<anyhow::Result<GameResult<'a>>> {
+        #[cfg(feature = "parallel")]
+        let puzzles = puzzles.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let puzzles = puzzles.iter();
+        self.install(|| {
+            puzzles
+                .map(|puzzle| {
+                    let processed = self.process(puzzle)?;
+                    Ok(self.strategy.solve(&processed).0)
+                })
+                .collect()
+        })
+    }
+
+    fn process(&self, puzzle: &PuzzleSpec) -> anyhow::Result<GameProcessed> {
+        let mut processed = GameProcessed::try_from(puzzle)?;
+        processed.scoring = Arc::clone(&self.scoring);
+        Ok(processed)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        self.pool.install(op)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn install<R>(&self, op: impl FnOnce() -> R) -> R {
+        op()
     }
 }
 
@@ -181,126 +1133,531 @@ impl<'a> SolveStrategy<'a> for BruteForce<'a> {
         BruteForce { dict }
     }
 
-    fn solve(&self, game: &GameProcessed) -> GameResult<'a> {
-        let word_to_points = self
-            .dict
-            .words
-            .iter()
-            .filter_map(|word| {
-                Guess::new(word)
-                    .eval_points(game, self.dict)
-                    .ok()
-                    .map(|points| (word, points))
-            })
-            .collect();
-
-        GameResult { word_to_points }
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        evaluate(self.dict.iter(), game, self.dict)
     }
 }
 
-pub struct ParallelBruteForce<'a> {
-    dict: &'a Dictionary,
+/// Words grouped by length, longest first. Strategies can skip the lengths
+/// the rules reject without looking at the words, and spread the long words,
+/// which are mostly rejected for a disallowed letter, over threads separately
+/// from the many short ones.
+pub struct LengthBuckets<'a> {
+    // (length, words) sorted by descending length.
+    buckets: Vec<(usize, Vec<&'a Word>)>,
 }
 
-impl<'a> SolveStrategy<'a> for ParallelBruteForce<'a> {
-    fn new(dict: &'a Dictionary) -> Self {
-        ParallelBruteForce { dict }
-    }
-
-    fn solve(&self, game: &GameProcessed) -> GameResult<'a> {
-        let word_to_points = self
-            .dict
-            .words
-            .par_iter()
-            .filter_map(|word| {
-                Guess::new(word)
-                    .eval_points(game, self.dict)
-                    .ok()
-                    .map(|points| (word, points))
-            })
-            .collect();
+impl<'a> LengthBuckets<'a> {
+    pub fn new(words: impl IntoIterator<Item = &'a Word>) -> Self {
+        let mut by_length: BTreeMap<usize, Vec<&'a Word>> = BTreeMap::new();
+        for word in words {
+            by_length
+                .entry(word.chars().count())
+                .or_default()
+                .push(word);
+        }
+        LengthBuckets {
+            buckets: by_length.into_iter().rev().collect(),
+        }
+    }
 
-        GameResult { word_to_points }
+    /// The words with at least `min_length` letters, by length, longest first.
+    pub fn at_least(&self, min_length: usize) -> impl Iterator<Item = (usize, &[&'a Word])> {
+        self.buckets
+            .iter()
+            .take_while(move |(length, _)| *length >= min_length)
+            .map(|(length, words)| (*length, words.as_slice()))
+    }
+
+    /// The number of words with fewer than `min_length` letters.
+    pub fn count_shorter(&self, min_length: usize) -> usize {
+        self.buckets
+            .iter()
+            .filter(|(length, _)| *length < min_length)
+            .map(|(_, words)| words.len())
+            .sum()
     }
 }
 
 // Pre-compute a map from letter to all words with that letter.
 pub struct LetterMap<'a> {
     letter_to_words: HashMap<Letter, HashSet<&'a Word>>,
-    dict: &'a Dictionary,
+    words: IndexedWords<'a>,
 }
 
 impl<'a> SolveStrategy<'a> for LetterMap<'a> {
     fn new(dict: &'a Dictionary) -> Self {
         let mut letter_to_words = HashMap::new();
-        for word in &dict.words {
-            for letter in word.chars() {
+        for word in dict.iter() {
+            for letter in letters(word) {
                 letter_to_words
                     .entry(letter)
-                    .or_insert_with(|| HashSet::new())
+                    .or_insert_with(HashSet::new)
                     .insert(word);
             }
         }
         Self {
             letter_to_words,
-            dict,
+            words: IndexedWords::new(dict),
         }
     }
 
-    fn solve(&self, game: &GameProcessed) -> GameResult<'a> {
-        let word_to_points = self
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        if !game.rules.require_center_letter {
+            return evaluate(self.words.iter(), game, &self.words);
+        }
+        let candidates = self
             .letter_to_words
             .get(&game.center_letter)
             .into_iter()
             .flatten()
-            .filter_map(|&word| {
-                Guess::new(word)
-                    .eval_points(game, self.dict)
-                    .ok()
-                    .map(|points| (word, points))
+            .copied();
+        evaluate(candidates, game, &self.words)
+    }
+
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.words.insert(word) {
+            for letter in letters(word) {
+                self.letter_to_words.entry(letter).or_default().insert(word);
+            }
+        }
+        true
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.words.remove(word) {
+            for letter in letters(word) {
+                if let Some(words) = self.letter_to_words.get_mut(&letter) {
+                    words.remove(word);
+                }
+            }
+        }
+        true
+    }
+}
+
+// Pre-compute a map from each word's rarest letter (by how many dictionary words contain it) to
+// the word. An answer's rarest letter is a puzzle letter that is at most as rare as the center
+// letter it contains, so only those few buckets need to be searched. Since each word is stored
+// exactly once, this skips most of the words `LetterMap` examines for common center letters.
+// Incremental updates keep the letter counts of the original dictionary, since any fixed order of
+// the letters works as long as every word is stored under its rarest letter by that order.
+pub struct RarestLetterMap<'a> {
+    rarest_letter_to_words: HashMap<Letter, Vec<&'a Word>>,
+    letter_to_word_count: HashMap<Letter, usize>,
+    words: IndexedWords<'a>,
+}
+
+impl RarestLetterMap<'_> {
+    // ties are broken by the letter itself, so that "rarest" is a strict total order.
+    fn rarity(&self, letter: Letter) -> (usize, Letter) {
+        let count = self.letter_to_word_count.get(&letter).copied().unwrap_or(0);
+        (count, letter)
+    }
+
+    fn rarest_letter(&self, word: &str) -> Option<Letter> {
+        letters(word).min_by_key(|&letter| self.rarity(letter))
+    }
+}
+
+impl<'a> SolveStrategy<'a> for RarestLetterMap<'a> {
+    fn new(dict: &'a Dictionary) -> Self {
+        let mut letter_to_word_count: HashMap<Letter, usize> = HashMap::new();
+        for word in dict.iter() {
+            for letter in distinct_letters(word) {
+                *letter_to_word_count.entry(letter).or_default() += 1;
+            }
+        }
+
+        let mut strategy = Self {
+            rarest_letter_to_words: HashMap::new(),
+            letter_to_word_count,
+            words: IndexedWords::new(dict),
+        };
+        for word in dict.iter() {
+            if let Some(rarest) = strategy.rarest_letter(word) {
+                strategy
+                    .rarest_letter_to_words
+                    .entry(rarest)
+                    .or_default()
+                    .push(word);
+            }
+        }
+        strategy
+    }
+
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        let center_rarity = self.rarity(game.center_letter);
+        let candidates = std::iter::once(&game.center_letter)
+            .chain(&game.non_center_letters)
+            .filter(|&&letter| {
+                !game.rules.require_center_letter || self.rarity(letter) <= center_rarity
             })
-            .collect();
+            .filter_map(|letter| self.rarest_letter_to_words.get(letter))
+            .flatten()
+            .copied();
+        evaluate(candidates, game, &self.words)
+    }
 
-        GameResult { word_to_points }
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.words.insert(word)
+            && let Some(rarest) = self.rarest_letter(word)
+        {
+            self.rarest_letter_to_words
+                .entry(rarest)
+                .or_default()
+                .push(word);
+        }
+        true
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.words.remove(word)
+            && let Some(rarest) = self.rarest_letter(word)
+            && let Some(words) = self.rarest_letter_to_words.get_mut(&rarest)
+        {
+            words.retain(|&other| other != word);
+        }
+        true
     }
 }
 
-pub struct ParallelLetterMap<'a> {
-    letter_to_words: HashMap<Letter, Vec<&'a Word>>,
-    dict: &'a Dictionary,
+// Pre-compute a map from each word's set of letters, as a bitmask, to the words with exactly that
+// set. Pangrams are exactly the words whose set equals the puzzle's, so they are found with a
+// single lookup. Only pangrams are returned.
+pub struct PangramIndex<'a> {
+    letter_set_to_words: HashMap<LetterSet, Vec<&'a Word>>,
+    words: IndexedWords<'a>,
+}
+
+impl<'a> PangramIndex<'a> {
+    /// The words consisting of exactly `letters`, i.e. the pangrams of a puzzle
+    /// with these letters, in arbitrary order.
+    pub fn words_with_letters(&self, letters: LetterSet) -> &[&'a Word] {
+        self.letter_set_to_words
+            .get(&letters)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+// words with other characters can't be answers, so they aren't indexed.
+fn word_letter_set(word: &str) -> Option<LetterSet> {
+    LetterSet::of_word(word)
 }
 
-impl<'a> SolveStrategy<'a> for ParallelLetterMap<'a> {
+impl<'a> SolveStrategy<'a> for PangramIndex<'a> {
     fn new(dict: &'a Dictionary) -> Self {
-        let mut letter_to_words = HashMap::new();
-        for word in &dict.words {
-            for letter in word.chars() {
-                letter_to_words
-                    .entry(letter)
-                    .or_insert_with(|| Vec::new())
+        let mut letter_set_to_words = HashMap::new();
+        for word in dict.iter() {
+            if let Some(set) = word_letter_set(word) {
+                letter_set_to_words
+                    .entry(set)
+                    .or_insert_with(Vec::new)
                     .push(word);
             }
         }
         Self {
-            letter_to_words,
-            dict,
+            letter_set_to_words,
+            words: IndexedWords::new(dict),
         }
     }
 
-    fn solve(&self, game: &GameProcessed) -> GameResult<'a> {
-        let word_to_points = match self.letter_to_words.get(&game.center_letter) {
-            Some(words) => words
-                .par_iter()
-                .filter_map(|&word| {
-                    Guess::new(word)
-                        .eval_points(game, self.dict)
-                        .ok()
-                        .map(|points| (word, points))
-                })
-                .collect(),
-            None => HashMap::new(),
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        let letters =
+            std::iter::once(game.center_letter).chain(game.non_center_letters.iter().copied());
+        let candidates = self
+            .letter_set_to_words
+            .get(&letters.collect())
+            .into_iter()
+            .flatten()
+            .copied();
+        evaluate(candidates, game, &self.words)
+    }
+
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.words.insert(word)
+            && let Some(set) = word_letter_set(word)
+        {
+            self.letter_set_to_words.entry(set).or_default().push(word);
+        }
+        true
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.words.remove(word)
+            && let Some(set) = word_letter_set(word)
+            && let Some(words) = self.letter_set_to_words.get_mut(&set)
+        {
+            words.retain(|&other| other != word);
+        }
+        true
+    }
+}
+
+// Pre-compute a map from each word's set of letters, as a bitmask, to the words with exactly that
+// set, grouped by the number of letters in the set. Answers only contain puzzle letters, so their
+// sets are subsets of the puzzle's set: for puzzles with few letters, the subsets are enumerated and
+// looked up, so that no word with a letter outside the puzzle is ever examined. Otherwise the sets
+// with at most as many letters as the puzzle are scanned, rejecting each with a single AND.
+pub struct ComplementIndex<'a> {
+    // indexed by the number of letters in the set.
+    letter_set_to_words: Vec<HashMap<LetterSet, Vec<&'a Word>>>,
+    words: IndexedWords<'a>,
+}
+
+impl<'a> ComplementIndex<'a> {
+    /// Puzzles with more letters than this are scanned rather than enumerating
+    /// the subsets of their letters.
+    const MAX_ENUMERATED_LETTERS: usize = 12;
+
+    fn index(&mut self, word: &'a Word) {
+        let Some(set) = word_letter_set(word) else {
+            return;
         };
+        let size = set.len();
+        if self.letter_set_to_words.len() <= size {
+            self.letter_set_to_words.resize_with(size + 1, HashMap::new);
+        }
+        self.letter_set_to_words[size]
+            .entry(set)
+            .or_default()
+            .push(word);
+    }
+}
+
+impl<'a> SolveStrategy<'a> for ComplementIndex<'a> {
+    fn new(dict: &'a Dictionary) -> Self {
+        let mut strategy = ComplementIndex {
+            letter_set_to_words: Vec::new(),
+            words: IndexedWords::new(dict),
+        };
+        for word in dict.iter() {
+            strategy.index(word);
+        }
+        strategy
+    }
+
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        let center = LetterSet::from_iter([game.center_letter]);
+        let allowed = game.letter_set;
+        // the sets that must be contained in an answer's set.
+        let required = if game.rules.require_all_letters {
+            allowed
+        } else if game.rules.require_center_letter {
+            center
+        } else {
+            LetterSet::EMPTY
+        };
+
+        let sets: Vec<LetterSet> = if allowed.len() <= Self::MAX_ENUMERATED_LETTERS {
+            // every subset of the optional letters, together with the required ones.
+            allowed
+                .difference(required)
+                .subsets()
+                .map(|subset| subset.union(required))
+                .collect()
+        } else {
+            self.letter_set_to_words
+                .iter()
+                .take(allowed.len() + 1)
+                .flat_map(HashMap::keys)
+                .copied()
+                .filter(|set| set.is_subset(allowed) && required.is_subset(*set))
+                .collect()
+        };
+
+        let candidates = sets
+            .into_iter()
+            .filter_map(|set| self.letter_set_to_words.get(set.len())?.get(&set))
+            .flatten()
+            .copied();
+        evaluate(candidates, game, &self.words)
+    }
+
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.words.insert(word) {
+            self.index(word);
+        }
+        true
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.words.remove(word)
+            && let Some(set) = word_letter_set(word)
+            && let Some(words) = self
+                .letter_set_to_words
+                .get_mut(set.len())
+                .and_then(|sets| sets.get_mut(&set))
+        {
+            words.retain(|&other| other != word);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict(words: &[&str]) -> Dictionary {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn puzzle(max_letter_uses: Option<usize>) -> PuzzleSpec {
+        let rules = Rules {
+            max_letter_uses,
+            ..Rules::SPELLING_BEE
+        };
+        PuzzleSpec::with_rules(Game::parse("C", "ALTEFI").unwrap(), rules)
+    }
+
+    #[test]
+    fn reusing_letters_is_allowed_by_default() {
+        // TACIT uses T twice.
+        let outcome = puzzle(None).check("tacit", &dict(&["TACIT"])).unwrap();
+        assert!(outcome.is_ok());
+    }
+
+    #[test]
+    fn reusing_letters_is_rejected_without_reuse() {
+        let outcome = puzzle(Some(1)).check("tacit", &dict(&["TACIT"])).unwrap();
+        assert_eq!(
+            outcome,
+            Err(GuessingError::ReusedLetter {
+                letter: 'T',
+                max_uses: 1
+            })
+        );
+    }
+
+    #[test]
+    fn letters_used_as_often_as_allowed_are_accepted() {
+        let dict = dict(&["TACIT", "FACET"]);
+        assert!(puzzle(Some(2)).check("tacit", &dict).unwrap().is_ok());
+        assert!(puzzle(Some(1)).check("facet", &dict).unwrap().is_ok());
+    }
+
+    // the center letter followed by the others in alphabetical order.
+    fn letters(text: &str) -> String {
+        let game: Game = text.parse().unwrap();
+        let mut letters: Vec<char> = game.letters().map(char::from).collect();
+        letters[1..].sort_unstable();
+        letters.into_iter().collect()
+    }
+
+    fn parse_error(text: &str) -> String {
+        text.parse::<Game>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn games_are_parsed_from_every_format() {
+        for text in [
+            "L A T E F I / C",
+            "c + altefi",
+            "C/ALTEFI",
+            "Center: C, Letters: A L T E F I",
+            "centre letter: c\nouter letters: altefi",
+            "Letters: ALTEFI Middle: C",
+            "[C] A L T E F I",
+            "(C) ALTEFI",
+            "**C** A L T E F I",
+            "CALTEFI",
+            "C ALTEFI",
+            "🇨 🇦 🇱 🇹 🇪 🇫 🇮",
+            "Spelling Bee 🐝 2024-01-02\n🇨 + 🇦 🇱 🇹 🇪 🇫 🇮",
+        ] {
+            assert_eq!(letters(text), "CAEFILT", "{text}");
+        }
+    }
+
+    #[test]
+    fn missing_center_letter_is_reported() {
+        assert_eq!(
+            parse_error("ALTEFI /"),
+            "the center letter is missing, expected it alone on one side of '/' or '+'"
+        );
+    }
+
+    #[test]
+    fn duplicate_letters_are_reported() {
+        assert_eq!(parse_error("C/ALTEFA"), "letter 'A' occurs more than once");
+        assert_eq!(
+            parse_error("C + ALTEFC"),
+            "center letter may not be part of non center letters"
+        );
+    }
+
+    #[test]
+    fn wrong_letter_count_is_reported() {
+        assert_eq!(
+            parse_error("C/ALTEF"),
+            "expected 6 letters besides the center letter, got 5"
+        );
+        assert_eq!(
+            parse_error("[C] ALTEFIS"),
+            "expected 6 letters besides the center letter, got 7"
+        );
+    }
+
+    #[test]
+    fn non_letters_are_reported() {
+        assert_eq!(
+            parse_error("Center: C, Letters: ALTEF1"),
+            "'1' is not a letter of any supported alphabet"
+        );
+    }
+
+    // the results of a strategy updated by a sequence of inserts and removes,
+    // and of the same strategy built from the resulting words.
+    fn assert_updates_match_rebuild<'a, S: SolveStrategy<'a>>(
+        dict: &'a Dictionary,
+        new_words: &'a [Word],
+        rebuilt: &'a Dictionary,
+    ) {
+        let [acetic, tacit] = new_words else {
+            panic!("expected two new words");
+        };
+        let facet = dict.iter().find(|word| *word == "FACET").unwrap();
+        let mut updated = S::new(dict);
+        // a dictionary word removed and inserted again.
+        assert!(updated.remove(facet));
+        assert!(updated.insert(facet));
+        assert!(updated.remove(&"CAFE".to_string()));
+        // a new word, inserted twice.
+        assert!(updated.insert(acetic));
+        assert!(updated.insert(acetic));
+        // a new word, removed again.
+        assert!(updated.insert(tacit));
+        assert!(updated.remove(tacit));
+        // words that aren't indexed, or already are.
+        assert!(updated.remove(&"LACE".to_string()));
+        assert!(updated.insert(dict.iter().find(|word| *word == "TALC").unwrap()));
+
+        let fresh = S::new(rebuilt);
+        let anywhere = Rules {
+            require_center_letter: false,
+            ..Rules::SPELLING_BEE
+        };
+        for puzzle in [
+            puzzle(None),
+            PuzzleSpec::with_rules(Game::parse("C", "ALTEFI").unwrap(), anywhere),
+            PuzzleSpec::from(Game::parse("A", "CLTEFI").unwrap()),
+        ] {
+            let game = GameProcessed::try_from(&puzzle).unwrap();
+            assert_eq!(updated.solve(&game).0, fresh.solve(&game).0);
+        }
+    }
 
-        GameResult { word_to_points }
+    #[test]
+    fn updated_indexes_match_rebuilt_ones() {
+        let rebuilt = dict(&["FACET", "LATTICE", "TALC", "FELICITATE", "ACETIC"]);
+        let dict = dict(&["FACET", "CAFE", "LATTICE", "TALC", "FELICITATE"]);
+        let new_words = ["ACETIC".to_string(), "TACIT".to_string()];
+        assert_updates_match_rebuild::<LetterMap>(&dict, &new_words, &rebuilt);
+        assert_updates_match_rebuild::<RarestLetterMap>(&dict, &new_words, &rebuilt);
+        assert_updates_match_rebuild::<PangramIndex>(&dict, &new_words, &rebuilt);
+        assert_updates_match_rebuild::<ComplementIndex>(&dict, &new_words, &rebuilt);
+        #[cfg(feature = "parallel")]
+        assert_updates_match_rebuild::<ParallelLetterMap>(&dict, &new_words, &rebuilt);
     }
 }
@@ -0,0 +1,133 @@
+//! Strategies evaluating the candidates on multiple threads.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rayon::prelude::*;
+
+use super::{
+    GameProcessed, GameResult, Guess, IndexedWords, LengthBuckets, Letter, SolveStats,
+    SolveStrategy, Word, distinct_letters,
+};
+use crate::dictionary::{Dictionary, WordList};
+
+/// Like `evaluate`, but evaluates the candidates in parallel.
+fn par_evaluate<'a, D: WordList + Sync + ?Sized>(
+    candidates: impl ParallelIterator<Item = &'a Word>,
+    game: &GameProcessed,
+    dict: &D,
+) -> (GameResult<'a>, SolveStats) {
+    let (word_to_score, stats) = candidates
+        .fold(
+            || (BTreeMap::new(), SolveStats::default()),
+            |(mut word_to_score, mut stats), word| {
+                if game.is_past_deadline() {
+                    stats.skipped += 1;
+                    return (word_to_score, stats);
+                }
+                let outcome = Guess::new(word).eval_points(game, dict);
+                stats.record(&outcome);
+                if let Ok(score) = outcome {
+                    word_to_score.insert(word, score);
+                }
+                (word_to_score, stats)
+            },
+        )
+        .reduce(
+            || (BTreeMap::new(), SolveStats::default()),
+            |(mut a, a_stats), (b, b_stats)| {
+                a.extend(b);
+                (a, a_stats.merge(b_stats))
+            },
+        );
+    (GameResult::new(word_to_score, game), stats)
+}
+
+pub struct ParallelBruteForce<'a> {
+    buckets: LengthBuckets<'a>,
+    dict: &'a Dictionary,
+}
+
+impl<'a> SolveStrategy<'a> for ParallelBruteForce<'a> {
+    fn new(dict: &'a Dictionary) -> Self {
+        ParallelBruteForce {
+            buckets: LengthBuckets::new(dict.iter()),
+            dict,
+        }
+    }
+
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        let min_length = game.rules.min_length;
+        // rayon steals work within and across the buckets, starting with the
+        // long words.
+        let buckets: Vec<&[&Word]> = self
+            .buckets
+            .at_least(min_length)
+            .map(|(_, words)| words)
+            .collect();
+        let candidates = buckets
+            .into_par_iter()
+            .flat_map(|words| words.par_iter().copied());
+        let (result, mut stats) = par_evaluate(candidates, game, self.dict);
+
+        // the shorter words are rejected without evaluating them.
+        let too_short = self.buckets.count_shorter(min_length);
+        stats.examined += too_short;
+        stats.too_short += too_short;
+        (result, stats)
+    }
+}
+
+pub struct ParallelLetterMap<'a> {
+    letter_to_words: HashMap<Letter, Vec<&'a Word>>,
+    words: IndexedWords<'a>,
+}
+
+impl<'a> SolveStrategy<'a> for ParallelLetterMap<'a> {
+    fn new(dict: &'a Dictionary) -> Self {
+        let mut letter_to_words = HashMap::new();
+        for word in dict.iter() {
+            // unlike a set, the vec would contain words with repeated letters multiple times.
+            for letter in distinct_letters(word) {
+                letter_to_words
+                    .entry(letter)
+                    .or_insert_with(Vec::new)
+                    .push(word);
+            }
+        }
+        Self {
+            letter_to_words,
+            words: IndexedWords::new(dict),
+        }
+    }
+
+    fn solve(&self, game: &GameProcessed) -> (GameResult<'a>, SolveStats) {
+        if !game.rules.require_center_letter {
+            return par_evaluate(self.words.par_iter(), game, &self.words);
+        }
+        let candidates = match self.letter_to_words.get(&game.center_letter) {
+            Some(words) => words.as_slice(),
+            None => &[],
+        };
+        par_evaluate(candidates.par_iter().copied(), game, &self.words)
+    }
+
+    fn insert(&mut self, word: &'a Word) -> bool {
+        if self.words.insert(word) {
+            for letter in distinct_letters(word) {
+                self.letter_to_words.entry(letter).or_default().push(word);
+            }
+        }
+        true
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        if self.words.remove(word) {
+            for letter in distinct_letters(word) {
+                if let Some(words) = self.letter_to_words.get_mut(&letter) {
+                    words.retain(|&other| other != word);
+                }
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,221 @@
+//! The fewest answers needed to reach the Genius rank, so a player can aim
+//! for "find these 12 words and you're done".
+
+use std::fmt;
+
+use crate::{
+    frequency::WordFrequencies,
+    game::{GameResult, Points, Score, Word},
+};
+
+/// NYT awards Genius at 70% of the total points, rounded.
+pub const GENIUS_FRACTION: f64 = 0.7;
+
+// beyond this many knapsack states, such as for huge word lists, the highest
+// scoring answers are taken instead of searching for the most common ones.
+const MAX_SEARCH_CELLS: usize = 1 << 22;
+
+pub fn genius_threshold(total_points: Points) -> Points {
+    (total_points as f64 * GENIUS_FRACTION).round() as Points
+}
+
+/// NYT's ranks of a player's progress on a puzzle, by the fraction of the
+/// total points found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Beginner,
+    GoodStart,
+    MovingUp,
+    Good,
+    Solid,
+    Nice,
+    Great,
+    Amazing,
+    Genius,
+    /// Every answer found.
+    QueenBee,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 10] = [
+        Rank::Beginner,
+        Rank::GoodStart,
+        Rank::MovingUp,
+        Rank::Good,
+        Rank::Solid,
+        Rank::Nice,
+        Rank::Great,
+        Rank::Amazing,
+        Rank::Genius,
+        Rank::QueenBee,
+    ];
+
+    /// The rank reached with `points` of `total_points`.
+    pub fn of(points: Points, total_points: Points) -> Rank {
+        Rank::ALL
+            .into_iter()
+            .rev()
+            .find(|rank| points >= rank.threshold(total_points))
+            .unwrap_or(Rank::Beginner)
+    }
+
+    /// The points needed for the rank, rounded like [`genius_threshold`].
+    pub fn threshold(self, total_points: Points) -> Points {
+        let fraction = match self {
+            Rank::Beginner => 0.0,
+            Rank::GoodStart => 0.02,
+            Rank::MovingUp => 0.05,
+            Rank::Good => 0.08,
+            Rank::Solid => 0.15,
+            Rank::Nice => 0.25,
+            Rank::Great => 0.4,
+            Rank::Amazing => 0.5,
+            Rank::Genius => GENIUS_FRACTION,
+            Rank::QueenBee => 1.0,
+        };
+        (total_points as f64 * fraction).round() as Points
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rank::Beginner => "Beginner",
+            Rank::GoodStart => "Good Start",
+            Rank::MovingUp => "Moving Up",
+            Rank::Good => "Good",
+            Rank::Solid => "Solid",
+            Rank::Nice => "Nice",
+            Rank::Great => "Great",
+            Rank::Amazing => "Amazing",
+            Rank::Genius => "Genius",
+            Rank::QueenBee => "Queen Bee",
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A smallest set of answers worth at least the Genius threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeniusPlan<'a> {
+    /// Sorted alphabetically.
+    pub words: Vec<(&'a Word, Score)>,
+    pub points: Points,
+    pub threshold: Points,
+}
+
+impl<'a> GameResult<'a> {
+    /// Find the fewest answers reaching the Genius threshold. Among the sets
+    /// of that size, the one with the most common words according to
+    /// `frequencies` is chosen, unless there are too many answers to search
+    /// through, in which case the most common of the highest scoring ones are.
+    pub fn genius_plan(&self, frequencies: Option<&WordFrequencies>) -> GeniusPlan<'a> {
+        let mut entries = self.entries();
+        entries.sort_by_key(|(word, _)| *word);
+        let threshold = genius_threshold(self.total_points());
+
+        // the most points with k words come from the k highest scoring
+        // answers, so that's the smallest size reaching the threshold.
+        let obscurity =
+            |word: &str| frequencies.map_or(0.0, |frequencies| frequencies.obscurity(word));
+        let mut by_points = entries.clone();
+        by_points.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .points
+                .cmp(&a_score.points)
+                .then(obscurity(a).total_cmp(&obscurity(b)))
+        });
+        let size = if threshold == 0 {
+            0
+        } else {
+            by_points
+                .iter()
+                .scan(0, |sum, (_, score)| {
+                    *sum += score.points;
+                    Some(*sum)
+                })
+                .position(|sum| sum >= threshold)
+                .map_or(0, |i| i + 1)
+        };
+
+        let cells = (size + 1) * (threshold + 1);
+        let mut words = if entries.len() * cells <= MAX_SEARCH_CELLS {
+            most_common(&entries, size, threshold, obscurity)
+        } else {
+            by_points.truncate(size);
+            by_points
+        };
+        words.sort_by_key(|(word, _)| *word);
+
+        GeniusPlan {
+            points: words.iter().map(|(_, score)| score.points).sum(),
+            words,
+            threshold,
+        }
+    }
+}
+
+/// The `size` answers worth at least `threshold` with the lowest total
+/// obscurity, found with a knapsack over (words taken, points capped at the
+/// threshold).
+fn most_common<'a>(
+    entries: &[(&'a Word, Score)],
+    size: usize,
+    threshold: Points,
+    obscurity: impl Fn(&str) -> f64,
+) -> Vec<(&'a Word, Score)> {
+    let cell = |count: usize, points: usize| count * (threshold + 1) + points;
+    let mut cost = vec![f64::INFINITY; (size + 1) * (threshold + 1)];
+    cost[cell(0, 0)] = 0.0;
+    // for each entry and state, the points before taking the entry if
+    // taking it reached the state more cheaply.
+    let mut taken_from = vec![vec![None; cost.len()]; entries.len()];
+    for (i, (word, score)) in entries.iter().enumerate() {
+        let word_cost = obscurity(word);
+        // backwards, so that each entry is taken at most once.
+        for count in (1..=size).rev() {
+            for points in (0..=threshold).rev() {
+                let next = cell(count, (points + score.points).min(threshold));
+                let next_cost = cost[cell(count - 1, points)] + word_cost;
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    taken_from[i][next] = Some(points);
+                }
+            }
+        }
+    }
+
+    // walk back from the target through the last improvement of each state.
+    let mut words = Vec::new();
+    let (mut count, mut points) = (size, threshold);
+    for (i, entry) in entries.iter().enumerate().rev() {
+        if count == 0 {
+            break;
+        }
+        if let Some(previous) = taken_from[i][cell(count, points)] {
+            words.push(*entry);
+            count -= 1;
+            points = previous;
+        }
+    }
+    words
+}
+
+impl fmt::Display for GeniusPlan<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "find these {} answers for {} points, Genius is at {}:",
+            self.words.len(),
+            self.points,
+            self.threshold
+        )?;
+        for (word, score) in &self.words {
+            writeln!(f, "{word} ({})", score.points)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! Answers counted by starting letter and length, as in the NYT hints grid.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::Serialize;
+
+use crate::game::{GameResult, Letter};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LetterGrid {
+    // first letter -> word length -> number of words, only for letters starting a word.
+    rows: BTreeMap<Letter, BTreeMap<usize, usize>>,
+}
+
+impl LetterGrid {
+    /// The letters starting at least one answer, in alphabetical order.
+    pub fn letters(&self) -> impl Iterator<Item = Letter> + '_ {
+        self.rows.keys().copied()
+    }
+
+    /// The lengths of at least one answer, in ascending order.
+    pub fn lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<_> = self
+            .rows
+            .values()
+            .flat_map(|row| row.keys())
+            .copied()
+            .collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        lengths
+    }
+
+    /// The number of answers starting with `letter` that have `length` letters.
+    pub fn count(&self, letter: Letter, length: usize) -> usize {
+        self.rows
+            .get(&letter)
+            .and_then(|row| row.get(&length))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The number of answers starting with `letter`.
+    pub fn row_total(&self, letter: Letter) -> usize {
+        self.rows.get(&letter).map_or(0, |row| row.values().sum())
+    }
+
+    /// The number of answers with `length` letters.
+    pub fn column_total(&self, length: usize) -> usize {
+        self.rows.values().filter_map(|row| row.get(&length)).sum()
+    }
+
+    pub fn total(&self) -> usize {
+        self.rows.values().flat_map(|row| row.values()).sum()
+    }
+}
+
+impl GameResult<'_> {
+    /// Count the answers by starting letter and length.
+    pub fn grid(&self) -> LetterGrid {
+        let mut rows: BTreeMap<Letter, BTreeMap<usize, usize>> = BTreeMap::new();
+        for (word, _) in self.entries() {
+            let first = word
+                .chars()
+                .next()
+                .and_then(|first| Letter::new(first).ok());
+            if let Some(first) = first {
+                *rows
+                    .entry(first)
+                    .or_default()
+                    .entry(word.chars().count())
+                    .or_default() += 1;
+            }
+        }
+        LetterGrid { rows }
+    }
+}
+
+/// A table with a row per letter and a column per length, with sums.
+impl fmt::Display for LetterGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lengths = self.lengths();
+
+        write!(f, "  ")?;
+        for length in &lengths {
+            write!(f, "{length:>3}")?;
+        }
+        writeln!(f, "  Σ")?;
+        for letter in self.letters() {
+            write!(f, "{letter}:")?;
+            for &length in &lengths {
+                match self.count(letter, length) {
+                    0 => write!(f, "  -")?,
+                    count => write!(f, "{count:>3}")?,
+                }
+            }
+            writeln!(f, "{:>3}", self.row_total(letter))?;
+        }
+        write!(f, "Σ:")?;
+        for &length in &lengths {
+            write!(f, "{:>3}", self.column_total(length))?;
+        }
+        writeln!(f, "{:>3}", self.total())
+    }
+}
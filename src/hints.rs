@@ -0,0 +1,224 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    game::{GameResult, Letter, LetterUsage, Word},
+    grid::LetterGrid,
+    session::HintLevel,
+};
+
+// the width of the bar of a letter used by every word.
+const HEAT_MAP_WIDTH: usize = 20;
+
+/// Spoiler-free summary of a solution, modelled after the NYT hints page.
+pub struct Hints {
+    word_count: usize,
+    total_points: usize,
+    pangram_count: usize,
+    bingo: bool,
+    grid: LetterGrid,
+    // first two letters -> number of words.
+    two_letter_list: BTreeMap<String, usize>,
+    letter_usage: BTreeMap<Letter, LetterUsage>,
+}
+
+impl Hints {
+    pub fn new(result: &GameResult) -> Hints {
+        let entries = result.entries();
+
+        let mut two_letter_list = BTreeMap::new();
+        for (word, _) in &entries {
+            *two_letter_list
+                .entry(word.chars().take(2).collect())
+                .or_default() += 1;
+        }
+
+        Hints {
+            word_count: entries.len(),
+            total_points: result.total_points(),
+            pangram_count: entries.iter().filter(|(_, score)| score.is_pangram).count(),
+            bingo: result.has_bingo(),
+            grid: result.grid(),
+            two_letter_list,
+            letter_usage: result.letter_usage(),
+        }
+    }
+}
+
+impl Hints {
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    pub fn total_points(&self) -> usize {
+        self.total_points
+    }
+
+    pub fn pangram_count(&self) -> usize {
+        self.pangram_count
+    }
+
+    /// The number of words, points and pangrams, and whether there is a bingo.
+    pub fn write_summary(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "WORDS: {}, POINTS: {}, PANGRAMS: {}",
+            self.word_count, self.total_points, self.pangram_count
+        )?;
+        if self.bingo {
+            write!(f, ", BINGO")?;
+        }
+        writeln!(f)
+    }
+
+    /// The number of words by first letter (rows) and length (columns), with sums.
+    pub fn write_grid(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+
+    /// The number of words by their first two letters, one line per first letter.
+    pub fn write_two_letter_list(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut by_first_letter: BTreeMap<char, Vec<String>> = BTreeMap::new();
+        for (prefix, count) in &self.two_letter_list {
+            if let Some(first) = prefix.chars().next() {
+                by_first_letter
+                    .entry(first)
+                    .or_default()
+                    .push(format!("{prefix}-{count}"));
+            }
+        }
+        for pairs in by_first_letter.values() {
+            writeln!(f, "{}", pairs.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// The number of words using and starting with each letter, with a bar
+    /// showing the share of words using it.
+    pub fn write_heat_map(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(f, "   USED START")?;
+        for (letter, usage) in &self.letter_usage {
+            let bar = (usage.words * HEAT_MAP_WIDTH)
+                .checked_div(self.word_count)
+                .unwrap_or(0);
+            writeln!(
+                f,
+                "{letter}: {:>4} {:>5} {}",
+                usage.words,
+                usage.starting,
+                "#".repeat(bar)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Hints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_summary(f)?;
+        writeln!(f)?;
+        self.write_grid(f)?;
+        writeln!(f)?;
+        self.write_two_letter_list(f)?;
+        writeln!(f)?;
+        self.write_heat_map(f)
+    }
+}
+
+/// The answers in the order their hints are escalated in, shortest first, then
+/// alphabetically, so that their positions don't change while playing.
+pub fn escalation_order<'a>(result: &GameResult<'a>) -> Vec<&'a Word> {
+    let mut words: Vec<&'a Word> = result.entries().into_iter().map(|(word, _)| word).collect();
+    words.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)));
+    words
+}
+
+/// The answers not found yet, sorted alphabetically to count the ones
+/// matching a prefix by binary search, without revealing them.
+pub struct RemainingAnswers<'a> {
+    words: Vec<&'a Word>,
+}
+
+impl<'a> RemainingAnswers<'a> {
+    pub fn new(result: &GameResult<'a>, is_found: impl Fn(&str) -> bool) -> Self {
+        let mut words: Vec<&'a Word> = result
+            .entries()
+            .into_iter()
+            .map(|(word, _)| word)
+            .filter(|word| !is_found(word))
+            .collect();
+        words.sort_unstable();
+        RemainingAnswers { words }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The number of remaining answers starting with `pattern`, uppercase,
+    /// where `_` stands for any letter, e.g. `FA` or `F_C`.
+    pub fn count_matching(&self, pattern: &str) -> usize {
+        let prefix = pattern.split('_').next().unwrap_or_default();
+        let start = self.words.partition_point(|word| word.as_str() < prefix);
+        let len = self.words[start..].partition_point(|word| word.starts_with(prefix));
+        if prefix.len() == pattern.len() {
+            return len;
+        }
+        self.words[start..start + len]
+            .iter()
+            .filter(|word| starts_with_pattern(word, pattern))
+            .count()
+    }
+}
+
+/// Whether `word` starts with `pattern`, where `_` stands for any letter.
+pub fn starts_with_pattern(word: &str, pattern: &str) -> bool {
+    word.chars().count() >= pattern.chars().count()
+        && word
+            .chars()
+            .zip(pattern.chars())
+            .all(|(letter, wanted)| wanted == '_' || letter == wanted)
+}
+
+/// The hint of one answer, revealing as much as its level.
+pub struct WordHint<'a> {
+    pub word: &'a Word,
+    /// None if no hint was asked for yet.
+    pub level: Option<HintLevel>,
+    pub found: bool,
+    /// Shown from [`HintLevel::Definition`] on.
+    pub definition: Option<&'a str>,
+}
+
+/// e.g. `?` without a hint, `FA___ (5 letters)` and `FACET` when revealed.
+impl fmt::Display for WordHint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.found {
+            return write!(f, "{} (found)", self.word);
+        }
+        let shown = match self.level {
+            None => return write!(f, "?"),
+            Some(HintLevel::Word) => return write!(f, "{}", self.word),
+            Some(HintLevel::Length) => 0,
+            Some(HintLevel::FirstLetter) => 1,
+            Some(HintLevel::FirstTwoLetters | HintLevel::Definition) => 2,
+        };
+        let len = self.word.chars().count();
+        let prefix: String = self.word.chars().take(shown).collect();
+        write!(
+            f,
+            "{prefix}{} ({len} letters)",
+            "_".repeat(len.saturating_sub(shown))
+        )?;
+        if self.level == Some(HintLevel::Definition) {
+            match self.definition {
+                Some(definition) => write!(f, ": {definition}")?,
+                None => write!(f, ": no definition found")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    io::Read,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use reqwest::{
+    Proxy, StatusCode,
+    blocking::{Client, RequestBuilder, Response},
+};
+use thiserror::Error;
+
+use crate::fetch::{HttpFetcher, HttpRequest, HttpResponse};
+
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Timeout of a single request attempt.
+    pub timeout: Duration,
+    /// How often a failed request is retried.
+    pub retries: u32,
+    /// Delay before the first retry, doubled on every further retry.
+    pub initial_backoff: Duration,
+    /// Proxy for all requests. The `HTTP(S)_PROXY` environment variables are
+    /// respected without it.
+    pub proxy: Option<String>,
+    pub user_agent: String,
+    /// When all requests together must be done, including their retries.
+    /// Attempts are cut short to end by then, and aren't retried after it.
+    pub deadline: Option<Instant>,
+}
+
+/// The [`HttpConfig::deadline`] passed before a request could be completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("ran out of time for network requests")]
+pub struct DeadlineExceeded;
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            timeout: Duration::from_secs(30),
+            retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            deadline: None,
+        }
+    }
+}
+
+/// HTTP client shared by everything that fetches data from the web.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    config: HttpConfig,
+}
+
+impl HttpClient {
+    pub fn new(config: HttpConfig) -> anyhow::Result<Self> {
+        let mut builder = Client::builder()
+            .timeout(config.timeout)
+            .user_agent(&config.user_agent);
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy).context("invalid proxy URL")?);
+        }
+        let client = builder.build().context("failed to build HTTP client")?;
+        Ok(HttpClient { client, config })
+    }
+
+    /// Send the request built by `request`, retrying timeouts, connection
+    /// errors, server errors and rate limiting with exponential backoff until
+    /// the deadline, if any.
+    pub fn send(&self, request: impl Fn(&Client) -> RequestBuilder) -> anyhow::Result<Response> {
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let mut builder = request(&self.client);
+            if let Some(remaining) = self.remaining() {
+                if remaining.is_zero() {
+                    return Err(DeadlineExceeded.into());
+                }
+                builder = builder.timeout(remaining.min(self.config.timeout));
+            }
+            let response = match builder.send() {
+                Err(e) if e.is_timeout() && self.remaining().is_some_and(|r| r.is_zero()) => {
+                    return Err(anyhow::Error::new(e).context(DeadlineExceeded));
+                }
+                response => response,
+            };
+            let retryable = match &response {
+                Ok(response) => {
+                    response.status().is_server_error()
+                        || response.status() == StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(e) => e.is_timeout() || e.is_connect(),
+            };
+            // a retry that couldn't start before the deadline is pointless.
+            let out_of_time = self
+                .remaining()
+                .is_some_and(|remaining| remaining <= backoff);
+            if !retryable || attempt == self.config.retries || out_of_time {
+                return Ok(response?);
+            }
+
+            attempt += 1;
+            match &response {
+                Ok(response) => log::warn!(
+                    "{} responded with {}, retrying in {backoff:?} ({attempt}/{})",
+                    response.url(),
+                    response.status(),
+                    self.config.retries
+                ),
+                Err(e) => log::warn!(
+                    "request failed: {e}, retrying in {backoff:?} ({attempt}/{})",
+                    self.config.retries
+                ),
+            }
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+
+    // the time left until the deadline, if there is one.
+    fn remaining(&self) -> Option<Duration> {
+        self.config
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+}
+
+impl HttpFetcher for HttpClient {
+    fn fetch(&self, request: &HttpRequest) -> anyhow::Result<HttpResponse> {
+        let response = self
+            .send(|client| {
+                let mut builder = client.get(request.url).query(&request.query);
+                for (name, value) in &request.headers {
+                    builder = builder.header(*name, value);
+                }
+                builder
+            })
+            .with_context(|| format!("failed to GET {}", request.url))?;
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            body: response
+                .text()
+                .context("failed to read response body as text")?,
+        })
+    }
+
+    // reqwest's status errors keep the exit code of network failures.
+    fn get_text(&self, url: &str) -> anyhow::Result<String> {
+        self.send(|client| client.get(url))
+            .with_context(|| format!("failed to GET {url}"))?
+            .error_for_status()?
+            .text()
+            .context("failed to read response body as text")
+    }
+
+    fn get_reader(&self, url: &str) -> anyhow::Result<Box<dyn Read + '_>> {
+        let response = self
+            .send(|client| client.get(url))
+            .with_context(|| format!("failed to GET {url}"))?
+            .error_for_status()?;
+        Ok(Box::new(response))
+    }
+}
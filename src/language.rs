@@ -0,0 +1,104 @@
+//! Languages of word lists, for Spelling Bee clones in other languages. Each
+//! language has an alphabet of the letters puzzles may consist of, and spells
+//! its other letters with those (or drops the words containing them).
+
+use std::{fmt, str::FromStr};
+
+use crate::{alphabet::Alphabet, dictionary::ParseOptions, game::Letter};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::German,
+        Language::Spanish,
+        Language::French,
+    ];
+
+    /// The ISO 639-1 code, e.g. `de`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Spanish => "es",
+            Language::French => "fr",
+        }
+    }
+
+    /// The letters of puzzles in this language: A to Z, and `Ñ` for Spanish.
+    pub fn alphabet(self) -> Alphabet {
+        match self {
+            Language::Spanish => {
+                Alphabet::LATIN.with(Letter::new('Ñ').expect("Ñ is a supported letter"))
+            }
+            Language::English | Language::German | Language::French => Alphabet::LATIN,
+        }
+    }
+
+    /// How word lists of this language are normalized by default:
+    /// - German capitalizes every noun, so capitalized words are kept.
+    /// - Spanish and French accents are stripped (`canción` -> `CANCION`).
+    pub fn parse_options(self) -> ParseOptions {
+        let default = ParseOptions {
+            language: self,
+            ..ParseOptions::default()
+        };
+        match self {
+            Language::English => default,
+            Language::German => ParseOptions {
+                drop_proper_nouns: false,
+                ..default
+            },
+            Language::Spanish | Language::French => ParseOptions {
+                strip_diacritics: true,
+                ..default
+            },
+        }
+    }
+
+    /// The spelling of an uppercase letter that isn't in the language's
+    /// alphabet, if the language has one. Letters without a spelling are kept,
+    /// so that words containing them are dropped.
+    pub(crate) fn transliterate(self, letter: char) -> Option<&'static str> {
+        match (self, letter) {
+            // `ß` is already uppercased to `SS`.
+            (Language::German, 'Ä') => Some("AE"),
+            (Language::German, 'Ö') => Some("OE"),
+            (Language::German, 'Ü') => Some("UE"),
+            (Language::French, 'Œ') => Some("OE"),
+            (Language::French, 'Æ') => Some("AE"),
+            _ => None,
+        }
+    }
+
+    /// Letters that must survive stripping diacritics, like Spanish `Ñ`, which
+    /// is a letter of its own rather than an `N` with a diacritic.
+    pub(crate) fn keeps_diacritic(self, letter: char) -> bool {
+        matches!((self, letter), (Language::Spanish, 'Ñ'))
+    }
+}
+
+impl FromStr for Language {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow::anyhow!("unknown language '{s}', expected en, de, es or fr"))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
@@ -0,0 +1,46 @@
+//! The hive as a hex grid: the center cell surrounded by a ring of six cells,
+//! each adjacent to the center and to its two neighbours in the ring.
+
+use crate::game::{GameProcessed, Letter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    center: Letter,
+    /// Clockwise from the top, in alphabetical order as the hive is rendered.
+    ring: [Letter; 6],
+}
+
+impl Layout {
+    /// The layout of a puzzle, or none unless it has six outer letters.
+    pub fn new(game: &GameProcessed) -> Option<Layout> {
+        Some(Layout {
+            center: game.center_letter(),
+            ring: game.non_center_letters().try_into().ok()?,
+        })
+    }
+
+    pub fn center(&self) -> Letter {
+        self.center
+    }
+
+    /// The outer letters, clockwise from the top.
+    pub fn ring(&self) -> [Letter; 6] {
+        self.ring
+    }
+
+    /// Whether the cells of `a` and `b` share an edge. A cell isn't adjacent
+    /// to itself.
+    pub fn are_adjacent(&self, a: Letter, b: Letter) -> bool {
+        if a == b {
+            return false;
+        }
+        if a == self.center || b == self.center {
+            return self.ring.contains(&a) || self.ring.contains(&b);
+        }
+        let position = |letter| self.ring.iter().position(|&cell| cell == letter);
+        match (position(a), position(b)) {
+            (Some(i), Some(j)) => (i + 1) % 6 == j || (j + 1) % 6 == i,
+            _ => false,
+        }
+    }
+}
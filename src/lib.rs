@@ -0,0 +1,61 @@
+//! Solver for the NYT Spelling Bee.
+//!
+//! Without default features, only the sequential strategies are available,
+//! and word lists and puzzles are fetched through any [`fetch::HttpFetcher`].
+//! The `parallel` feature adds the parallel strategies and the `network`
+//! feature adds an HTTP client for fetching. The `ffi` feature adds C bindings
+//! and the `tower` feature solving as a `tower::Service`.
+
+pub mod alphabet;
+pub mod analytics;
+pub mod annotate;
+pub mod archive;
+pub mod badge;
+pub mod center;
+pub mod compact;
+pub mod constraints;
+pub mod date;
+pub mod definitions;
+pub mod dictionary;
+pub mod difficulty;
+pub mod enrich;
+pub mod evaluation;
+pub mod extensions;
+pub mod fetch;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod frequency;
+pub mod fuzz;
+pub mod game;
+pub mod genius;
+pub mod grid;
+pub mod hints;
+#[cfg(feature = "network")]
+pub mod http;
+pub mod language;
+pub mod layout;
+pub mod lint;
+pub mod mapped;
+pub mod morphology;
+pub mod nyt;
+pub mod planner;
+pub mod player_stats;
+pub mod practice;
+pub mod profile;
+pub mod puzzle_file;
+pub mod query;
+pub mod registry;
+pub mod render;
+pub mod scoring;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod session;
+pub mod share;
+pub mod shared;
+pub mod sheet;
+pub mod sources;
+pub mod suggest;
+pub mod summary;
+pub mod tricky;
+pub mod validate;
+pub mod word_index;
@@ -0,0 +1,152 @@
+//! Problems in raw word lists that the parser silently drops or works around,
+//! reported line by line so that the list can be cleaned up at its source.
+
+use std::{collections::HashMap, fmt};
+
+// the byte order mark some editors start UTF-8 files with.
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// The file starts with a UTF-8 byte order mark.
+    Bom,
+    /// The line ends with `\r\n` instead of `\n`.
+    CrLf,
+    /// The line isn't valid UTF-8.
+    InvalidUtf8,
+    Empty,
+    /// Leading or trailing whitespace around the word.
+    Whitespace,
+    /// The word mixes uppercase and lowercase letters.
+    MixedCase,
+    /// The word contains characters other than the letters A to Z.
+    NonAlphabetic,
+    /// The word is shorter than the minimum length of answers, so the parser
+    /// drops it.
+    TooShort {
+        min_length: usize,
+    },
+    /// The word, uppercased, already occurs in an earlier line.
+    Duplicate {
+        first_line: usize,
+    },
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintKind::Bom => write!(f, "byte order mark"),
+            LintKind::CrLf => write!(f, "CRLF line ending"),
+            LintKind::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            LintKind::Empty => write!(f, "empty line"),
+            LintKind::Whitespace => write!(f, "surrounding whitespace"),
+            LintKind::MixedCase => write!(f, "mixed case"),
+            LintKind::NonAlphabetic => write!(f, "non-alphabetic characters"),
+            LintKind::TooShort { min_length } => write!(f, "shorter than {min_length} letters"),
+            LintKind::Duplicate { first_line } => write!(f, "duplicate of line {first_line}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// 1-based.
+    pub line: usize,
+    pub kind: LintKind,
+    /// The line, lossily decoded and without its line ending.
+    pub text: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {:?}", self.line, self.kind, self.text)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LintReport {
+    pub lines: usize,
+    /// In order of their lines.
+    pub lints: Vec<Lint>,
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.lints.is_empty()
+    }
+}
+
+/// One line per problem, then the number of problems.
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for lint in &self.lints {
+            writeln!(f, "{lint}")?;
+        }
+        write!(f, "{} problems in {} lines", self.lints.len(), self.lines)
+    }
+}
+
+/// Check every line of a raw word list, which needn't be valid UTF-8, for
+/// answers of at least `min_length` letters.
+pub fn lint(bytes: &[u8], min_length: usize) -> LintReport {
+    let mut report = LintReport::default();
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+
+    for (i, raw) in bytes.split_inclusive(|&b| b == b'\n').enumerate() {
+        let line = i + 1;
+        report.lines += 1;
+        let mut push = |kind, text: &str| {
+            report.lints.push(Lint {
+                line,
+                kind,
+                text: text.to_string(),
+            })
+        };
+
+        let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+        let (raw, crlf) = match raw.strip_suffix(b"\r") {
+            Some(raw) => (raw, true),
+            None => (raw, false),
+        };
+        let text = String::from_utf8_lossy(raw);
+        let mut text = text.as_ref();
+        if line == 1
+            && let Some(rest) = text.strip_prefix(BOM)
+        {
+            push(LintKind::Bom, rest);
+            text = rest;
+        }
+        if crlf {
+            push(LintKind::CrLf, text);
+        }
+        if std::str::from_utf8(raw).is_err() {
+            push(LintKind::InvalidUtf8, text);
+            continue;
+        }
+
+        let word = text.trim();
+        if word.is_empty() {
+            push(LintKind::Empty, text);
+            continue;
+        }
+        if word.len() != text.len() {
+            push(LintKind::Whitespace, text);
+        }
+        if word.chars().any(char::is_uppercase) && word.chars().any(char::is_lowercase) {
+            push(LintKind::MixedCase, text);
+        }
+        if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            push(LintKind::NonAlphabetic, text);
+        }
+        if word.chars().count() < min_length {
+            push(LintKind::TooShort { min_length }, text);
+        }
+        match first_lines.get(&word.to_uppercase()) {
+            Some(&first_line) => push(LintKind::Duplicate { first_line }, text),
+            None => {
+                first_lines.insert(word.to_uppercase(), line);
+            }
+        }
+    }
+    report
+}
@@ -1,53 +1,2686 @@
-use game::{
-    BruteForce, Dictionary, Game, GameSolver, LetterMap, ParallelBruteForce, ParallelLetterMap,
+use std::{
+    collections::{BTreeSet, HashSet},
+    convert::Infallible,
+    ffi::OsStr,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
-mod game;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use exit::Failure;
+use export::ExportFormat;
+use metrics::SolveMetric;
+use nyt_spelling_bee_solver::{
+    alphabet::LetterSet,
+    analytics::{ArchiveStats, DictionaryCoverage},
+    archive::{AnswerCorpus, Archive, ArchivedPuzzle},
+    badge::{Badge, BadgeFormat},
+    center, compact,
+    constraints::{Constraint, Constraints},
+    date::PuzzleDate,
+    definitions,
+    dictionary::{Dictionary, DictionaryMetadata, ParseOptions, WordList, WordOverlay, WordStream},
+    difficulty::Rating,
+    enrich::Enricher,
+    evaluation::EvaluationReport,
+    frequency::WordFrequencies,
+    fuzz::{self, FuzzOptions},
+    game::{
+        AnswerOrder, AnswerStream, Game, GameProcessed, GameSolver, GuessingError, Letter,
+        PangramIndex, ParallelLetterMap, PuzzleSpec, RarestLetterMap, ResultFilter, Rules, Score,
+        SolveStats, SolveStrategy, Word,
+    },
+    hints::Hints,
+    http::{HttpClient, HttpConfig},
+    language::Language,
+    lint,
+    mapped::MappedDictionary,
+    nyt,
+    player_stats::PlayerStats,
+    practice,
+    profile::NytProfile,
+    puzzle_file::PuzzleFile,
+    query,
+    registry::{DynSolver, RegisteredStrategy, StrategyRegistry},
+    scoring::{LengthSquaredScoring, NytScoring, Scoring, ScrabbleScoring},
+    session::SessionStore,
+    share, sheet,
+    sources::DictionarySource,
+    suggest,
+    tricky::{self, TrickinessScorer},
+    validate::{self, ValidationOptions},
+};
+use output::{OutputFormat, Spoiler};
+use style::{ColorMode, Theme};
+use timings::{Phase, Timings, TimingsFormat};
+use watch::FileWatcher;
+
+mod benchmark;
+mod discord;
+mod exit;
+mod export;
+mod metrics;
+mod output;
+mod repl;
+mod style;
+mod timings;
+mod watch;
+
+#[derive(Parser)]
+#[command(about = "Solver for the NYT Spelling Bee")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Number of threads used by the parallel strategies (0 = one per CPU).
+    #[arg(long, global = true, default_value_t = 0)]
+    threads: usize,
+
+    /// Append how long each solve took to this local JSON Lines file, see
+    /// `metrics report`. Nothing is recorded without it.
+    #[arg(long, global = true, env = "SPELLING_BEE_METRICS", value_name = "PATH")]
+    metrics: Option<PathBuf>,
+
+    /// Report how long each phase (fetch, parse, index, solve, enrich,
+    /// format) took on stderr when done.
+    #[arg(long, global = true, value_name = "FORMAT")]
+    timings: Option<TimingsFormat>,
+
+    /// When to color the output, `auto` respects NO_COLOR and pipes.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    color: ColorMode,
+
+    /// Styles of the colored output, e.g. `pangram=bright-magenta bold,found=italic,header=underline`.
+    #[arg(long, global = true, env = "SPELLING_BEE_THEME", value_name = "THEME")]
+    theme: Option<Theme>,
+
+    #[command(flatten)]
+    http: HttpArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle and print the solution.
+    Solve(SolveArgs),
+    /// Solve today's NYT puzzle, leaving out the words your account already found.
+    Today(TodayArgs),
+    /// Check whether a single word is a valid answer.
+    Check {
+        word: String,
+
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+
+        /// Look the word up in the memory-mapped `--dict`, which must be sorted
+        /// (see `dict sort`), instead of loading the whole word list.
+        #[arg(
+            long,
+            requires = "dict",
+            conflicts_with_all = ["drop_plurals", "max_obscurity", "blocklist"]
+        )]
+        mapped: bool,
+
+        /// Print the score or the violated rule as JSON.
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Check whether a custom puzzle satisfies the constraints of NYT puzzles.
+    Validate {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+
+        /// Letters the puzzle may not contain.
+        #[arg(long, default_value = "S")]
+        forbidden_letters: String,
+
+        /// Minimum number of answers.
+        #[arg(long, default_value_t = ValidationOptions::default().min_answers)]
+        min_answers: usize,
+
+        /// Maximum number of answers.
+        #[arg(long, default_value_t = ValidationOptions::default().max_answers)]
+        max_answers: usize,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Solve a custom puzzle with each of its letters as the center and
+    /// compare the answers, points and pangrams of each option.
+    SuggestCenter {
+        /// The letters of the puzzle, e.g. `altefic`.
+        letters: String,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Load the dictionary once and answer queries interactively.
+    Repl {
+        /// Resume the session saved with `session save <path>`.
+        #[arg(long, value_name = "PATH")]
+        session: Option<PathBuf>,
+
+        /// Store the progress on every puzzle in this directory, for `stats me`.
+        #[arg(long, env = "SPELLING_BEE_SESSIONS")]
+        session_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Play a random practice puzzle in the REPL, the same one for everyone
+    /// on a date.
+    Practice {
+        /// The date of the puzzle, by default today's.
+        #[arg(long)]
+        date: Option<PuzzleDate>,
+
+        /// Play the puzzle of this seed instead, e.g. one shared by another
+        /// player. The seed of a date is the date's digits, like 20240601.
+        #[arg(long, conflicts_with = "date")]
+        seed: Option<u64>,
+
+        /// Store the progress on every puzzle in this directory, for `stats me`.
+        #[arg(long, env = "SPELLING_BEE_SESSIONS")]
+        session_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        generator: GeneratorArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Answer `!bee` commands in a Discord channel, e.g. `!bee c altefi` or
+    /// `!bee today hints`, with the answers behind spoilers.
+    DiscordBot {
+        /// The token of the Discord bot, which needs to read and send messages
+        /// in the channel.
+        #[arg(long, env = "DISCORD_TOKEN", hide_env_values = true)]
+        token: String,
+
+        /// The id of the channel to answer commands in.
+        #[arg(long)]
+        channel: String,
+
+        /// How often to check for new commands, in seconds.
+        #[arg(long, value_name = "SECONDS", default_value_t = 2)]
+        interval: u64,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Write a puzzle file to solve with `solve --puzzle-file`: a custom
+    /// puzzle, an archived one with its official answers, or a practice one.
+    ExportPuzzle {
+        /// The center letter of a custom puzzle.
+        #[arg(long, requires = "letters", conflicts_with_all = ["date", "seed"])]
+        center: Option<String>,
+
+        /// The six outer letters of a custom puzzle, e.g. `altefi`.
+        #[arg(long, requires = "center")]
+        letters: Option<String>,
+
+        /// Export the archived puzzle of this date.
+        #[arg(long, conflicts_with = "seed")]
+        date: Option<PuzzleDate>,
+
+        /// Export the practice puzzle of this seed, with its answers.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Include the dictionary's answers of a custom puzzle.
+        #[arg(long, requires = "center")]
+        answers: bool,
+
+        /// Write the file here instead of to stdout.
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// What the practice puzzle of `--seed` has to meet.
+        #[command(flatten)]
+        generator: GeneratorArgs,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Write a small shareable image of the hints of today's puzzle: its
+    /// number of words, points and pangrams, without any answers.
+    Badge {
+        /// The image format: svg or png.
+        #[arg(long, default_value_t = BadgeFormat::default())]
+        format: BadgeFormat,
+
+        /// The archived puzzle of this date instead of today's.
+        #[arg(long)]
+        date: Option<PuzzleDate>,
+
+        /// Write the image here instead of to stdout.
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Dictionary maintenance.
+    Dict {
+        #[command(subcommand)]
+        command: DictCommand,
+    },
+    /// Maintain the local archive of official NYT puzzles.
+    Archive {
+        #[command(subcommand)]
+        command: ArchiveCommand,
+    },
+    /// Look up the archived puzzles a word fits, or the pangrams of a set of letters.
+    Query {
+        #[command(subcommand)]
+        command: QueryCommand,
+    },
+    /// The available solving strategies.
+    Strategies {
+        #[command(subcommand)]
+        command: StrategiesCommand,
+    },
+    /// Print a sheet with the hive, the hints and blank answer lines for solving
+    /// a NYT puzzle on paper.
+    Print {
+        /// Publication date of the puzzle (e.g. 2024-06-01), the current one by default.
+        #[arg(long)]
+        date: Option<PuzzleDate>,
+
+        /// File to write the sheet to instead of stdout.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Write a PDF instead of HTML, converted with wkhtmltopdf or a headless Chromium.
+        #[arg(long, requires = "output")]
+        pdf: bool,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Time building the index of and solving with each strategy.
+    Bench {
+        /// Comma separated names of the strategies (see `strategies list`), or `all`.
+        #[arg(long, value_delimiter = ',', default_value = "all")]
+        strategies: Vec<String>,
+
+        /// How often each strategy is built and run.
+        #[arg(long, default_value_t = 10)]
+        repeat: usize,
+
+        /// The center letter of the benchmarked puzzle.
+        #[arg(long, default_value = "C")]
+        center: String,
+
+        /// The six outer letters of the benchmarked puzzle.
+        #[arg(long, default_value = "ALTEFI")]
+        letters: String,
+
+        #[command(flatten)]
+        rules: RulesArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Summarize the solve durations recorded with `--metrics`.
+    Metrics {
+        #[command(subcommand)]
+        command: MetricsCommand,
+    },
+    /// Check that every strategy finds the same answers as brute force on random
+    /// puzzles, reporting divergences with a minimized word list reproducing them.
+    Fuzz {
+        /// Number of random puzzles.
+        #[arg(long, default_value_t = 1000)]
+        iterations: usize,
+
+        /// Seed of the random puzzles, picked from the current time if not given.
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Also generate puzzles with other rules, such as a different minimum length.
+        #[arg(long)]
+        vary_rules: bool,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Measure the solver against the official answers: solve archived puzzles
+    /// and print the precision and recall of their solutions.
+    Verify {
+        /// Verify the puzzle of this date, fetching and archiving it if it is
+        /// the current or previous one. By default the current puzzle.
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        date: Option<PuzzleDate>,
+
+        /// Verify the archived puzzles from this date on, e.g. 2023-01-01.
+        #[arg(long)]
+        from: Option<PuzzleDate>,
+
+        /// Verify the archived puzzles up to this date.
+        #[arg(long)]
+        to: Option<PuzzleDate>,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+    /// Aggregate statistics over the archived puzzles, or with `stats me`, over
+    /// your sessions.
+    #[command(args_conflicts_with_subcommands = true)]
+    Stats {
+        #[command(subcommand)]
+        command: Option<StatsCommand>,
+
+        /// First publication date to include, e.g. 2023-01-01.
+        #[arg(long)]
+        from: Option<PuzzleDate>,
+
+        /// Last publication date to include.
+        #[arg(long)]
+        to: Option<PuzzleDate>,
+
+        /// Number of entries shown per ranking.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+    },
+}
+
+#[derive(Args)]
+struct SolveArgs {
+    /// Solve the NYT puzzle of this date (e.g. 2024-06-01) from the archive,
+    /// fetching and archiving it if it is the current or previous one.
+    #[arg(long)]
+    date: Option<PuzzleDate>,
+
+    /// Read the puzzle from the clipboard, e.g. `Center: C, Letters: ALTEFI`
+    /// or `[C] A L T E F I`.
+    #[arg(long, conflicts_with_all = ["date", "from_text"])]
+    from_clipboard: bool,
+
+    /// Read the puzzle from a file (`-` for stdin) in the formats of
+    /// `--from-clipboard`, such as text recognized in a screenshot.
+    #[arg(long, value_name = "PATH", conflicts_with = "date")]
+    from_text: Option<PathBuf>,
+
+    /// Read the puzzle from a JSON puzzle file (see `export-puzzle`). If it
+    /// lists answers, the text output ends with how the solution differs.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["date", "from_clipboard", "from_text"]
+    )]
+    puzzle_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    archive: ArchiveArgs,
+
+    /// Output format of the solution.
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Obfuscate the answers, leaving hints and statistics readable, to share
+    /// partial help without spoiling the puzzle. Only for the text, stems, grid
+    /// and markdown formats.
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["tricky", "extensions", "genius", "known", "known_max_obscurity", "export"]
+    )]
+    spoiler: Option<Spoiler>,
+
+    /// Print how many words each strategy examined and why it rejected them.
+    #[arg(long)]
+    stats: bool,
+
+    /// Stop solving after this many milliseconds and print the answers found
+    /// so far, e.g. for huge word lists on slow devices.
+    #[arg(long, value_name = "MS")]
+    deadline: Option<u64>,
+
+    /// Only search for pangrams, using a dedicated index.
+    #[arg(long)]
+    pangrams_only: bool,
+
+    /// Memory-map the `--dict`, which must be sorted (see `dict sort`), and
+    /// only load the words spelled with the puzzle's letters instead of the
+    /// whole word list. The dictionary's word count is then theirs.
+    #[arg(long, requires = "dict")]
+    mapped: bool,
+
+    /// Solve with this strategy (see `strategies list`) instead of the rarest
+    /// letter map, can be repeated to compare their solutions and `--stats`. The
+    /// first one's solution is printed.
+    #[arg(
+        long = "strategy",
+        value_name = "NAME",
+        conflicts_with = "pangrams_only"
+    )]
+    strategies: Vec<String>,
+
+    /// Finish the text output with the N answers most likely to be missed.
+    #[arg(long, value_name = "N")]
+    tricky: Option<usize>,
+
+    /// Finish the text output with the families of answers extending other
+    /// answers, like TALC and TALCS.
+    #[arg(long)]
+    extensions: bool,
+
+    /// Finish the text output with the fewest answers reaching Genius, the
+    /// most common ones according to `--frequencies`.
+    #[arg(long)]
+    genius: bool,
+
+    /// Finish the text output with whether Genius is reachable with only the
+    /// answers in this file (one per line, `-` for stdin), and the fewest
+    /// others making up the shortfall otherwise.
+    #[arg(long, value_name = "PATH")]
+    known: Option<PathBuf>,
+
+    /// Like `--known`, counting the answers at most this obscure (from 0 for
+    /// the most common word to 1) according to `--frequencies` as known.
+    #[arg(long, value_name = "X", requires = "frequencies")]
+    known_max_obscurity: Option<f64>,
+
+    /// Word frequency list (`word count` per line) used to rank tricky words.
+    #[arg(long)]
+    frequencies: Option<PathBuf>,
+
+    /// Export the answers (for Anki, the --tricky N trickiest if given)
+    /// instead of printing the solution.
+    #[arg(long, value_enum)]
+    export: Option<ExportFormat>,
+
+    /// The SQLite database `--export sqlite` appends the solution to, created
+    /// if needed.
+    #[arg(long, value_name = "PATH", required_if_eq("export", "sqlite"))]
+    database: Option<PathBuf>,
+
+    /// Annotate each answer with how many archived puzzles (published before
+    /// `--date`, if given) had it as an official answer.
+    #[arg(long)]
+    official: bool,
+
+    /// Look up definitions of the answers (the exported words with `--export`) online.
+    #[arg(long)]
+    definitions: bool,
+
+    /// How many definitions to look up at once.
+    #[arg(long, value_name = "N", default_value_t = 8)]
+    lookups: usize,
+
+    /// How answers are scored.
+    #[arg(long, value_enum, default_value_t)]
+    scoring: ScoringSystem,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    #[command(flatten)]
+    rules: RulesArgs,
+
+    /// Keep running and re-solve with the first strategy whenever the `--dict`
+    /// file changes, printing the answers gained and lost.
+    #[arg(long)]
+    watch: bool,
+
+    #[command(flatten)]
+    dict: DictArgs,
+}
+
+#[derive(Args)]
+struct TodayArgs {
+    /// Value of the `NYT-S` cookie of a logged in nytimes.com session.
+    #[arg(long, env = "NYT_S", hide_env_values = true)]
+    cookie: Option<String>,
+
+    /// Print hints about the remaining words instead of the words themselves.
+    #[arg(long)]
+    hints: bool,
+
+    /// Only search for pangrams, using a dedicated index.
+    #[arg(long)]
+    pangrams_only: bool,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Output format of the solution.
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Obfuscate the answers like `solve --spoiler`.
+    #[arg(long, value_enum, conflicts_with = "hints")]
+    spoiler: Option<Spoiler>,
+
+    #[command(flatten)]
+    dict: DictArgs,
+}
+
+#[derive(Subcommand)]
+enum StatsCommand {
+    /// Your lifetime statistics over the sessions stored by `repl` and
+    /// `practice` with `--session-dir`: ranks, Genius streaks, pangrams and
+    /// words found.
+    Me {
+        /// The directory of the stored sessions.
+        #[arg(long, env = "SPELLING_BEE_SESSIONS", default_value = "sessions")]
+        session_dir: PathBuf,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum DictCommand {
+    /// Convert a word list into the compact binary format.
+    Compact {
+        input: PathBuf,
+        output: PathBuf,
+
+        #[command(flatten)]
+        parse: ParseArgs,
+    },
+    /// Write a word list sorted with one word per line, which can be memory-mapped
+    /// by `check --mapped`.
+    Sort {
+        input: PathBuf,
+        output: PathBuf,
+
+        #[command(flatten)]
+        parse: ParseArgs,
+    },
+    /// List the built-in word list sources with their licenses.
+    Sources,
+    /// Report duplicates, non-alphabetic, mixed case and too short words, and
+    /// encoding problems like byte order marks and CRLF line endings.
+    Lint {
+        input: PathBuf,
+
+        /// Write the normalized word list, sorted with one word per line, to
+        /// this file instead of failing on problems.
+        #[arg(long, value_name = "OUTPUT")]
+        fix: Option<PathBuf>,
+
+        #[command(flatten)]
+        parse: ParseArgs,
+    },
+    /// Report the words added and removed between two word lists.
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+
+        /// Also re-solve the archived puzzles with both word lists and report
+        /// which answers change.
+        #[arg(long)]
+        archived: bool,
+
+        /// First publication date of the re-solved puzzles.
+        #[arg(long, requires = "archived")]
+        from: Option<PuzzleDate>,
+
+        /// Last publication date of the re-solved puzzles.
+        #[arg(long, requires = "archived")]
+        to: Option<PuzzleDate>,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        parse: ParseArgs,
+    },
+    /// Score a word list against the official answers of the archived
+    /// puzzles: how many it contains, and which it lacks or wrongly suggests.
+    Coverage {
+        /// First publication date to include, e.g. 2023-01-01.
+        #[arg(long)]
+        from: Option<PuzzleDate>,
+
+        /// Last publication date to include.
+        #[arg(long)]
+        to: Option<PuzzleDate>,
+
+        /// Number of entries shown per ranking.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetricsCommand {
+    /// Print the runs and solve durations of each strategy per month.
+    Report,
+}
+
+#[derive(Subcommand)]
+enum StrategiesCommand {
+    /// List the strategies with their descriptions.
+    List,
+}
+
+#[derive(Subcommand)]
+enum ArchiveCommand {
+    /// Add the puzzles currently published by NYT to the archive.
+    Update {
+        #[command(flatten)]
+        archive: ArchiveArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueryCommand {
+    /// The archived puzzles a word was an answer of, or would have been, i.e.
+    /// whose letters spell it without NYT listing it.
+    Word {
+        word: String,
+
+        #[command(flatten)]
+        archive: ArchiveArgs,
+    },
+    /// The dictionary pangrams of a set of letters, e.g. CALTEFI.
+    Pangrams {
+        letters: String,
+
+        #[command(flatten)]
+        dict: DictArgs,
+    },
+}
+
+#[derive(Args)]
+struct ArchiveArgs {
+    /// Archive of official puzzles, stored as JSON Lines.
+    #[arg(long, env = "SPELLING_BEE_ARCHIVE", default_value = "archive.jsonl")]
+    archive: PathBuf,
+}
+
+#[derive(Args)]
+struct GeneratorArgs {
+    /// Letters practice puzzles may not contain.
+    #[arg(long, value_name = "LETTERS", default_value = "S")]
+    exclude: String,
+
+    /// Minimum number of vowels (A, E, I, O and U) among the seven letters.
+    #[arg(long, default_value_t = 0)]
+    min_vowels: usize,
+
+    /// Maximum number of vowels among the seven letters.
+    #[arg(long, default_value_t = 7)]
+    max_vowels: usize,
+
+    /// Require a pangram using each letter exactly once.
+    #[arg(long)]
+    perfect_pangram: bool,
+
+    /// Require an estimated difficulty: easy, medium or hard.
+    #[arg(long)]
+    difficulty: Option<Rating>,
+
+    /// Minimum number of answers.
+    #[arg(long, default_value_t = ValidationOptions::default().min_answers)]
+    min_answers: usize,
+
+    /// Maximum number of answers.
+    #[arg(long, default_value_t = ValidationOptions::default().max_answers)]
+    max_answers: usize,
+}
+
+impl GeneratorArgs {
+    fn constraints(&self) -> anyhow::Result<Constraints> {
+        let exclude = self
+            .exclude
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(Letter::new)
+            .collect::<Result<_, _>>()?;
+        let mut constraints = Constraints::new()
+            .with(Constraint::ExcludeLetters(exclude))
+            .with(Constraint::Answers(self.min_answers..=self.max_answers));
+        if (self.min_vowels, self.max_vowels) != (0, 7) {
+            constraints = constraints.with(Constraint::Vowels(self.min_vowels..=self.max_vowels));
+        }
+        if self.perfect_pangram {
+            constraints = constraints.with(Constraint::PerfectPangram);
+        }
+        if let Some(rating) = self.difficulty {
+            constraints = constraints.with(Constraint::Difficulty(rating));
+        }
+        Ok(constraints)
+    }
+}
+
+#[derive(Args)]
+struct PuzzleArgs {
+    /// The center letter, which every answer must contain.
+    #[arg(long)]
+    center: String,
+
+    /// The six outer letters, e.g. `altefi`.
+    #[arg(long)]
+    letters: String,
+
+    #[command(flatten)]
+    rules: RulesArgs,
+}
+
+impl PuzzleArgs {
+    fn puzzle(&self) -> anyhow::Result<PuzzleSpec> {
+        let game = Game::parse(&self.center, &self.letters)?;
+        Ok(PuzzleSpec::with_rules(game, Rules::from(&self.rules)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum ScoringSystem {
+    /// The NYT Spelling Bee scoring.
+    #[default]
+    Nyt,
+    /// The sum of the letters' Scrabble tile values.
+    Scrabble,
+    /// The squared number of letters.
+    LengthSquared,
+}
+
+impl From<ScoringSystem> for Arc<dyn Scoring> {
+    fn from(system: ScoringSystem) -> Self {
+        match system {
+            ScoringSystem::Nyt => Arc::new(NytScoring),
+            ScoringSystem::Scrabble => Arc::new(ScrabbleScoring),
+            ScoringSystem::LengthSquared => Arc::new(LengthSquaredScoring),
+        }
+    }
+}
+
+/// Filters applied to the answers before they are printed.
+#[derive(Args)]
+struct FilterArgs {
+    /// Only print answers worth at least this many points.
+    #[arg(long, default_value_t = 0)]
+    min_points: usize,
+
+    /// Only print answers with at least this many letters. This only filters
+    /// the output; --min-answer-length changes which answers are valid and
+    /// their points instead.
+    #[arg(long, default_value_t = 0)]
+    min_length: usize,
+
+    /// Only print pangrams.
+    #[arg(long)]
+    only_pangrams: bool,
+
+    /// Skip this many answers, in the order of --order.
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
+
+    /// Print at most this many answers.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// The order of the answers in every output format: alpha, points_desc,
+    /// length_then_alpha or pangrams_first, with ties alphabetically.
+    #[arg(long, default_value_t = AnswerOrder::default())]
+    order: AnswerOrder,
+}
+
+impl From<&FilterArgs> for ResultFilter {
+    fn from(args: &FilterArgs) -> Self {
+        ResultFilter {
+            min_points: args.min_points,
+            min_length: args.min_length,
+            only_pangrams: args.only_pangrams,
+            offset: args.offset,
+            limit: args.limit,
+        }
+    }
+}
+
+/// Rules of variants of the Spelling Bee, which are the NYT ones by default.
+#[derive(Args)]
+struct RulesArgs {
+    /// Minimum number of letters of a valid answer, a rule which also changes
+    /// the points. To only print longer answers, use --min-length.
+    #[arg(long, default_value_t = Rules::SPELLING_BEE.min_length)]
+    min_answer_length: usize,
+
+    /// Accept answers that don't contain the center letter.
+    #[arg(long)]
+    no_center_letter: bool,
+
+    /// Only accept answers that use every letter.
+    #[arg(long)]
+    all_letters: bool,
+
+    /// Only accept answers whose consecutive letters are adjacent in the hive,
+    /// as rendered (outer letters clockwise from the top in alphabetical order).
+    #[arg(long)]
+    adjacent_letters: bool,
+
+    /// Only accept answers using each letter at most this often, e.g. 1 for
+    /// variants without reusing letters.
+    #[arg(long, value_name = "N")]
+    max_letter_uses: Option<usize>,
+}
+
+impl From<&RulesArgs> for Rules {
+    fn from(args: &RulesArgs) -> Self {
+        Rules {
+            min_length: args.min_answer_length,
+            require_center_letter: !args.no_center_letter,
+            require_all_letters: args.all_letters,
+            require_adjacent_letters: args.adjacent_letters,
+            max_letter_uses: args.max_letter_uses,
+        }
+    }
+}
+
+/// Options of the HTTP client used to fetch word lists and puzzles.
+#[derive(Args)]
+struct HttpArgs {
+    /// Timeout of a single HTTP request in seconds.
+    #[arg(long, global = true, value_name = "SECONDS", default_value_t = HttpConfig::default().timeout.as_secs())]
+    http_timeout: u64,
+
+    /// How often failed HTTP requests are retried, with exponential backoff.
+    #[arg(long, global = true, default_value_t = HttpConfig::default().retries)]
+    retries: u32,
+
+    /// Proxy for all HTTP requests, overriding the HTTP(S)_PROXY environment variables.
+    #[arg(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// User agent sent with all HTTP requests.
+    #[arg(long, global = true, default_value_t = HttpConfig::default().user_agent)]
+    user_agent: String,
+
+    /// Time in seconds that all HTTP requests of the command together may take,
+    /// including retries. Word lists fall back to their cached copies when
+    /// downloading them runs out of time.
+    #[arg(long, global = true, value_name = "SECONDS")]
+    timeout: Option<u64>,
+}
+
+impl From<&HttpArgs> for HttpConfig {
+    fn from(args: &HttpArgs) -> Self {
+        HttpConfig {
+            timeout: Duration::from_secs(args.http_timeout),
+            retries: args.retries,
+            proxy: args.proxy.clone(),
+            user_agent: args.user_agent.clone(),
+            deadline: args
+                .timeout
+                .map(|timeout| Instant::now() + Duration::from_secs(timeout)),
+            ..HttpConfig::default()
+        }
+    }
+}
+
+#[derive(Args)]
+struct DictArgs {
+    /// Word list to use instead of scraping the default one: a local file, or
+    /// the name of a built-in source (enable, sowpods, twl06 or 12dicts, see
+    /// `dict sources`) that is downloaded once.
+    #[arg(long)]
+    dict: Option<DictLocation>,
+
+    /// Directory caching the word lists of built-in sources and the last
+    /// scraped word list, which is used if scraping fails.
+    #[arg(
+        long,
+        env = "SPELLING_BEE_DICT_CACHE",
+        default_value = "word-lists",
+        value_name = "DIR"
+    )]
+    dict_cache: PathBuf,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+
+    #[command(flatten)]
+    profile: ProfileArgs,
+
+    /// File of words that are never answers, one per line, e.g. ones NYT is
+    /// known to reject.
+    #[arg(long, value_name = "PATH")]
+    exclude_words: Option<PathBuf>,
+
+    /// File of words that are valid answers even if the word list lacks them,
+    /// one per line, e.g. ones NYT is known to accept.
+    #[arg(long, value_name = "PATH")]
+    include_words: Option<PathBuf>,
+}
+
+impl DictArgs {
+    /// The words of `--include-words` and `--exclude-words`.
+    fn overlay(&self) -> anyhow::Result<WordOverlay> {
+        let mut overlay = WordOverlay::new();
+        if let Some(path) = &self.include_words {
+            overlay = overlay.include_file(path)?;
+        }
+        if let Some(path) = &self.exclude_words {
+            overlay = overlay.exclude_file(path)?;
+        }
+        Ok(overlay)
+    }
+}
+
+/// Where `--dict` loads the word list from.
+#[derive(Clone)]
+enum DictLocation {
+    Source(&'static DictionarySource),
+    /// Files named like a source can be passed as `./enable`.
+    Path(PathBuf),
+}
+
+impl FromStr for DictLocation {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match DictionarySource::find(s) {
+            Some(source) => DictLocation::Source(source),
+            None => DictLocation::Path(PathBuf::from(s)),
+        })
+    }
+}
+
+/// Rules approximating NYT's curation, see `NytProfile`.
+#[derive(Args)]
+struct ProfileArgs {
+    /// Drop words whose only S is a plural ending, like FACETS next to FACET.
+    #[arg(long)]
+    drop_plurals: bool,
+
+    /// Drop words more obscure than this (0 is the most frequent word, 1 words
+    /// missing from `--obscurity-frequencies`).
+    #[arg(long, requires = "obscurity_frequencies")]
+    max_obscurity: Option<f64>,
+
+    /// Word frequency list (`word count` per line) used by `--max-obscurity`.
+    #[arg(long, requires = "max_obscurity")]
+    obscurity_frequencies: Option<PathBuf>,
+
+    /// File of words to drop, such as offensive ones, one per line.
+    #[arg(long)]
+    blocklist: Option<PathBuf>,
+}
+
+impl ProfileArgs {
+    fn is_enabled(&self) -> bool {
+        self.drop_plurals || self.max_obscurity.is_some() || self.blocklist.is_some()
+    }
+
+    /// The profile of the enabled rules, if any are.
+    fn profile(&self) -> anyhow::Result<Option<NytProfile>> {
+        let max_obscurity = match (&self.obscurity_frequencies, self.max_obscurity) {
+            (Some(path), Some(max)) => Some((WordFrequencies::load(path)?, max)),
+            _ => None,
+        };
+        let blocklist = match &self.blocklist {
+            Some(path) => NytProfile::load_blocklist(path)?,
+            None => HashSet::new(),
+        };
+        if !self.drop_plurals && max_obscurity.is_none() && blocklist.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(NytProfile {
+            drop_plurals: self.drop_plurals,
+            max_obscurity,
+            blocklist,
+        }))
+    }
+}
+
+#[derive(Args)]
+struct ParseArgs {
+    /// Language of the word list (en, de, es or fr), which decides how letters
+    /// outside its alphabet are spelled and the defaults of the other options.
+    #[arg(long = "lang", value_name = "LANG", default_value_t)]
+    language: Language,
+
+    /// Strip apostrophes from words instead of dropping those words.
+    #[arg(long)]
+    strip_apostrophes: bool,
+
+    /// Strip diacritics from words instead of dropping those words.
+    #[arg(long)]
+    strip_diacritics: bool,
+
+    /// Keep capitalized words, which are dropped as likely proper nouns by default.
+    #[arg(long)]
+    keep_proper_nouns: bool,
+
+    /// Keep all uppercase words without vowels, which are dropped as likely
+    /// abbreviations (e.g. HTML) from lists with lowercase words by default.
+    #[arg(long)]
+    keep_abbreviations: bool,
+
+    /// Keep Roman numerals (e.g. XLIV), which are dropped from lists with
+    /// lowercase words by default.
+    #[arg(long)]
+    keep_roman_numerals: bool,
+}
+
+impl From<&ParseArgs> for ParseOptions {
+    fn from(args: &ParseArgs) -> Self {
+        let defaults = args.language.parse_options();
+        ParseOptions {
+            strip_apostrophes: defaults.strip_apostrophes || args.strip_apostrophes,
+            strip_diacritics: defaults.strip_diacritics || args.strip_diacritics,
+            drop_proper_nouns: defaults.drop_proper_nouns && !args.keep_proper_nouns,
+            drop_abbreviations: defaults.drop_abbreviations && !args.keep_abbreviations,
+            drop_roman_numerals: defaults.drop_roman_numerals && !args.keep_roman_numerals,
+            ..defaults
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // clap exits with 2 on usage errors, which is reserved for invalid puzzles.
+        Err(e) if e.use_stderr() => {
+            let _ = e.print();
+            return exit::usage_error();
+        }
+        Err(e) => e.exit(),
+    };
 
-fn main() -> anyhow::Result<()> {
     simple_logger::SimpleLogger::new()
         .with_level(log::LevelFilter::Info)
         .init()
         .unwrap();
+    let threads = match cli.threads {
+        0 => std::thread::available_parallelism().map_or(1, usize::from),
+        threads => threads,
+    };
+    anstream::ColorChoice::write_global(cli.color.into());
+    let timings = Timings::new(threads);
+    let format = cli.timings;
+    let result = run(cli, &timings);
+    if let Some(format) = format
+        && let Err(e) = timings.write(format, &mut std::io::stderr().lock())
+    {
+        log::warn!("failed to report the timings: {e:?}");
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // like returning the error from `main`, but with a specific exit code.
+            eprintln!("Error: {e:?}");
+            exit::exit_code(&e)
+        }
+    }
+}
+
+/// The global options and state shared by the commands.
+struct Globals<'a> {
+    http: &'a HttpClient,
+    threads: usize,
+    metrics: Option<&'a Path>,
+    timings: &'a Timings,
+    theme: Theme,
+}
+
+fn run(cli: Cli, timings: &Timings) -> anyhow::Result<()> {
+    let http = HttpClient::new(HttpConfig::from(&cli.http))?;
+    let globals = &Globals {
+        http: &http,
+        threads: cli.threads,
+        metrics: cli.metrics.as_deref(),
+        timings,
+        theme: cli.theme.unwrap_or_default(),
+    };
+
+    match cli.command {
+        Command::Solve(args) => solve(&args, globals),
+        Command::Today(args) => today(&args, globals),
+        Command::Check {
+            word,
+            puzzle,
+            mapped,
+            json,
+            dict,
+        } => check_word(&word, &puzzle.puzzle()?, mapped, json, &dict, globals),
+        Command::SuggestCenter { letters, dict } => {
+            let letters: Vec<Letter> = letters
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(Letter::new)
+                .collect::<Result<_, _>>()?;
+            let distinct: HashSet<_> = letters.iter().collect();
+            anyhow::ensure!(
+                distinct.len() == letters.len(),
+                "every letter must occur only once"
+            );
+            let dict = load_dict(&dict, globals)?;
+            let solver = timings.time(Phase::Index, "rarest-letter-map", || {
+                GameSolver::<RarestLetterMap>::new(&dict, cli.threads)
+            })?;
+            let options = timings.time(Phase::Solve, "suggest centers", || {
+                center::suggest_centers(&solver, &letters)
+            })?;
+            for option in options {
+                println!("{option}");
+            }
+            Ok(())
+        }
+        Command::Validate {
+            puzzle,
+            forbidden_letters,
+            min_answers,
+            max_answers,
+            dict,
+        } => {
+            let puzzle = puzzle.puzzle()?;
+            let options = ValidationOptions {
+                forbidden_letters: forbidden_letters
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(Letter::new)
+                    .collect::<Result<_, _>>()?,
+                min_answers,
+                max_answers,
+            };
+            let dict = load_dict_for(&dict, &puzzle, globals)?;
+            let report = validate::validate(&puzzle, &options, || {
+                GameSolver::<RarestLetterMap>::new(&dict, cli.threads)?.solve(&puzzle)
+            })?;
+            print!("{report}");
+            if report.lacks_pangram() {
+                let index = PangramIndex::new(&dict);
+                let swaps = validate::nearest_pangrams(&puzzle, &options, &index);
+                if swaps.is_empty() {
+                    println!("no swap of a single outer letter gives a pangram");
+                } else {
+                    println!("swaps of a single outer letter giving a pangram:");
+                    for swap in swaps {
+                        println!("  {swap}");
+                    }
+                }
+                return Err(anyhow::anyhow!(Failure::NoPangram));
+            }
+            anyhow::ensure!(report.is_valid(), Failure::Validation);
+            Ok(())
+        }
+        Command::Repl {
+            session,
+            session_dir,
+            dict,
+        } => {
+            let dict = load_dict(&dict, globals)?;
+            let lookup = |word: &str| definitions::lookup(&http, word);
+            let mut repl =
+                repl::Repl::new(&dict, cli.threads, globals.theme)?.with_definitions(&lookup);
+            if let Some(dir) = session_dir {
+                repl = repl.with_store(SessionStore::new(&dir));
+            }
+            if let Some(session) = session {
+                repl.load_session(&session)?;
+            }
+            repl.run(std::io::stdin().lock(), &mut anstream::stdout().lock())
+        }
+        Command::Practice {
+            date,
+            seed,
+            session_dir,
+            generator,
+            dict,
+        } => {
+            let seed = match (seed, date) {
+                (Some(seed), _) => seed,
+                (None, Some(date)) => practice::seed(date),
+                (None, None) => practice::seed(PuzzleDate::current()?),
+            };
+            let dict = load_dict(&dict, globals)?;
+            let lookup = |word: &str| definitions::lookup(&http, word);
+            let mut repl = repl::Repl::new(&dict, cli.threads, globals.theme)?
+                .with_definitions(&lookup)
+                .with_constraints(generator.constraints()?);
+            if let Some(dir) = session_dir {
+                repl = repl.with_store(SessionStore::new(&dir));
+            }
+            let mut out = anstream::stdout().lock();
+            repl.practice(seed, &mut out)?;
+            repl.run(std::io::stdin().lock(), &mut out)
+        }
+        Command::DiscordBot {
+            token,
+            channel,
+            interval,
+            dict,
+        } => {
+            let dict = load_dict(&dict, globals)?;
+            discord::DiscordBot::new(globals.http, &token, &channel, &dict, cli.threads)?
+                .run(Duration::from_secs(interval))
+        }
+        Command::ExportPuzzle {
+            center,
+            letters,
+            date,
+            seed,
+            answers,
+            output,
+            generator,
+            archive,
+            dict,
+        } => {
+            let file = match (center.zip(letters), date, seed) {
+                (Some((center, letters)), _, _) => {
+                    let game = Game::parse(&center, &letters)?;
+                    let file = PuzzleFile::new(&game);
+                    if answers {
+                        let dict = load_dict(&dict, globals)?;
+                        let result = GameSolver::<RarestLetterMap>::new(&dict, cli.threads)?
+                            .solve(&PuzzleSpec::from(game))?;
+                        file.with_result(&result)
+                    } else {
+                        file
+                    }
+                }
+                (None, Some(date), _) => {
+                    let mut archive = Archive::open(&archive.archive)?;
+                    let client = nyt::NytClient::new(globals.http.clone(), None);
+                    PuzzleFile::from(archive.get_or_fetch(date, &client)?)
+                }
+                (None, None, Some(seed)) => {
+                    let dict = load_dict(&dict, globals)?;
+                    let solver = GameSolver::<RarestLetterMap>::new(&dict, cli.threads)?;
+                    let (puzzle, result) = practice::practice_puzzle(
+                        &dict,
+                        seed,
+                        &generator.constraints()?,
+                        |puzzle| solver.solve(puzzle),
+                    )?;
+                    PuzzleFile::new(puzzle.game()).with_result(&result)
+                }
+                (None, None, None) => {
+                    anyhow::bail!("choose a puzzle with --center and --letters, --date or --seed")
+                }
+            };
+            match output {
+                Some(path) => file.save(&path),
+                None => {
+                    let mut out = std::io::stdout().lock();
+                    serde_json::to_writer_pretty(&mut out, &file)?;
+                    writeln!(out)?;
+                    Ok(())
+                }
+            }
+        }
+        Command::Badge {
+            format,
+            date,
+            output,
+            archive,
+            dict,
+        } => {
+            let client = nyt::NytClient::new(globals.http.clone(), None);
+            let (game, date) = match date {
+                Some(date) => {
+                    let mut archive = Archive::open(&archive.archive)?;
+                    (archive.get_or_fetch(date, &client)?.game(), date)
+                }
+                None => {
+                    let puzzle = globals
+                        .timings
+                        .time(Phase::Fetch, "today's puzzle", || client.today())?;
+                    (puzzle.game()?, puzzle.print_date)
+                }
+            };
+            let dict = load_dict(&dict, globals)?;
+            let result = globals.timings.time(Phase::Solve, "badge", || {
+                GameSolver::<RarestLetterMap>::new(&dict, cli.threads)?
+                    .solve(&PuzzleSpec::from(game))
+            })?;
+            let badge = Badge::new(&Hints::new(&result), Some(date));
+            match output {
+                Some(path) => badge.write(format, &mut File::create(&path)?)?,
+                None => badge.write(format, &mut std::io::stdout().lock())?,
+            }
+            Ok(())
+        }
+        Command::Dict {
+            command:
+                DictCommand::Compact {
+                    input,
+                    output,
+                    parse,
+                },
+        } => compact_dict(input, output, &parse, timings),
+        Command::Dict {
+            command:
+                DictCommand::Sort {
+                    input,
+                    output,
+                    parse,
+                },
+        } => sort_dict(input, output, &parse, timings),
+        Command::Dict {
+            command: DictCommand::Sources,
+        } => {
+            for source in DictionarySource::all() {
+                println!(
+                    "{} ({}): {}\n  {}",
+                    source.name, source.license, source.description, source.url
+                );
+            }
+            Ok(())
+        }
+        Command::Dict {
+            command: DictCommand::Lint { input, fix, parse },
+        } => lint_dict(&input, fix, &parse),
+        Command::Dict {
+            command:
+                DictCommand::Diff {
+                    old,
+                    new,
+                    archived,
+                    from,
+                    to,
+                    archive,
+                    parse,
+                },
+        } => {
+            let archived = if archived {
+                Some((Archive::open(&archive.archive)?, from, to))
+            } else {
+                None
+            };
+            diff_dicts(&old, &new, &parse, archived, globals)
+        }
+        Command::Dict {
+            command:
+                DictCommand::Coverage {
+                    from,
+                    to,
+                    top,
+                    archive,
+                    dict,
+                },
+        } => {
+            let archive = Archive::open(&archive.archive)?;
+            let dict = load_dict(&dict, globals)?;
+            dict_coverage(&dict, archive.range(from, to).collect(), top, globals)
+        }
+        Command::Archive {
+            command: ArchiveCommand::Update { archive },
+        } => update_archive(&archive.archive, globals),
+        Command::Query {
+            command: QueryCommand::Word { word, archive },
+        } => {
+            let archive = Archive::open(&archive.archive)?;
+            let word = word.to_uppercase();
+            let matches = query::puzzles_with_answer(&archive, &word);
+            for found in &matches {
+                let letters: String = found.puzzle.game().letters().map(char::from).collect();
+                let kind = match (found.official, found.pangram) {
+                    (true, true) => "official answer, pangram",
+                    (true, false) => "official answer",
+                    (false, true) => "not listed, pangram",
+                    (false, false) => "not listed",
+                };
+                println!("{} {letters}: {kind}", found.puzzle.print_date);
+            }
+            let official = matches.iter().filter(|found| found.official).count();
+            println!(
+                "{word} fits {} of {} archived puzzles, {official} of them listed it",
+                matches.len(),
+                archive.len()
+            );
+            Ok(())
+        }
+        Command::Query {
+            command: QueryCommand::Pangrams { letters, dict },
+        } => {
+            let letters: LetterSet = letters
+                .to_uppercase()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(Letter::new)
+                .collect::<Result<_, _>>()?;
+            let dict = load_dict(&dict, globals)?;
+            let index = PangramIndex::new(&dict);
+            for pangram in query::pangrams_of(&index, letters) {
+                println!("{pangram}");
+            }
+            Ok(())
+        }
+        Command::Strategies {
+            command: StrategiesCommand::List,
+        } => {
+            for strategy in StrategyRegistry::default().iter() {
+                let pangrams = if strategy.pangrams_only {
+                    " (pangrams only)"
+                } else {
+                    ""
+                };
+                println!("{}{pangrams}: {}", strategy.name, strategy.description);
+            }
+            Ok(())
+        }
+        Command::Print {
+            date,
+            output,
+            pdf,
+            archive,
+            dict,
+        } => print_sheet(
+            date,
+            output.as_deref(),
+            pdf,
+            &archive.archive,
+            &dict,
+            globals,
+        ),
+        Command::Bench {
+            strategies,
+            repeat,
+            center,
+            letters,
+            rules,
+            dict,
+        } => {
+            let registry = StrategyRegistry::default();
+            let strategies: Vec<&RegisteredStrategy> = if strategies == ["all"] {
+                registry.iter().collect()
+            } else {
+                strategies
+                    .iter()
+                    .map(|name| registry.find(name))
+                    .collect::<anyhow::Result<_>>()?
+            };
+            let puzzle =
+                PuzzleSpec::with_rules(Game::parse(&center, &letters)?, Rules::from(&rules));
+            let dict = load_dict_for(&dict, &puzzle, globals)?;
+            let timings = benchmark::run(&strategies, &dict, &puzzle, repeat, cli.threads)?;
+            benchmark::write_table(&timings, &mut std::io::stdout().lock())
+        }
+        Command::Metrics {
+            command: MetricsCommand::Report,
+        } => {
+            let path = globals.metrics.context(
+                "no metrics recorded, pass `--metrics PATH` or set SPELLING_BEE_METRICS",
+            )?;
+            metrics::write_report(path, &mut std::io::stdout().lock())
+        }
+        Command::Fuzz {
+            iterations,
+            seed,
+            vary_rules,
+            dict,
+        } => fuzz_strategies(iterations, seed, vary_rules, &dict, globals),
+        Command::Verify {
+            date,
+            from,
+            to,
+            archive,
+            dict,
+        } => {
+            let mut archive = Archive::open(&archive.archive)?;
+            if from.is_none() && to.is_none() {
+                let date = match date {
+                    Some(date) => date,
+                    None => PuzzleDate::current()?,
+                };
+                let client = nyt::NytClient::new(globals.http.clone(), None);
+                archive.get_or_fetch(date, &client)?;
+                verify(
+                    &dict,
+                    archive.range(Some(date), Some(date)).collect(),
+                    globals,
+                )
+            } else {
+                verify(&dict, archive.range(from, to).collect(), globals)
+            }
+        }
+        Command::Stats {
+            command: Some(StatsCommand::Me { session_dir, dict }),
+            ..
+        } => player_stats(&session_dir, &dict, globals),
+        Command::Stats {
+            command: None,
+            from,
+            to,
+            top,
+            archive,
+        } => {
+            let archive = Archive::open(&archive.archive)?;
+            let puzzles = archive.range(from, to);
+            print!("{}", ArchiveStats::new(puzzles, top));
+            Ok(())
+        }
+    }
+}
+
+fn load_dict(args: &DictArgs, globals: &Globals) -> anyhow::Result<Dictionary> {
+    load_dict_with(args, ParseOptions::from(&args.parse), globals)
+}
+
+/// The dictionary to solve `puzzle` with, which keeps words as short as its
+/// rules allow.
+fn load_dict_for(
+    args: &DictArgs,
+    puzzle: &PuzzleSpec,
+    globals: &Globals,
+) -> anyhow::Result<Dictionary> {
+    load_dict_with(args, parse_options_for(args, puzzle), globals)
+}
+
+fn parse_options_for(args: &DictArgs, puzzle: &PuzzleSpec) -> ParseOptions {
+    ParseOptions {
+        min_length: puzzle.rules().min_length,
+        ..ParseOptions::from(&args.parse)
+    }
+}
+
+fn load_dict_with(
+    args: &DictArgs,
+    options: ParseOptions,
+    globals: &Globals,
+) -> anyhow::Result<Dictionary> {
+    let load = |path: &Path| {
+        globals.timings.time(Phase::Parse, "load dictionary", || {
+            Dictionary::load(path, options)
+        })
+    };
+    let dict = match &args.dict {
+        Some(DictLocation::Path(path)) => load(path),
+        Some(DictLocation::Source(source)) => {
+            let path = globals.timings.time(Phase::Fetch, source.name, || {
+                source.fetch(globals.http, &args.dict_cache)
+            });
+            path.and_then(|path| Ok(load(&path)?.with_metadata(source.metadata(&path))))
+        }
+        None => {
+            // downloading and parsing can't be told apart.
+            let scraped = globals.timings.time(Phase::Fetch, "scrape dictionary", || {
+                Dictionary::scrape(globals.http, options)
+            });
+            // the last scraped word list stands in if scraping fails or times out.
+            let cache = args.dict_cache.join("scraped.txt");
+            match scraped {
+                Ok(dict) => {
+                    let written = std::fs::create_dir_all(&args.dict_cache)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|()| write_sorted(&dict, &cache));
+                    if let Err(e) = written {
+                        log::warn!("failed to cache the scraped word list: {e:#}");
+                    }
+                    Ok(dict)
+                }
+                Err(e) if cache.exists() => {
+                    log::warn!(
+                        "failed to scrape the word list ({e:#}), using the copy cached at {}",
+                        cache.display()
+                    );
+                    load(&cache)
+                }
+                Err(e) => Err(e),
+            }
+        }
+    }
+    .context(Failure::Dictionary)?;
+    curate_dict(dict, args)
+}
+
+/// The words of the memory-mapped `--dict` that could be answers of `puzzle`,
+/// curated like by `load_dict`.
+fn load_mapped_dict(
+    args: &DictArgs,
+    puzzle: &PuzzleSpec,
+    globals: &Globals,
+) -> anyhow::Result<Dictionary> {
+    let Some(DictLocation::Path(path)) = &args.dict else {
+        anyhow::bail!("--mapped requires a local --dict file");
+    };
+    let words = globals
+        .timings
+        .time(Phase::Parse, "map dictionary", || {
+            MappedDictionary::open(path, parse_options_for(args, puzzle))
+        })
+        .context(Failure::Dictionary)?;
+    log::info!("mapped {} words", words.len());
+    let dict = globals
+        .timings
+        .time(Phase::Parse, "select candidates", || {
+            words.candidates(puzzle)
+        })
+        .with_metadata(DictionaryMetadata {
+            source: Some(path.display().to_string()),
+            ..DictionaryMetadata::default()
+        });
+    curate_dict(dict, args)
+}
+
+/// Apply the profile and the overlay of `args` to a loaded dictionary.
+fn curate_dict(mut dict: Dictionary, args: &DictArgs) -> anyhow::Result<Dictionary> {
+    if let Some(profile) = args.profile.profile().context(Failure::Dictionary)? {
+        profile.apply(&mut dict);
+    }
+    let overlay = args.overlay().context(Failure::Dictionary)?;
+    if !overlay.is_empty() {
+        dict = dict.with_overlay(overlay);
+    }
+    log::info!("dictionary had {} entries", dict.len());
+    Ok(dict)
+}
+
+/// The `--spoiler` for solutions written to stdout in `format`.
+fn spoiler(spoiler: Option<Spoiler>, format: OutputFormat) -> anyhow::Result<Option<Spoiler>> {
+    let Some(spoiler) = spoiler else {
+        return Ok(None);
+    };
+    anyhow::ensure!(
+        matches!(
+            format,
+            OutputFormat::Text | OutputFormat::Stems | OutputFormat::Grid | OutputFormat::Markdown
+        ),
+        "--spoiler only hides the answers of the text, stems, grid and markdown formats"
+    );
+    let styled = anstream::AutoStream::choice(&std::io::stdout()) != anstream::ColorChoice::Never;
+    Ok(Some(spoiler.for_output(styled)))
+}
+
+/// The clipboard's text, read with the first available platform tool.
+fn read_clipboard() -> anyhow::Result<String> {
+    const TOOLS: &[(&str, &[&str])] = &[
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-out"]),
+        ("xsel", &["--clipboard", "--output"]),
+        ("pbpaste", &[]),
+        ("powershell", &["-NoProfile", "-Command", "Get-Clipboard"]),
+    ];
+    for (program, args) in TOOLS {
+        match std::process::Command::new(program).args(*args).output() {
+            Ok(output) if output.status.success() => {
+                return String::from_utf8(output.stdout)
+                    .context("clipboard text is not valid UTF-8");
+            }
+            Ok(output) => log::debug!("{program} failed with {}", output.status),
+            Err(e) => log::debug!("failed to run {program}: {e}"),
+        }
+    }
+    anyhow::bail!("failed to read the clipboard, install wl-paste, xclip or xsel")
+}
+
+/// The text of `path`, or of stdin for `-`.
+fn read_text(path: &Path) -> anyhow::Result<String> {
+    if path == Path::new("-") {
+        return std::io::read_to_string(std::io::stdin()).context("failed to read stdin");
+    }
+    std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn check_word(
+    word: &str,
+    puzzle: &PuzzleSpec,
+    mapped: bool,
+    json: bool,
+    args: &DictArgs,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    let write = |check: &Result<Score, GuessingError>, suggestions: &[&Word]| {
+        let mut out = std::io::stdout().lock();
+        if json {
+            output::write_check_json(word, check, suggestions, &mut out)
+        } else {
+            output::write_check(word, check, suggestions, &mut out)
+        }
+    };
+    let (check, dict) = match (&args.dict, mapped) {
+        (Some(DictLocation::Path(path)), true) => {
+            let words = globals
+                .timings
+                .time(Phase::Parse, "map dictionary", || {
+                    MappedDictionary::open(path, parse_options_for(args, puzzle))
+                })
+                .context(Failure::Dictionary)?;
+            (puzzle.check(word, &words)?, None)
+        }
+        _ => {
+            let dict = load_dict_for(args, puzzle, globals)?;
+            (puzzle.check(word, &dict)?, Some(dict))
+        }
+    };
+    if !matches!(check, Err(GuessingError::UnknownWord)) {
+        return write(&check, &[]);
+    }
+
+    // the answers are only needed to suggest near misses of unknown words.
+    let dict = match dict {
+        Some(dict) => dict,
+        None => load_dict_for(args, puzzle, globals)?,
+    };
+    let solver = globals
+        .timings
+        .time(Phase::Index, "rarest-letter-map", || {
+            GameSolver::<RarestLetterMap>::new(&dict, globals.threads)
+        })?;
+    let result = globals
+        .timings
+        .time(Phase::Solve, "rarest-letter-map", || solver.solve(puzzle))?;
+    let suggestions = suggest::near_misses(word, &result);
+    write(&check, &suggestions)
+}
+
+fn solve(args: &SolveArgs, globals: &Globals) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !args.watch || matches!(args.dict.dict, Some(DictLocation::Path(_))),
+        "--watch requires a local --dict file"
+    );
+    let spoiler = spoiler(args.spoiler, args.format)?;
+    let frequencies = match &args.frequencies {
+        Some(path) => Some(WordFrequencies::load(path)?),
+        None => None,
+    };
+
+    let mut archive = if args.date.is_some() || args.official {
+        Some(Archive::open(&args.archive.archive)?)
+    } else {
+        None
+    };
+    let puzzle_file = args
+        .puzzle_file
+        .as_deref()
+        .map(PuzzleFile::load)
+        .transpose()?;
+    let game = if let (Some(date), Some(archive)) = (args.date, &mut archive) {
+        let client = nyt::NytClient::new(globals.http.clone(), None);
+        archive.get_or_fetch(date, &client)?.game()
+    } else if let Some(file) = &puzzle_file {
+        file.game()?
+    } else if args.from_clipboard {
+        share::parse_game(&read_clipboard()?)?
+    } else if let Some(path) = &args.from_text {
+        share::parse_game(&read_text(path)?)?
+    } else {
+        Game::parse("C", "ALTEFI")?
+    };
+    let corpus = match (&archive, args.official) {
+        (Some(archive), true) => {
+            let before = args.date.map(|date| date.previous()).transpose()?;
+            let corpus = AnswerCorpus::new(archive.range(None, before));
+            log::info!("the archive has {} official answers", corpus.len());
+            Some(corpus)
+        }
+        _ => None,
+    };
+    let puzzle = PuzzleSpec::with_rules(game, Rules::from(&args.rules));
+    if let OutputFormat::Ndjson = args.format {
+        return solve_streaming(args, &puzzle, globals);
+    }
+
+    let dict = load_solve_dict(args, &puzzle, globals)?;
+    let scoring = Arc::<dyn Scoring>::from(args.scoring);
+    let registry = StrategyRegistry::default();
+    let strategies: Vec<&RegisteredStrategy> = if args.strategies.is_empty() {
+        let default = if args.pangrams_only {
+            "pangram-index"
+        } else {
+            "rarest-letter-map"
+        };
+        vec![registry.find(default)?]
+    } else {
+        args.strategies
+            .iter()
+            .map(|name| registry.find(name))
+            .collect::<anyhow::Result<_>>()?
+    };
+    let pangrams_only = strategies.iter().all(|strategy| strategy.pangrams_only);
+    let watched = strategies[0];
+    let mut runs = Vec::new();
+    for strategy in strategies {
+        let solver = globals.timings.time(Phase::Index, strategy.name, || {
+            (strategy.build)(&dict, globals.threads, Arc::clone(&scoring))
+        })?;
+        let start = Instant::now();
+        let (sol, stats) = globals.timings.time(Phase::Solve, strategy.name, || {
+            let Some(deadline) = args.deadline else {
+                return solver.solve_with_stats(&puzzle);
+            };
+            let partial = solver.solve_with_deadline(&puzzle, Duration::from_millis(deadline))?;
+            if partial.is_partial() {
+                log::warn!(
+                    "{} ran out of time after examining {:.0}% of the candidates, the solution is partial",
+                    strategy.name,
+                    100.0 * partial.completion()
+                );
+            }
+            Ok((partial.result, partial.stats))
+        })?;
+        if let Some(path) = globals.metrics {
+            let metric = SolveMetric::new(
+                "solve",
+                strategy.name,
+                start.elapsed(),
+                dict.len(),
+                args.date,
+                sol.entries().len(),
+            );
+            metrics::append(path, &metric)?;
+        }
+        runs.push((strategy.name, sol, stats));
+    }
+
+    let (reference, sol, _) = &runs[0];
+    for (label, other, _) in &runs[1..] {
+        if other != sol {
+            log::warn!("{label} found a different solution than {reference}");
+        }
+    }
+    // rated and planned before filtering, which only hides answers.
+    let difficulty = sol.difficulty(frequencies.as_ref());
+    let genius = args.genius.then(|| sol.genius_plan(frequencies.as_ref()));
+    let reach = if args.known.is_some() || args.known_max_obscurity.is_some() {
+        let known: HashSet<Word> = match &args.known {
+            Some(path) => read_text(path)?
+                .lines()
+                .map(|line| line.trim().to_uppercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            None => HashSet::new(),
+        };
+        let common = |word: &Word| match (args.known_max_obscurity, &frequencies) {
+            (Some(max), Some(frequencies)) => frequencies.obscurity(word) <= max,
+            _ => false,
+        };
+        Some(sol.genius_reach(
+            |word| known.contains(word) || common(word),
+            frequencies.as_ref(),
+        ))
+    } else {
+        None
+    };
+    let listed = puzzle_file.as_ref().and_then(|file| file.compare(sol));
+    let sol = &sol
+        .clone()
+        .with_order(args.filter.order)
+        .filtered(&ResultFilter::from(&args.filter));
+
+    let mut out = anstream::stdout().lock();
+    let header = globals.theme.header;
+    let lookup = |word: &str| definitions::lookup(globals.http, word);
+    let mut enricher = Enricher::new(args.lookups);
+    if args.definitions {
+        enricher = enricher.with_definitions(&lookup);
+    }
+    if let Some(corpus) = &corpus {
+        enricher = enricher.with_corpus(corpus);
+    }
 
-    let dict = timeit!("scrape dictionary", Dictionary::scrape()?);
-    log::info!("dictionary had {} entries", dict.words.len());
+    if let Some(ExportFormat::Anki) = args.export {
+        let ranked = TrickinessScorer::new(&dict, frequencies.as_ref()).rank(sol);
+        let ranked = &ranked[..args.tricky.unwrap_or(ranked.len()).min(ranked.len())];
+        // only the exported words are looked up.
+        let mut exported = sol.clone();
+        exported.retain(|word| ranked.iter().any(|tricky| tricky.word == word));
+        let enriched = globals.timings.time(Phase::Enrich, "enrich answers", || {
+            enricher.enrich(&exported)
+        });
+        let cards: Vec<_> = ranked
+            .iter()
+            .map(|tricky| {
+                let definition = enriched
+                    .get(tricky.word)
+                    .and_then(|enrichment| enrichment.definition.clone());
+                (tricky, definition)
+            })
+            .collect();
+        globals.timings.time(Phase::Format, "anki", || {
+            export::write_anki(&cards, &puzzle, &mut out)
+        })?;
+    } else if let (Some(ExportFormat::Sqlite), Some(path)) = (args.export, &args.database) {
+        globals.timings.time(Phase::Format, "sqlite", || {
+            export::write_sqlite(sol, &puzzle, &dict.metadata(), path)
+        })?;
+        log::info!(
+            "exported {} answers to {}",
+            sol.entries().len(),
+            path.display()
+        );
+    } else {
+        if let Some(frequencies) = &frequencies {
+            enricher = enricher.with_frequencies(frequencies);
+        }
+        let enriched = globals
+            .timings
+            .time(Phase::Enrich, "enrich answers", || enricher.enrich(sol));
+        let format = args.format.to_possible_value().expect("no skipped formats");
+        globals.timings.time(Phase::Format, format.get_name(), || {
+            output::write_result(
+                &enriched,
+                &puzzle,
+                &dict.metadata(),
+                args.format,
+                &globals.theme,
+                spoiler,
+                &mut out,
+            )
+        })?;
+        // the pangrams alone say little about the difficulty.
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) && !pangrams_only {
+            writeln!(out, "difficulty: {difficulty}")?;
+        }
+    }
 
-    let game = Game::new('C', vec!['A', 'L', 'T', 'E', 'F', 'I']);
+    if let (Some(limit), None) = (args.tricky, args.export) {
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) {
+            let ranked = TrickinessScorer::new(&dict, frequencies.as_ref()).rank(sol);
+            tricky::write_tricky(&ranked, limit, &mut out)?;
+        } else {
+            log::warn!("tricky words are only shown in the text and stems formats");
+        }
+    }
 
-    let solver = GameSolver::<BruteForce>::new(&dict);
-    let sol = timeit!("brute force", solver.solve(&game)?);
+    if args.extensions {
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) {
+            writeln!(
+                out,
+                "\n{header}Answer families (each indented below the answer it extends):{header:#}"
+            )?;
+            for family in sol.extensions() {
+                write!(out, "{family}")?;
+            }
+        } else {
+            log::warn!("answer families are only shown in the text and stems formats");
+        }
+    }
 
-    let solver = GameSolver::<ParallelBruteForce>::new(&dict);
-    timeit!("parallel brute force", solver.solve(&game)?);
+    if let Some(genius) = genius {
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) {
+            write!(out, "\n{header}Genius plan:{header:#} {genius}")?;
+        } else {
+            log::warn!("the Genius plan is only shown in the text and stems formats");
+        }
+    }
 
-    let solver = GameSolver::<LetterMap>::new(&dict);
-    timeit!("letter map", solver.solve(&game)?);
+    if let Some(reach) = reach {
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) {
+            write!(out, "\n{header}Genius reach:{header:#} {reach}")?;
+        } else {
+            log::warn!("the Genius reach is only shown in the text and stems formats");
+        }
+    }
 
-    let solver = GameSolver::<ParallelLetterMap>::new(&dict);
-    timeit!("parallel letter map", solver.solve(&game)?);
+    if let Some(listed) = listed {
+        if matches!(args.format, OutputFormat::Text | OutputFormat::Stems) {
+            writeln!(out, "\n{header}Puzzle file:{header:#} {listed}")?;
+        } else {
+            log::warn!("the puzzle file's answers are only compared in the text and stems formats");
+        }
+    }
 
-    dbg!(sol);
+    if args.stats {
+        for (label, _, stats) in &runs {
+            eprintln!("{label}: {stats}");
+        }
+    }
 
+    if args.watch {
+        drop(out);
+        let answers = sol.entries().into_iter().map(|(word, _)| word.clone());
+        watch_dict(args, globals, &puzzle, watched, scoring, answers.collect())?;
+    }
+
+    Ok(())
+}
+
+/// Write each answer to `puzzle` as a line of JSON as soon as it is found. A
+/// local plain word list is read line by line rather than loaded, so that
+/// memory stays flat however large it is, unless the NYT profile needs all
+/// words at once.
+fn solve_streaming(args: &SolveArgs, puzzle: &PuzzleSpec, globals: &Globals) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        args.strategies.is_empty() && args.export.is_none() && !args.watch,
+        "--format ndjson can't be combined with --strategy, --export or --watch"
+    );
+    if args.tricky.is_some() || args.extensions || args.genius || args.known.is_some() {
+        log::warn!(
+            "tricky words, answer families and the Genius plan and reach need the whole solution and aren't shown with --format ndjson"
+        );
+    }
+    let scoring = Arc::<dyn Scoring>::from(args.scoring);
+    let filter = ResultFilter::from(&args.filter);
+    let mut out = anstream::stdout().lock();
+    let mut write = |words: &mut dyn Iterator<Item = Word>| -> anyhow::Result<SolveStats> {
+        let mut answers = AnswerStream::new(puzzle, words)?.with_scoring(Arc::clone(&scoring));
+        let kept = answers
+            .by_ref()
+            .filter(|(word, score)| filter.matches(word, score))
+            .skip(filter.offset)
+            .take(filter.limit.unwrap_or(usize::MAX));
+        for (word, score) in kept {
+            output::write_ndjson_answer(&word, &score, &mut out)?;
+        }
+        Ok(answers.stats())
+    };
+
+    let stats = match &args.dict.dict {
+        Some(DictLocation::Path(path)) if !args.dict.profile.is_enabled() => {
+            let mut words = WordStream::open(path, parse_options_for(&args.dict, puzzle))
+                .context(Failure::Dictionary)?;
+            let overlay = args.dict.overlay().context(Failure::Dictionary)?;
+            let stats = globals.timings.time(Phase::Solve, "stream answers", || {
+                write(&mut overlay.apply(words.by_ref()))
+            })?;
+            let parsed = words
+                .finish()
+                .with_context(|| format!("failed to read {}", path.display()))
+                .context(Failure::Dictionary)?;
+            log::info!("parsed word list: {parsed}");
+            stats
+        }
+        _ => {
+            let dict = load_dict_for(&args.dict, puzzle, globals)?;
+            globals.timings.time(Phase::Solve, "stream answers", || {
+                write(&mut dict.iter().cloned())
+            })?
+        }
+    };
+    if args.stats {
+        eprintln!("stream: {stats}");
+    }
+    Ok(())
+}
+
+/// The dictionary to solve `puzzle` with, only its candidates with `--mapped`.
+fn load_solve_dict(
+    args: &SolveArgs,
+    puzzle: &PuzzleSpec,
+    globals: &Globals,
+) -> anyhow::Result<Dictionary> {
+    if args.mapped {
+        load_mapped_dict(&args.dict, puzzle, globals)
+    } else {
+        load_dict_for(&args.dict, puzzle, globals)
+    }
+}
+
+/// Re-solve `puzzle` whenever the `--dict` file changes and print the changes
+/// to the `answers` of the previous solve, until interrupted.
+fn watch_dict(
+    args: &SolveArgs,
+    globals: &Globals,
+    puzzle: &PuzzleSpec,
+    strategy: &RegisteredStrategy,
+    scoring: Arc<dyn Scoring>,
+    mut answers: BTreeSet<Word>,
+) -> anyhow::Result<()> {
+    let Some(DictLocation::Path(path)) = &args.dict.dict else {
+        anyhow::bail!("--watch requires a local --dict file");
+    };
+    let filter = ResultFilter::from(&args.filter);
+    let mut watcher = FileWatcher::new(path);
+    log::info!("watching {} for changes", path.display());
+    let mut base = load_solve_dict(args, puzzle, globals)?;
+    let mut rebuilt = false;
+    loop {
+        // the solver borrows its words from `base`, so it is updated in place
+        // while words of `base` are removed and restored, and rebuilt when the
+        // word list gains new words.
+        let mut solver = globals.timings.time(Phase::Index, strategy.name, || {
+            (strategy.build)(&base, globals.threads, Arc::clone(&scoring))
+        })?;
+        if rebuilt {
+            answers = print_changes(
+                &*solver,
+                puzzle,
+                &filter,
+                strategy.name,
+                &answers,
+                globals.timings,
+            )?;
+        }
+        let mut removed: HashSet<&Word> = HashSet::new();
+        let new = loop {
+            watcher.wait();
+            // a half-edited word list shouldn't end the session.
+            let new = match load_solve_dict(args, puzzle, globals) {
+                Ok(dict) => dict,
+                Err(e) => {
+                    log::error!("{e:#}");
+                    continue;
+                }
+            };
+            if new.iter().any(|word| !base.contains(word)) {
+                break new;
+            }
+            let now_removed: HashSet<&Word> =
+                base.iter().filter(|word| !new.contains(word)).collect();
+            let updated = removed
+                .difference(&now_removed)
+                .all(|word| solver.insert(word))
+                && now_removed
+                    .difference(&removed)
+                    .all(|word| solver.remove(word));
+            if !updated {
+                log::info!("{} can't be updated, rebuilding it", strategy.name);
+                break new;
+            }
+            removed = now_removed;
+            answers = print_changes(
+                &*solver,
+                puzzle,
+                &filter,
+                strategy.name,
+                &answers,
+                globals.timings,
+            )?;
+        };
+        drop(solver);
+        base = new;
+        rebuilt = true;
+    }
+}
+
+/// Solve `puzzle` and print the answers gained and lost compared to
+/// `answers`, returning the new answers.
+fn print_changes(
+    solver: &dyn DynSolver,
+    puzzle: &PuzzleSpec,
+    filter: &ResultFilter,
+    label: &str,
+    answers: &BTreeSet<Word>,
+    timings: &Timings,
+) -> anyhow::Result<BTreeSet<Word>> {
+    let sol = timings
+        .time(Phase::Solve, label, || solver.solve(puzzle))?
+        .filtered(filter);
+    let current: BTreeSet<Word> = sol
+        .entries()
+        .into_iter()
+        .map(|(word, _)| word.clone())
+        .collect();
+    for word in current.difference(answers) {
+        println!("+ {word}");
+    }
+    for word in answers.difference(&current) {
+        println!("- {word}");
+    }
+    println!("{} words, {} points", current.len(), sol.total_points());
+    Ok(current)
+}
+
+fn today(args: &TodayArgs, globals: &Globals) -> anyhow::Result<()> {
+    let spoiler = spoiler(args.spoiler, args.format)?;
+    let client = nyt::NytClient::new(globals.http.clone(), args.cookie.clone());
+    let nyt_puzzle = globals
+        .timings
+        .time(Phase::Fetch, "today's puzzle", || client.today())?;
+    log::info!(
+        "today's puzzle is #{} from {}",
+        nyt_puzzle.id,
+        nyt_puzzle.print_date
+    );
+
+    let dict = load_dict(&args.dict, globals)?;
+    let puzzle = PuzzleSpec::from(nyt_puzzle.game()?);
+    let (strategy, mut sol, duration) = if args.pangrams_only {
+        let solver = GameSolver::<PangramIndex>::new(&dict, globals.threads)?;
+        let start = Instant::now();
+        ("pangram-index", solver.solve(&puzzle)?, start.elapsed())
+    } else {
+        let solver = GameSolver::<ParallelLetterMap>::new(&dict, globals.threads)?;
+        let start = Instant::now();
+        (
+            "parallel-letter-map",
+            solver.solve(&puzzle)?,
+            start.elapsed(),
+        )
+    };
+    if let Some(path) = globals.metrics {
+        let metric = SolveMetric::new(
+            "today",
+            strategy,
+            duration,
+            dict.len(),
+            Some(nyt_puzzle.print_date),
+            sol.entries().len(),
+        );
+        metrics::append(path, &metric)?;
+    }
+
+    if args.cookie.is_some() {
+        let found: HashSet<_> = globals
+            .timings
+            .time(Phase::Fetch, "found words", || {
+                client.found_words(&nyt_puzzle)
+            })?
+            .into_iter()
+            .collect();
+        log::info!("you already found {} words", found.len());
+        sol.retain(|word| !found.contains(word));
+    }
+    let sol = sol
+        .with_order(args.filter.order)
+        .filtered(&ResultFilter::from(&args.filter));
+
+    let mut out = anstream::stdout().lock();
+    if args.hints {
+        write!(out, "{}", Hints::new(&sol))?;
+    } else {
+        output::write_result(
+            &(&sol).into(),
+            &puzzle,
+            &dict.metadata(),
+            args.format,
+            &globals.theme,
+            spoiler,
+            &mut out,
+        )?;
+    }
     Ok(())
 }
 
-#[macro_export]
-macro_rules! timeit {
-    // bare expression
-    ($label:expr, $expr:expr) => {{
-        let __t_start = std::time::Instant::now();
-        let __t_val = $expr;
-        let __t_dur = __t_start.elapsed();
-        log::info!(concat!("[timeit] '{}' took {:?}"), $label, __t_dur);
-        __t_val
-    }};
-    // block `{ ... }`
-    ($label:expr, { $($body:tt)* }) => {{
-        let __t_start = std::time::Instant::now();
-        let __t_val = { $($body)* };
-        let __t_dur = __t_start.elapsed();
-        log::info!(concat!("[timeit] '{}' took {:?}"), $label, __t_dur);
-        __t_val
-    }};
+fn update_archive(path: &Path, globals: &Globals) -> anyhow::Result<()> {
+    let mut archive = Archive::open(path)?;
+    let client = nyt::NytClient::new(globals.http.clone(), None);
+    let puzzles = globals
+        .timings
+        .time(Phase::Fetch, "puzzles", || client.puzzles())?;
+    let mut added = 0;
+    for puzzle in &puzzles {
+        if archive.insert(ArchivedPuzzle::try_from(puzzle)?) {
+            added += 1;
+        }
+    }
+    archive.save()?;
+    log::info!(
+        "added {added} of {} fetched puzzles, the archive has {} puzzles",
+        puzzles.len(),
+        archive.len()
+    );
+    Ok(())
+}
+
+fn print_sheet(
+    date: Option<PuzzleDate>,
+    output: Option<&Path>,
+    pdf: bool,
+    archive: &Path,
+    dict: &DictArgs,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    let date = match date {
+        Some(date) => date,
+        None => PuzzleDate::current()?,
+    };
+    let mut archive = Archive::open(archive)?;
+    let client = nyt::NytClient::new(globals.http.clone(), None);
+    let puzzle = PuzzleSpec::from(archive.get_or_fetch(date, &client)?.game());
+    let dict = load_dict(dict, globals)?;
+    let result = GameSolver::<RarestLetterMap>::new(&dict, globals.threads)?.solve(&puzzle)?;
+
+    let game = GameProcessed::try_from(&puzzle)?;
+    let html = sheet::sheet_html(&game, &result, &format!("Spelling Bee {date}"));
+    match output {
+        Some(path) if pdf => html_to_pdf(&html, path),
+        Some(path) => std::fs::write(path, html)
+            .with_context(|| format!("failed to write {}", path.display())),
+        None => Ok(std::io::stdout().write_all(html.as_bytes())?),
+    }
+}
+
+/// Convert `html` into a PDF at `output` with the first available converter.
+fn html_to_pdf(html: &str, output: &Path) -> anyhow::Result<()> {
+    let input = std::env::temp_dir().join(format!("spelling-bee-{}.html", std::process::id()));
+    std::fs::write(&input, html).with_context(|| format!("failed to write {}", input.display()))?;
+    let print_to_pdf = format!("--print-to-pdf={}", output.display());
+    let chromium_args: Vec<&OsStr> = vec![
+        "--headless".as_ref(),
+        "--no-pdf-header-footer".as_ref(),
+        print_to_pdf.as_ref(),
+        input.as_os_str(),
+    ];
+    let converters = [
+        ("wkhtmltopdf", vec![input.as_os_str(), output.as_os_str()]),
+        ("chromium", chromium_args.clone()),
+        ("google-chrome", chromium_args),
+    ];
+    let converted = converters.iter().any(|(program, args)| {
+        match std::process::Command::new(program).args(args).output() {
+            Ok(result) if result.status.success() => true,
+            Ok(result) => {
+                log::debug!("{program} failed with {}", result.status);
+                false
+            }
+            Err(e) => {
+                log::debug!("failed to run {program}: {e}");
+                false
+            }
+        }
+    });
+    // the temporary page is no longer needed either way.
+    let _ = std::fs::remove_file(&input);
+    anyhow::ensure!(
+        converted,
+        "failed to convert the sheet to PDF, install wkhtmltopdf or chromium"
+    );
+    Ok(())
+}
+
+fn fuzz_strategies(
+    iterations: usize,
+    seed: Option<u64>,
+    vary_rules: bool,
+    dict: &DictArgs,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    // varied rules may allow words as short as a single letter.
+    let dict = if vary_rules {
+        let options = ParseOptions {
+            min_length: 1,
+            ..ParseOptions::from(&dict.parse)
+        };
+        load_dict_with(dict, options, globals)?
+    } else {
+        load_dict(dict, globals)?
+    };
+    let seed = match seed {
+        Some(seed) => seed,
+        None => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_nanos() as u64,
+    };
+    log::info!("fuzzing with seed {seed}");
+    let options = FuzzOptions {
+        iterations,
+        seed,
+        vary_rules,
+        threads: globals.threads,
+    };
+    let divergences = globals.timings.time(Phase::Solve, "fuzz strategies", || {
+        fuzz::fuzz(&dict, &StrategyRegistry::default(), &options)
+    })?;
+    for divergence in &divergences {
+        println!("{divergence}");
+    }
+    anyhow::ensure!(
+        divergences.is_empty(),
+        "{} divergences in {iterations} puzzles, rerun with --seed {seed}",
+        divergences.len()
+    );
+    println!("all strategies agreed on {iterations} puzzles");
+    Ok(())
+}
+
+fn compact_dict(
+    input: PathBuf,
+    output: PathBuf,
+    parse: &ParseArgs,
+    timings: &Timings,
+) -> anyhow::Result<()> {
+    let dict = timings
+        .time(Phase::Parse, "load dictionary", || {
+            Dictionary::load(&input, ParseOptions::from(parse))
+        })
+        .context(Failure::Dictionary)?;
+    let bytes = compact::encode(dict.iter().map(String::as_str))?;
+    std::fs::write(&output, &bytes)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+
+    let input_len = std::fs::metadata(&input)?.len();
+    log::info!(
+        "wrote {} ({} bytes, {:.1}x smaller than {})",
+        output.display(),
+        bytes.len(),
+        input_len as f64 / bytes.len() as f64,
+        input.display()
+    );
+    Ok(())
+}
+
+fn sort_dict(
+    input: PathBuf,
+    output: PathBuf,
+    parse: &ParseArgs,
+    timings: &Timings,
+) -> anyhow::Result<()> {
+    let dict = timings
+        .time(Phase::Parse, "load dictionary", || {
+            Dictionary::load(&input, ParseOptions::from(parse))
+        })
+        .context(Failure::Dictionary)?;
+    write_sorted(&dict, &output)
+}
+
+fn write_sorted(dict: &Dictionary, output: &Path) -> anyhow::Result<()> {
+    let mut words: Vec<&str> = dict.iter().map(String::as_str).collect();
+    words.sort_unstable();
+
+    let file =
+        File::create(output).with_context(|| format!("failed to create {}", output.display()))?;
+    let mut out = BufWriter::new(file);
+    for word in &words {
+        writeln!(out, "{word}")?;
+    }
+    out.flush()
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    log::info!("wrote {} words to {}", words.len(), output.display());
+    Ok(())
+}
+
+fn lint_dict(input: &Path, fix: Option<PathBuf>, parse: &ParseArgs) -> anyhow::Result<()> {
+    let bytes =
+        std::fs::read(input).with_context(|| format!("failed to read {}", input.display()))?;
+    let options = ParseOptions::from(parse);
+    let report = lint::lint(&bytes, options.min_length);
+    println!("{report}");
+    match fix {
+        Some(output) => write_sorted(&Dictionary::parse_lossy(&bytes, options), &output),
+        None => {
+            anyhow::ensure!(
+                report.is_clean(),
+                "{} has problems, fix them with `--fix OUTPUT`",
+                input.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Solve the archived puzzles and print how well the answers match the
+/// official ones (see `EvaluationReport`).
+fn verify(
+    dict: &DictArgs,
+    archived: Vec<&ArchivedPuzzle>,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !archived.is_empty(),
+        "no archived puzzles to verify, see `archive update`"
+    );
+    let dict = load_dict(dict, globals)?;
+    let puzzles: Vec<_> = archived
+        .iter()
+        .map(|puzzle| PuzzleSpec::from(puzzle.game()))
+        .collect();
+    let solver = globals
+        .timings
+        .time(Phase::Index, "rarest-letter-map", || {
+            GameSolver::<RarestLetterMap>::new(&dict, globals.threads)
+        })?;
+    let results = globals
+        .timings
+        .time(Phase::Solve, "archived puzzles", || {
+            solver.solve_many(&puzzles)
+        })
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    print!(
+        "{}",
+        EvaluationReport::new(archived.into_iter().zip(&results))
+    );
+    Ok(())
+}
+
+fn player_stats(session_dir: &Path, dict: &DictArgs, globals: &Globals) -> anyhow::Result<()> {
+    let sessions = SessionStore::new(session_dir).load_all()?;
+    anyhow::ensure!(
+        !sessions.is_empty(),
+        "no sessions in {}, play with `repl --session-dir` or `practice --session-dir` first",
+        session_dir.display()
+    );
+    let dict = load_dict(dict, globals)?;
+    let puzzles: Vec<_> = sessions
+        .iter()
+        .map(|session| PuzzleSpec::from(session.game()))
+        .collect();
+    let solver = globals
+        .timings
+        .time(Phase::Index, "rarest-letter-map", || {
+            GameSolver::<RarestLetterMap>::new(&dict, globals.threads)
+        })?;
+    let results = globals
+        .timings
+        .time(Phase::Solve, "played puzzles", || {
+            solver.solve_many(&puzzles)
+        })
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let stats = PlayerStats::new(sessions.iter().zip(&results), PuzzleDate::current()?)?;
+    print!("{stats}");
+    Ok(())
+}
+
+fn dict_coverage(
+    dict: &Dictionary,
+    archived: Vec<&ArchivedPuzzle>,
+    top: usize,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    let puzzles: Vec<_> = archived
+        .iter()
+        .map(|puzzle| PuzzleSpec::from(puzzle.game()))
+        .collect();
+    let solver = globals
+        .timings
+        .time(Phase::Index, "rarest-letter-map", || {
+            GameSolver::<RarestLetterMap>::new(dict, globals.threads)
+        })?;
+    let results = globals
+        .timings
+        .time(Phase::Solve, "archived puzzles", || {
+            solver.solve_many(&puzzles)
+        })
+        .into_iter()
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let coverage = DictionaryCoverage::new(dict, archived.into_iter().zip(&results), top);
+    print!("{coverage}");
+    Ok(())
+}
+
+/// Print the words added to and removed from `old` in `new`, and optionally how
+/// the answers of archived puzzles between two dates change.
+fn diff_dicts(
+    old: &Path,
+    new: &Path,
+    parse: &ParseArgs,
+    archived: Option<(Archive, Option<PuzzleDate>, Option<PuzzleDate>)>,
+    globals: &Globals,
+) -> anyhow::Result<()> {
+    let options = ParseOptions::from(parse);
+    let load = |label, path| {
+        globals
+            .timings
+            .time(Phase::Parse, label, || Dictionary::load(path, options))
+            .context(Failure::Dictionary)
+    };
+    let old = load("load old dictionary", old)?;
+    let new = load("load new dictionary", new)?;
+
+    let mut out = std::io::stdout().lock();
+    let diff = old.diff(&new);
+    for word in &diff.added {
+        writeln!(out, "+{word}")?;
+    }
+    for word in &diff.removed {
+        writeln!(out, "-{word}")?;
+    }
+    writeln!(
+        out,
+        "{} words added, {} words removed",
+        diff.added.len(),
+        diff.removed.len()
+    )?;
+
+    let Some((archive, from, to)) = archived else {
+        return Ok(());
+    };
+    let archived: Vec<_> = archive.range(from, to).collect();
+    let puzzles: Vec<_> = archived
+        .iter()
+        .map(|puzzle| PuzzleSpec::from(puzzle.game()))
+        .collect();
+    let solve = |label, dict| -> anyhow::Result<_> {
+        let solver = globals.timings.time(Phase::Index, label, || {
+            GameSolver::<RarestLetterMap>::new(dict, globals.threads)
+        })?;
+        Ok(globals
+            .timings
+            .time(Phase::Solve, label, || solver.solve_many(&puzzles)))
+    };
+    let old_results = solve("old dictionary", &old)?;
+    let new_results = solve("new dictionary", &new)?;
+
+    writeln!(out)?;
+    let mut changed = 0;
+    for ((puzzle, old_result), new_result) in archived.iter().zip(old_results).zip(new_results) {
+        let old_words: HashSet<_> = old_result?.entries().into_iter().map(|(w, _)| w).collect();
+        let new_words: HashSet<_> = new_result?.entries().into_iter().map(|(w, _)| w).collect();
+        if old_words == new_words {
+            continue;
+        }
+        changed += 1;
+        let mut added: Vec<_> = new_words.difference(&old_words).collect();
+        let mut removed: Vec<_> = old_words.difference(&new_words).collect();
+        added.sort_unstable();
+        removed.sort_unstable();
+        let changes: Vec<String> = added
+            .iter()
+            .map(|word| format!("+{word}"))
+            .chain(removed.iter().map(|word| format!("-{word}")))
+            .collect();
+        let letters: String = puzzle.game().letters().map(char::from).collect();
+        writeln!(
+            out,
+            "{} {letters}: {}",
+            puzzle.print_date,
+            changes.join(" ")
+        )?;
+    }
+    writeln!(
+        out,
+        "answers of {changed} of {} archived puzzles change",
+        archived.len()
+    )?;
+    Ok(())
 }
@@ -0,0 +1,167 @@
+//! Zero-copy access to sorted word lists.
+//!
+//! Loading a [`Dictionary`](crate::dictionary::Dictionary) allocates a string
+//! per word, which dominates start-up time and memory for lists with millions
+//! of words. A [`MappedDictionary`] instead memory-maps a sorted word list with
+//! one word per line (see `solver dict sort`) and binary searches the mapped
+//! bytes directly, so opening it costs a single validation pass. To solve a
+//! puzzle, [`MappedDictionary::candidates`] copies out only the words that
+//! could be answers, which any strategy then solves like the whole list.
+
+use std::{fs::File, path::Path};
+
+use anyhow::Context;
+use memmap2::Mmap;
+
+use crate::{
+    alphabet::LetterSet,
+    dictionary::{Dictionary, ParseOptions, WordList},
+    game::PuzzleSpec,
+};
+
+pub struct MappedDictionary {
+    bytes: Mmap,
+    len: usize,
+}
+
+impl MappedDictionary {
+    /// Map the word list at `path`, which must contain one word of at least
+    /// `options.min_length` uppercase letters of the language's alphabet per
+    /// line in strictly ascending order.
+    pub fn open(path: &Path, options: ParseOptions) -> anyhow::Result<MappedDictionary> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        // SAFETY: we accept garbage results if another process modifies the
+        // file while it is mapped, like any reader of a shared file would.
+        let bytes = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("failed to memory-map {}", path.display()))?;
+
+        let alphabet = options.language.alphabet();
+        let mut len = 0;
+        let mut prev: &[u8] = &[];
+        for (line_number, word) in lines(&bytes).enumerate() {
+            anyhow::ensure!(
+                std::str::from_utf8(word).is_ok_and(|word| {
+                    alphabet.spells(word) && word.chars().count() >= options.min_length
+                }),
+                "line {} of {} is not a word of at least {} letters of {alphabet}",
+                line_number + 1,
+                path.display(),
+                options.min_length
+            );
+            anyhow::ensure!(
+                prev < word,
+                "{} is not sorted: line {} is not after the previous one",
+                path.display(),
+                line_number + 1
+            );
+            prev = word;
+            len += 1;
+        }
+
+        Ok(MappedDictionary { bytes, len })
+    }
+
+    /// All words in alphabetical order, borrowed from the mapping.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        // SAFETY: `open` checked that all words are valid UTF-8.
+        lines(&self.bytes).map(|word| unsafe { std::str::from_utf8_unchecked(word) })
+    }
+
+    /// The words spelled only with the letters of `puzzle`, the only ones that
+    /// can be its answers whatever its rules, as a dictionary to solve it with.
+    /// The other words are skipped without allocating.
+    pub fn candidates(&self, puzzle: &PuzzleSpec) -> Dictionary {
+        let letters: LetterSet = puzzle.game().letters().collect();
+        self.iter()
+            .filter(|word| LetterSet::of_word(word).is_some_and(|set| set.is_subset(letters)))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl WordList for MappedDictionary {
+    fn contains(&self, word: &str) -> bool {
+        let bytes: &[u8] = &self.bytes;
+        let word = word.as_bytes();
+
+        // `lo` always is the start of a line, and `hi` the end of the file or
+        // the start of a line, so `lo..hi` always covers whole lines.
+        let (mut lo, mut hi) = (0, bytes.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let start = bytes[lo..mid]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(lo, |i| lo + i + 1);
+            let end = bytes[start..hi]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(hi, |i| start + i);
+            let line = &bytes[start..end];
+            match trim_line(line).cmp(word) {
+                std::cmp::Ordering::Equal => return true,
+                std::cmp::Ordering::Less => lo = end + 1,
+                std::cmp::Ordering::Greater => hi = start,
+            }
+        }
+        false
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    bytes
+        .split(|&b| b == b'\n')
+        .filter(|_| !bytes.is_empty())
+        .map(trim_line)
+}
+
+// tolerate windows line endings.
+fn trim_line(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+
+    // `words` mapped from a file named after the test.
+    fn open(name: &str, words: &str, options: ParseOptions) -> anyhow::Result<MappedDictionary> {
+        let path =
+            std::env::temp_dir().join(format!("spelling-bee-{}-{name}.txt", std::process::id()));
+        std::fs::write(&path, words)?;
+        let mapped = MappedDictionary::open(&path, options);
+        std::fs::remove_file(&path)?;
+        mapped
+    }
+
+    #[test]
+    fn words_are_spelled_with_the_languages_alphabet() {
+        let spanish = ParseOptions {
+            language: Language::Spanish,
+            ..ParseOptions::default()
+        };
+        let mapped = open("spanish", "AÑEJO\nCAÑA\n", spanish).unwrap();
+        assert_eq!(mapped.len(), 2);
+        assert!(mapped.contains("CAÑA"));
+        assert!(!mapped.contains("CANA"));
+        assert!(open("english", "AÑEJO\nCAÑA\n", ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn words_have_letters_rather_than_bytes_counted() {
+        // 4 letters, but 5 bytes.
+        let spanish = ParseOptions {
+            language: Language::Spanish,
+            min_length: 5,
+            ..ParseOptions::default()
+        };
+        assert!(open("short", "CAÑA\n", spanish).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Opt-in local log of how long solving took, stored as JSON Lines, so that
+//! performance over time is visible without sending anything anywhere.
+
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::Context;
+use jiff::{Timestamp, tz::TimeZone};
+use nyt_spelling_bee_solver::date::PuzzleDate;
+use serde::{Deserialize, Serialize};
+
+/// One solve with one strategy.
+#[derive(Serialize, Deserialize)]
+pub struct SolveMetric {
+    pub timestamp: Timestamp,
+    /// The subcommand, e.g. `solve`.
+    pub command: String,
+    pub strategy: String,
+    pub solve_ms: f64,
+    pub dictionary_words: usize,
+    /// The publication date of NYT puzzles.
+    pub puzzle_date: Option<PuzzleDate>,
+    pub answers: usize,
+}
+
+impl SolveMetric {
+    pub fn new(
+        command: &str,
+        strategy: &str,
+        duration: Duration,
+        dictionary_words: usize,
+        puzzle_date: Option<PuzzleDate>,
+        answers: usize,
+    ) -> SolveMetric {
+        SolveMetric {
+            timestamp: Timestamp::now(),
+            command: command.to_string(),
+            strategy: strategy.to_string(),
+            solve_ms: duration.as_secs_f64() * 1000.0,
+            dictionary_words,
+            puzzle_date,
+            answers,
+        }
+    }
+}
+
+/// Append `metric` to the log at `path`, creating it if needed.
+pub fn append(path: &Path, metric: &SolveMetric) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut line = serde_json::to_string(metric)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[derive(Default)]
+struct Summary {
+    runs: usize,
+    total_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+    dictionary_words: usize,
+}
+
+/// A table of the runs and solve durations of each strategy per month.
+pub fn write_report(path: &Path, out: &mut impl Write) -> anyhow::Result<()> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let time_zone = TimeZone::system();
+
+    // (strategy, month) -> summary.
+    let mut summaries: BTreeMap<(String, String), Summary> = BTreeMap::new();
+    for (line_number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let metric: SolveMetric = serde_json::from_str(&line).with_context(|| {
+            format!(
+                "invalid metric in line {} of {}",
+                line_number + 1,
+                path.display()
+            )
+        })?;
+        let month = metric
+            .timestamp
+            .to_zoned(time_zone.clone())
+            .date()
+            .strftime("%Y-%m")
+            .to_string();
+        let summary = summaries.entry((metric.strategy, month)).or_default();
+        if summary.runs == 0 || metric.solve_ms < summary.min_ms {
+            summary.min_ms = metric.solve_ms;
+        }
+        summary.max_ms = summary.max_ms.max(metric.solve_ms);
+        summary.runs += 1;
+        summary.total_ms += metric.solve_ms;
+        summary.dictionary_words = summary.dictionary_words.max(metric.dictionary_words);
+    }
+
+    let width = summaries
+        .keys()
+        .map(|(strategy, _)| strategy.len())
+        .chain(["strategy".len()])
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        out,
+        "{:<width$}  {:<7}  {:>5}  {:>10} {:>10} {:>10}  {:>10}",
+        "strategy", "month", "runs", "mean ms", "min ms", "max ms", "max words"
+    )?;
+    for ((strategy, month), summary) in &summaries {
+        writeln!(
+            out,
+            "{strategy:<width$}  {month:<7}  {:>5}  {:>10.3} {:>10.3} {:>10.3}  {:>10}",
+            summary.runs,
+            summary.total_ms / summary.runs as f64,
+            summary.min_ms,
+            summary.max_ms,
+            summary.dictionary_words
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,261 @@
+//! Word roots, to group answers like ACT, ACTED and ACTING that are usually
+//! found together.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::game::{GameResult, Points, Word};
+
+/// The stem of `word` in lowercase according to the Porter stemming
+/// algorithm, e.g. `act` for ACTING. Words sharing a stem usually share their
+/// root, although the stem itself need not be a word.
+pub fn stem(word: &str) -> String {
+    let mut stemmer = Stemmer {
+        word: word.to_ascii_lowercase().into_bytes(),
+    };
+    // the algorithm assumes stems of at least one syllable.
+    if stemmer.word.len() > 2 {
+        stemmer.step1a();
+        stemmer.step1b();
+        stemmer.step1c();
+        stemmer.step2();
+        stemmer.step3();
+        stemmer.step4();
+        stemmer.step5();
+    }
+    // only ASCII letters were lowercased, the rest is unchanged.
+    String::from_utf8(stemmer.word).expect("stem is not UTF-8")
+}
+
+struct Stemmer {
+    word: Vec<u8>,
+}
+
+impl Stemmer {
+    // a Y is a consonant at the start and after a vowel.
+    fn is_consonant(&self, i: usize) -> bool {
+        match self.word[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    /// The number of vowel-consonant sequences in the first `len` letters.
+    fn measure(&self, len: usize) -> usize {
+        let mut measure = 0;
+        let mut previous_vowel = false;
+        for i in 0..len {
+            let consonant = self.is_consonant(i);
+            if consonant && previous_vowel {
+                measure += 1;
+            }
+            previous_vowel = !consonant;
+        }
+        measure
+    }
+
+    fn has_vowel(&self, len: usize) -> bool {
+        (0..len).any(|i| !self.is_consonant(i))
+    }
+
+    fn ends_with_double_consonant(&self, len: usize) -> bool {
+        len >= 2 && self.word[len - 1] == self.word[len - 2] && self.is_consonant(len - 1)
+    }
+
+    // consonant, vowel, consonant, where the last isn't W, X or Y, like HOP.
+    fn ends_with_cvc(&self, len: usize) -> bool {
+        len >= 3
+            && self.is_consonant(len - 3)
+            && !self.is_consonant(len - 2)
+            && self.is_consonant(len - 1)
+            && !matches!(self.word[len - 1], b'w' | b'x' | b'y')
+    }
+
+    /// The length of the stem left by removing `suffix`, if the word ends with it.
+    fn stem_len(&self, suffix: &str) -> Option<usize> {
+        self.word
+            .ends_with(suffix.as_bytes())
+            .then(|| self.word.len() - suffix.len())
+    }
+
+    fn replace(&mut self, stem_len: usize, replacement: &str) {
+        self.word.truncate(stem_len);
+        self.word.extend_from_slice(replacement.as_bytes());
+    }
+
+    /// Replace the first of `rules` whose suffix the word ends with, if the
+    /// remaining stem's measure is above `min_measure`.
+    fn replace_suffix(&mut self, rules: &[(&str, &str)], min_measure: usize) {
+        if let Some((stem_len, replacement)) = rules
+            .iter()
+            .find_map(|(suffix, replacement)| Some((self.stem_len(suffix)?, replacement)))
+            && self.measure(stem_len) > min_measure
+        {
+            self.replace(stem_len, replacement);
+        }
+    }
+
+    // plurals.
+    fn step1a(&mut self) {
+        if let Some(len) = self.stem_len("sses").or_else(|| self.stem_len("ies")) {
+            self.replace(len, if self.word[len] == b's' { "ss" } else { "i" });
+        } else if !self.word.ends_with(b"ss") && self.word.ends_with(b"s") {
+            self.word.pop();
+        }
+    }
+
+    // past tenses and gerunds.
+    fn step1b(&mut self) {
+        if let Some(len) = self.stem_len("eed") {
+            if self.measure(len) > 0 {
+                self.word.pop();
+            }
+            return;
+        }
+        let Some(len) = ["ed", "ing"]
+            .into_iter()
+            .find_map(|suffix| self.stem_len(suffix))
+            .filter(|&len| self.has_vowel(len))
+        else {
+            return;
+        };
+        self.word.truncate(len);
+        if self.word.ends_with(b"at") || self.word.ends_with(b"bl") || self.word.ends_with(b"iz") {
+            self.word.push(b'e');
+        } else if self.ends_with_double_consonant(len)
+            && !matches!(self.word[len - 1], b'l' | b's' | b'z')
+        {
+            self.word.pop();
+        } else if self.measure(len) == 1 && self.ends_with_cvc(len) {
+            self.word.push(b'e');
+        }
+    }
+
+    fn step1c(&mut self) {
+        if let Some(len) = self.stem_len("y")
+            && self.has_vowel(len)
+        {
+            self.replace(len, "i");
+        }
+    }
+
+    // double suffixes.
+    fn step2(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("ational", "ate"),
+            ("tional", "tion"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("izer", "ize"),
+            ("bli", "ble"),
+            ("alli", "al"),
+            ("entli", "ent"),
+            ("eli", "e"),
+            ("ousli", "ous"),
+            ("ization", "ize"),
+            ("ation", "ate"),
+            ("ator", "ate"),
+            ("alism", "al"),
+            ("iveness", "ive"),
+            ("fulness", "ful"),
+            ("ousness", "ous"),
+            ("aliti", "al"),
+            ("iviti", "ive"),
+            ("biliti", "ble"),
+            ("logi", "log"),
+        ];
+        self.replace_suffix(RULES, 0);
+    }
+
+    fn step3(&mut self) {
+        const RULES: &[(&str, &str)] = &[
+            ("icate", "ic"),
+            ("ative", ""),
+            ("alize", "al"),
+            ("iciti", "ic"),
+            ("ical", "ic"),
+            ("ful", ""),
+            ("ness", ""),
+        ];
+        self.replace_suffix(RULES, 0);
+    }
+
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+            "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+        // the longest suffix the word ends with.
+        let Some((suffix, len)) = SUFFIXES
+            .iter()
+            .filter_map(|suffix| Some((*suffix, self.stem_len(suffix)?)))
+            .min_by_key(|(_, len)| *len)
+        else {
+            return;
+        };
+        let allowed = suffix != "ion" || (len > 0 && matches!(self.word[len - 1], b's' | b't'));
+        if allowed && self.measure(len) > 1 {
+            self.word.truncate(len);
+        }
+    }
+
+    // a final E or double L.
+    fn step5(&mut self) {
+        if let Some(len) = self.stem_len("e") {
+            let measure = self.measure(len);
+            if measure > 1 || (measure == 1 && !self.ends_with_cvc(len)) {
+                self.word.pop();
+            }
+        }
+        let len = self.word.len();
+        if self.word.ends_with(b"ll") && self.measure(len) > 1 {
+            self.word.pop();
+        }
+    }
+}
+
+/// Answers sharing a stem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StemGroup<'a> {
+    pub stem: String,
+    /// Shortest first, then alphabetically.
+    pub words: Vec<&'a Word>,
+    pub points: Points,
+}
+
+impl<'a> GameResult<'a> {
+    /// Group the answers by their [`stem`], sorted by the first answer of each
+    /// group. Answers without any relatives form groups of their own.
+    pub fn stem_groups(&self) -> Vec<StemGroup<'a>> {
+        let mut groups: BTreeMap<String, StemGroup<'a>> = BTreeMap::new();
+        for (word, score) in self.entries() {
+            let stem = stem(word);
+            let group = groups.entry(stem.clone()).or_insert_with(|| StemGroup {
+                stem,
+                words: Vec::new(),
+                points: 0,
+            });
+            group.words.push(word);
+            group.points += score.points;
+        }
+        let mut groups: Vec<_> = groups.into_values().collect();
+        for group in &mut groups {
+            group
+                .words
+                .sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        }
+        groups.sort_by(|a, b| a.words[0].cmp(b.words[0]));
+        groups
+    }
+}
+
+/// e.g. `ACT ACTS ACTED ACTING (13 points)`.
+impl fmt::Display for StemGroup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for word in &self.words {
+            write!(f, "{word} ")?;
+        }
+        let plural = if self.points == 1 { "" } else { "s" };
+        write!(f, "({} point{plural})", self.points)
+    }
+}
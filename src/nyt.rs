@@ -0,0 +1,143 @@
+//! Client for the (undocumented) NYT Spelling Bee endpoints.
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{
+    date::PuzzleDate,
+    fetch::{HttpFetcher, HttpRequest},
+    game::{Game, PuzzleMetadata, Word},
+};
+
+/// The page embedding the current puzzles, see [`NytClient::puzzles`].
+pub const PUZZLE_PAGE_URL: &str = "https://www.nytimes.com/puzzles/spelling-bee";
+/// The state of a logged in account's games, see [`NytClient::found_words`].
+pub const GAME_STATE_URL: &str = "https://www.nytimes.com/svc/games/state/spelling_bee/latests";
+
+/// A puzzle as embedded in the puzzle page's `window.gameData`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NytPuzzle {
+    pub id: u64,
+    pub print_date: PuzzleDate,
+    #[serde(default)]
+    pub editor: Option<String>,
+    /// e.g. `Thursday`.
+    #[serde(default)]
+    display_weekday: Option<String>,
+    center_letter: String,
+    outer_letters: Vec<String>,
+    /// The official answers, in lowercase.
+    #[serde(default)]
+    pub answers: Vec<Word>,
+    #[serde(default)]
+    pub pangrams: Vec<Word>,
+}
+
+impl NytPuzzle {
+    pub fn game(&self) -> anyhow::Result<Game> {
+        let game = Game::parse(&self.center_letter, &self.outer_letters.concat())?;
+        Ok(game.with_metadata(self.metadata()))
+    }
+
+    pub fn metadata(&self) -> PuzzleMetadata {
+        let mut metadata = PuzzleMetadata::new(self.print_date, self.id, self.editor.clone());
+        if let Some(weekday) = &self.display_weekday {
+            metadata.weekday = weekday.clone();
+        }
+        metadata
+    }
+}
+
+#[derive(Deserialize)]
+struct GameData {
+    today: NytPuzzle,
+    #[serde(default)]
+    yesterday: Option<NytPuzzle>,
+}
+
+#[derive(Deserialize)]
+struct GameStates {
+    states: Vec<GameState>,
+}
+
+#[derive(Deserialize)]
+struct GameState {
+    game_data: GameStateData,
+}
+
+#[derive(Deserialize)]
+struct GameStateData {
+    answers: Vec<Word>,
+}
+
+pub struct NytClient<F> {
+    http: F,
+    /// Value of the `NYT-S` session cookie of a logged in account.
+    session_cookie: Option<String>,
+}
+
+impl<F: HttpFetcher> NytClient<F> {
+    pub fn new(http: F, session_cookie: Option<String>) -> Self {
+        NytClient {
+            http,
+            session_cookie,
+        }
+    }
+
+    pub fn today(&self) -> anyhow::Result<NytPuzzle> {
+        Ok(self.game_data()?.today)
+    }
+
+    /// All puzzles on the puzzle page, which are today's and usually yesterday's.
+    pub fn puzzles(&self) -> anyhow::Result<Vec<NytPuzzle>> {
+        let game_data = self.game_data()?;
+        Ok(std::iter::once(game_data.today)
+            .chain(game_data.yesterday)
+            .collect())
+    }
+
+    fn game_data(&self) -> anyhow::Result<GameData> {
+        let page = self.http.get_text(PUZZLE_PAGE_URL)?;
+
+        let (_, game_data) = page
+            .split_once("window.gameData = ")
+            .context("puzzle page does not contain the game data, has the page layout changed?")?;
+        // the JSON object is followed by the rest of the script, so only parse the first value.
+        let game_data: GameData = serde_json::Deserializer::from_str(game_data)
+            .into_iter()
+            .next()
+            .context("puzzle page contains empty game data")?
+            .context("failed to parse the puzzle page's game data")?;
+
+        Ok(game_data)
+    }
+
+    /// The words the logged in account has already found for the puzzle.
+    pub fn found_words(&self, puzzle: &NytPuzzle) -> anyhow::Result<Vec<Word>> {
+        let cookie = self
+            .session_cookie
+            .as_ref()
+            .context("fetching found words requires an NYT-S session cookie")?;
+
+        let request = HttpRequest::get(GAME_STATE_URL)
+            .query("puzzle_ids", puzzle.id)
+            .header("Cookie", format!("NYT-S={cookie}"));
+        let response = self.http.fetch(&request)?;
+        // unauthorized or forbidden.
+        if matches!(response.status, 401 | 403) {
+            anyhow::bail!("NYT rejected the session cookie, it may have expired");
+        }
+        let body = response.error_for_status(GAME_STATE_URL)?.body;
+        let states: GameStates =
+            serde_json::from_str(&body).context("failed to parse game state")?;
+
+        // no state means the puzzle hasn't been started yet.
+        Ok(states
+            .states
+            .into_iter()
+            .flat_map(|state| state.game_data.answers)
+            .map(|word| word.to_uppercase())
+            .collect())
+    }
+}
@@ -0,0 +1,598 @@
+use std::{borrow::Cow, collections::BTreeMap, io::Write};
+
+use anstyle::Style;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use nyt_spelling_bee_solver::{
+    dictionary::DictionaryMetadata,
+    enrich::{EnrichedFields, EnrichedResult, Enrichment},
+    game::{
+        AnswerOrder, GameProcessed, GameResult, GuessingError, Letter, PuzzleMetadata, PuzzleSpec,
+        Score, Word,
+    },
+    hints::Hints,
+    render,
+    scoring::PointsBreakdown,
+    summary::ResultSummary,
+};
+
+use crate::style::Theme;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    /// One JSON object per line for each answer, without the summary. `solve`
+    /// writes each answer as soon as it is found, in no particular order.
+    Ndjson,
+    Csv,
+    Markdown,
+    /// Like `text`, with the answers sharing a stem (ACT, ACTED, ACTING) on one line.
+    Stems,
+    /// Only the hive of letters, as an SVG image.
+    Svg,
+    /// Only the number of answers by starting letter and length, as in the hints grid.
+    Grid,
+    /// Hints and answers as posted in the Spelling Bee forum.
+    Forum,
+    /// Like `forum`, with every answer hidden behind a Discord spoiler.
+    ForumDiscord,
+    /// Like `forum`, with every answer hidden behind a Reddit spoiler.
+    ForumReddit,
+}
+
+/// How `--spoiler` obfuscates the answers, leaving everything else readable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Spoiler {
+    /// ROT13, e.g. SNPRG for FACET, to decode when stuck.
+    Rot13,
+    /// Concealed, revealed by selecting them in terminals that support it, or
+    /// replaced by one `*` per letter where the output isn't styled.
+    Hidden,
+    /// One `*` per letter, `Hidden` for unstyled output.
+    #[value(skip)]
+    Masked,
+}
+
+impl Spoiler {
+    /// The spoiler for output that is `styled` or not, where concealing
+    /// wouldn't hide anything.
+    pub fn for_output(self, styled: bool) -> Spoiler {
+        match self {
+            Spoiler::Hidden if !styled => Spoiler::Masked,
+            spoiler => spoiler,
+        }
+    }
+
+    // `word` as written, styled with `style`.
+    fn hide(self, word: &str, style: Style) -> String {
+        let (style, word) = match self {
+            Spoiler::Rot13 => {
+                let rotated = word
+                    .chars()
+                    .map(|c| match c {
+                        'A'..='Z' => char::from((c as u8 - b'A' + 13) % 26 + b'A'),
+                        c => c,
+                    })
+                    .collect();
+                (style, rotated)
+            }
+            Spoiler::Hidden => (style.hidden(), word.to_string()),
+            Spoiler::Masked => (style, "*".repeat(word.chars().count())),
+        };
+        format!("{style}{word}{style:#}")
+    }
+}
+
+// an answer as written, hidden by `spoiler` if any.
+fn answer(word: &str, style: Style, spoiler: Option<Spoiler>) -> String {
+    match spoiler {
+        Some(spoiler) => spoiler.hide(word, style),
+        None => format!("{style}{word}{style:#}"),
+    }
+}
+
+/// Markup hiding a spoiler until it is clicked, as (opening, closing) fence.
+const DISCORD_SPOILER: (&str, &str) = ("||", "||");
+const REDDIT_SPOILER: (&str, &str) = (">!", "!<");
+
+#[derive(Serialize)]
+struct JsonAnswer<'a> {
+    word: &'a str,
+    points: usize,
+    breakdown: PointsBreakdown,
+    pangram: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    definition: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obscurity: Option<f64>,
+    /// How many archived puzzles had the word as an official answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    official_count: Option<usize>,
+}
+
+impl<'a> JsonAnswer<'a> {
+    fn new(word: &'a str, score: Score, enrichment: &'a Enrichment) -> Self {
+        JsonAnswer {
+            word,
+            points: score.points,
+            breakdown: score.breakdown,
+            pangram: score.is_pangram,
+            definition: enrichment.definition.as_deref(),
+            obscurity: enrichment.obscurity,
+            official_count: enrichment.official_count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    /// Where the puzzle was published, for official puzzles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    puzzle: Option<&'a PuzzleMetadata>,
+    answers: Vec<JsonAnswer<'a>>,
+    total_points: usize,
+    bingo: bool,
+    starting_letters: BTreeMap<Letter, usize>,
+    /// The order of `answers`.
+    order: AnswerOrder,
+    summary: ResultSummary<'a>,
+    /// The word list the answers are from.
+    dictionary: &'a DictionaryMetadata,
+}
+
+/// Write the result in `format`, including the enrichments of each answer
+/// (except in the stems, SVG, grid and forum formats), where the puzzle was
+/// published, if it is an official one, and the result's summary (except in
+/// the NDJSON and SVG formats, which have no room for it, and the forum
+/// formats, where the longest answer would be a spoiler). The text formats are styled
+/// with `theme`. JSON also includes the provenance of the `dictionary`.
+/// The answers are hidden by `spoiler` in the text, stems, grid and Markdown
+/// formats.
+pub fn write_result(
+    enriched: &EnrichedResult,
+    puzzle: &PuzzleSpec,
+    dictionary: &DictionaryMetadata,
+    format: OutputFormat,
+    theme: &Theme,
+    spoiler: Option<Spoiler>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let game = GameProcessed::try_from(puzzle)?;
+    let result = enriched.result;
+    let metadata = result.metadata();
+    match format {
+        OutputFormat::Text => {
+            write_metadata_line(metadata, theme, out)?;
+            writeln!(out, "{}\n", render::hive_ascii(&game))?;
+            write_text(enriched, theme, spoiler, out)
+        }
+        OutputFormat::Stems => {
+            write_metadata_line(metadata, theme, out)?;
+            writeln!(out, "{}\n", render::hive_ascii(&game))?;
+            write_stems(result, theme, spoiler, out)
+        }
+        OutputFormat::Json => write_json(enriched, dictionary, out),
+        OutputFormat::Ndjson => {
+            enriched
+                .entries()
+                .into_iter()
+                .try_for_each(|(word, score, enrichment)| {
+                    write_json_line(&JsonAnswer::new(word, score, enrichment), out)
+                })
+        }
+        OutputFormat::Csv => write_csv(enriched, out),
+        OutputFormat::Markdown => write_markdown(enriched, spoiler, out),
+        OutputFormat::Svg => Ok(write!(out, "{}", render::hive_svg(&game))?),
+        OutputFormat::Grid => {
+            write_metadata_line(metadata, theme, out)?;
+            write!(out, "{}", result.grid())?;
+            write_summary(result, theme, spoiler, out)
+        }
+        OutputFormat::Forum => write_forum(result, &game, None, out),
+        OutputFormat::ForumDiscord => write_forum(result, &game, Some(DISCORD_SPOILER), out),
+        OutputFormat::ForumReddit => write_forum(result, &game, Some(REDDIT_SPOILER), out),
+    }
+}
+
+// e.g. `Spelling Bee Thursday 2024-06-06 (#21934), edited by Sam Ezersky`.
+fn write_metadata_line(
+    metadata: Option<&PuzzleMetadata>,
+    theme: &Theme,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    if let Some(metadata) = metadata {
+        let header = theme.header;
+        writeln!(out, "{header}Spelling Bee {metadata}{header:#}\n")?;
+    }
+    Ok(())
+}
+
+fn write_text(
+    enriched: &EnrichedResult,
+    theme: &Theme,
+    spoiler: Option<Spoiler>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let result = enriched.result;
+    let entries = enriched.entries();
+    let width = entries
+        .iter()
+        .map(|(word, _, _)| word.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (word, score, enrichment) in &entries {
+        let pangram = if score.is_pangram { " (pangram)" } else { "" };
+        let official = match enrichment.official_count {
+            Some(0) => " (never official)",
+            Some(count) => &format!(" (official {count}x)"),
+            None => "",
+        };
+        let obscurity = match enrichment.obscurity {
+            Some(obscurity) => &format!(" (obscurity {obscurity:.2})"),
+            None => "",
+        };
+        let definition = match &enrichment.definition {
+            Some(definition) => &format!(": {definition}"),
+            None => "",
+        };
+        let style = if score.is_pangram {
+            theme.pangram
+        } else {
+            Style::new()
+        };
+        // padded separately, styles would count as width.
+        writeln!(
+            out,
+            "{}{:padding$} {:>2}{pangram}{official}{obscurity}{definition}",
+            answer(word, style, spoiler),
+            "",
+            score.points,
+            padding = width - word.chars().count()
+        )?;
+    }
+    let bingo = if result.has_bingo() { ", bingo" } else { "" };
+    let header = theme.header;
+    writeln!(
+        out,
+        "{header}{} words, {} points{bingo}{header:#}",
+        entries.len(),
+        result.total_points()
+    )?;
+    writeln!(out, "starting letters: {}", starting_letters(result))?;
+    write_summary(result, theme, spoiler, out)
+}
+
+fn write_summary(
+    result: &GameResult,
+    theme: &Theme,
+    spoiler: Option<Spoiler>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let header = theme.header;
+    writeln!(out, "\n{header}Summary:{header:#}")?;
+    write!(out, "{}", summary(result, spoiler))?;
+    Ok(())
+}
+
+// the summary of `result`, with its longest answers hidden by `spoiler`.
+fn summary(result: &GameResult, spoiler: Option<Spoiler>) -> String {
+    let mut summary = result.summary();
+    let longest: Vec<Word> = summary
+        .longest
+        .iter()
+        .map(|word| answer(word, Style::new(), spoiler))
+        .collect();
+    summary.longest = longest.iter().collect();
+    summary.to_string()
+}
+
+fn write_stems(
+    result: &GameResult,
+    theme: &Theme,
+    spoiler: Option<Spoiler>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let groups = result.stem_groups();
+    let pangrams: Vec<&Word> = result
+        .entries()
+        .into_iter()
+        .filter(|(_, score)| score.is_pangram)
+        .map(|(word, _)| word)
+        .collect();
+    for group in &groups {
+        // like the group's `Display`, with the pangrams styled.
+        for word in &group.words {
+            let style = if pangrams.contains(word) {
+                theme.pangram
+            } else {
+                Style::new()
+            };
+            write!(out, "{} ", answer(word, style, spoiler))?;
+        }
+        let plural = if group.points == 1 { "" } else { "s" };
+        writeln!(out, "({} point{plural})", group.points)?;
+    }
+    let header = theme.header;
+    writeln!(
+        out,
+        "{header}{} words in {} groups, {} points{header:#}",
+        result.entries().len(),
+        groups.len(),
+        result.total_points()
+    )?;
+    writeln!(out, "starting letters: {}", starting_letters(result))?;
+    write_summary(result, theme, spoiler, out)
+}
+
+fn write_json(
+    enriched: &EnrichedResult,
+    dictionary: &DictionaryMetadata,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let result = enriched.result;
+    let answers = enriched
+        .entries()
+        .into_iter()
+        .map(|(word, score, enrichment)| JsonAnswer::new(word, score, enrichment))
+        .collect();
+    let json = JsonResult {
+        puzzle: result.metadata(),
+        answers,
+        total_points: result.total_points(),
+        bingo: result.has_bingo(),
+        starting_letters: result.starting_letter_counts(),
+        order: result.order(),
+        summary: result.summary(),
+        dictionary,
+    };
+    serde_json::to_writer_pretty(&mut *out, &json)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// A line of `--format ndjson` for an answer without enrichments.
+pub fn write_ndjson_answer(word: &str, score: &Score, out: &mut impl Write) -> anyhow::Result<()> {
+    write_json_line(&JsonAnswer::new(word, *score, &Enrichment::default()), out)
+}
+
+fn write_json_line(answer: &JsonAnswer, out: &mut impl Write) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut *out, answer)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+// the (CSV name, Markdown title, value) of the enabled enrichments, with empty
+// values for words without a definition.
+fn enrichment_columns(
+    fields: EnrichedFields,
+    enrichment: &Enrichment,
+) -> Vec<(&'static str, &'static str, String)> {
+    let mut columns = Vec::new();
+    if fields.definition {
+        let definition = enrichment.definition.clone().unwrap_or_default();
+        columns.push(("definition", "Definition", definition));
+    }
+    if fields.obscurity {
+        let obscurity = enrichment.obscurity.unwrap_or_default();
+        columns.push(("obscurity", "Obscurity", format!("{obscurity:.2}")));
+    }
+    if fields.official_count {
+        let count = enrichment.official_count.unwrap_or_default();
+        columns.push(("official_count", "Official", count.to_string()));
+    }
+    columns
+}
+
+// a row per answer, followed by the totals, bingo, starting letters and
+// summary as lines commented out with `#`, which CSV readers can skip.
+fn write_csv(enriched: &EnrichedResult, out: &mut impl Write) -> anyhow::Result<()> {
+    let result = enriched.result;
+    let empty = Enrichment::default();
+    // official puzzles repeat where they were published in every row.
+    let metadata = enriched.result.metadata().map(|metadata| {
+        let editor = metadata.editor.as_deref().unwrap_or_default();
+        format!(",{},{},{}", metadata.date, metadata.id, csv_field(editor))
+    });
+    write!(out, "word,points,pangram")?;
+    for (column, _, _) in enrichment_columns(enriched.fields, &empty) {
+        write!(out, ",{column}")?;
+    }
+    if metadata.is_some() {
+        write!(out, ",date,puzzle_id,editor")?;
+    }
+    writeln!(out)?;
+    for (word, score, enrichment) in enriched.entries() {
+        write!(out, "{word},{},{}", score.points, score.is_pangram)?;
+        for (_, _, value) in enrichment_columns(enriched.fields, enrichment) {
+            write!(out, ",{}", csv_field(&value))?;
+        }
+        writeln!(out, "{}", metadata.as_deref().unwrap_or_default())?;
+    }
+    let bingo = if result.has_bingo() { ", bingo" } else { "" };
+    writeln!(
+        out,
+        "# {} words, {} points{bingo}",
+        result.entries().len(),
+        result.total_points()
+    )?;
+    writeln!(out, "# starting letters: {}", starting_letters(result))?;
+    for line in result.summary().to_string().lines() {
+        writeln!(out, "# {line}")?;
+    }
+    Ok(())
+}
+
+// words only ever consist of letters, but definitions may need quoting.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+fn write_markdown(
+    enriched: &EnrichedResult,
+    spoiler: Option<Spoiler>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let result = enriched.result;
+    let empty = Enrichment::default();
+    let columns = enrichment_columns(enriched.fields, &empty);
+    if let Some(metadata) = result.metadata() {
+        writeln!(out, "## Spelling Bee {metadata}\n")?;
+    }
+    write!(out, "| Word | Points | Pangram |")?;
+    for (_, title, _) in &columns {
+        write!(out, " {title} |")?;
+    }
+    write!(out, "\n| --- | ---: | :---: |")?;
+    for (name, _, _) in &columns {
+        let align = if *name == "definition" { "---" } else { "---:" };
+        write!(out, " {align} |")?;
+    }
+    writeln!(out)?;
+    for (word, score, enrichment) in enriched.entries() {
+        let pangram = if score.is_pangram { "✓" } else { "" };
+        let word = answer(word, Style::new(), spoiler);
+        write!(out, "| {word} | {} | {pangram} |", score.points)?;
+        for (_, _, value) in enrichment_columns(enriched.fields, enrichment) {
+            write!(out, " {} |", value.replace('|', "\\|"))?;
+        }
+        writeln!(out)?;
+    }
+    writeln!(
+        out,
+        "| **Total** | **{}** | |{}",
+        result.total_points(),
+        " |".repeat(columns.len())
+    )?;
+    let bingo = if result.has_bingo() { "yes" } else { "no" };
+    writeln!(
+        out,
+        "\nBingo: {bingo}, starting letters: {}",
+        starting_letters(result)
+    )?;
+    writeln!(
+        out,
+        "\n### Summary\n\n```text\n{}```",
+        summary(result, spoiler)
+    )?;
+    Ok(())
+}
+
+// e.g. `A 4, C 6, E 0`.
+fn starting_letters(result: &GameResult) -> String {
+    let counts: Vec<String> = result
+        .starting_letter_counts()
+        .iter()
+        .map(|(letter, count)| format!("{letter} {count}"))
+        .collect();
+    counts.join(", ")
+}
+
+/// Forum conventions: the letters with the center in bold, the summary, the
+/// hints grid and two letter list, and the answers with pangrams in bold.
+fn write_forum(
+    result: &GameResult,
+    game: &GameProcessed,
+    spoiler: Option<(&str, &str)>,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let outer: Vec<String> = game
+        .non_center_letters()
+        .iter()
+        .map(Letter::to_string)
+        .collect();
+    if let Some(metadata) = game.metadata() {
+        writeln!(out, "Spelling Bee {metadata}\n")?;
+    }
+    writeln!(out, "**{}** {}\n", game.center_letter(), outer.join(" "))?;
+
+    let hints = Hints::new(result);
+    let mut summary = String::new();
+    hints.write_summary(&mut summary)?;
+    writeln!(out, "{summary}")?;
+
+    // a code block keeps the grid aligned.
+    let mut block = String::new();
+    hints.write_grid(&mut block)?;
+    block.push('\n');
+    hints.write_two_letter_list(&mut block)?;
+    writeln!(out, "```\n{block}```\n")?;
+
+    let (open, close) = spoiler.unwrap_or(("", ""));
+    let answers: Vec<String> = result
+        .entries()
+        .iter()
+        .map(|(word, score)| {
+            if score.is_pangram {
+                format!("{open}**{word}**{close}")
+            } else {
+                format!("{open}{word}{close}")
+            }
+        })
+        .collect();
+    writeln!(out, "{}", answers.join(" "))?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonCheck<'a> {
+    word: &'a str,
+    score: Option<&'a Score>,
+    error: Option<&'a GuessingError>,
+    /// The error's message for display, e.g. "word does not use the center letter".
+    message: Option<String>,
+    suggestions: &'a [&'a Word],
+}
+
+pub fn write_check_json(
+    word: &str,
+    check: &Result<Score, GuessingError>,
+    suggestions: &[&Word],
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let word = word.trim().to_uppercase();
+    let json = JsonCheck {
+        word: &word,
+        score: check.as_ref().ok(),
+        error: check.as_ref().err(),
+        message: check.as_ref().err().map(ToString::to_string),
+        suggestions,
+    };
+    serde_json::to_writer_pretty(&mut *out, &json)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Describe the outcome of checking a single guessed word.
+pub fn write_check(
+    word: &str,
+    check: &Result<Score, GuessingError>,
+    suggestions: &[&Word],
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let word = word.trim().to_uppercase();
+    match check {
+        Ok(score) if score.is_pangram => writeln!(
+            out,
+            "{word} is a valid pangram worth {} points ({})",
+            score.points, score.breakdown
+        )?,
+        Ok(score) => writeln!(
+            out,
+            "{word} is valid and worth {} points ({})",
+            score.points, score.breakdown
+        )?,
+        Err(e) => writeln!(out, "{word} is invalid: {e}")?,
+    }
+    if !suggestions.is_empty() {
+        let suggestions: Vec<&str> = suggestions.iter().map(|word| word.as_str()).collect();
+        writeln!(out, "did you mean {}?", suggestions.join(", "))?;
+    }
+    Ok(())
+}
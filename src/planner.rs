@@ -0,0 +1,105 @@
+//! Whether Genius is reachable with only the answers a player is confident
+//! they know, and which other answers would close the gap otherwise.
+
+use std::fmt;
+
+use crate::{
+    frequency::WordFrequencies,
+    game::{GameResult, Points, Score, Word},
+    genius::genius_threshold,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeniusReach<'a> {
+    /// The known answers, sorted alphabetically.
+    pub known: Vec<(&'a Word, Score)>,
+    /// The total of `known`.
+    pub points: Points,
+    pub threshold: Points,
+    /// The fewest other answers worth at least the shortfall, empty if Genius
+    /// is reachable.
+    pub to_learn: Vec<(&'a Word, Score)>,
+}
+
+impl GeniusReach<'_> {
+    pub fn is_reachable(&self) -> bool {
+        self.points >= self.threshold
+    }
+
+    /// The points missing to Genius with only the known answers.
+    pub fn shortfall(&self) -> Points {
+        self.threshold.saturating_sub(self.points)
+    }
+}
+
+impl<'a> GameResult<'a> {
+    /// Whether the answers for which `knows` holds reach the Genius threshold.
+    /// Otherwise the highest scoring other answers make up the shortfall, the
+    /// most common ones according to `frequencies` among equal points.
+    pub fn genius_reach(
+        &self,
+        knows: impl Fn(&Word) -> bool,
+        frequencies: Option<&WordFrequencies>,
+    ) -> GeniusReach<'a> {
+        let (mut known, mut unknown): (Vec<_>, Vec<_>) = self
+            .entries()
+            .into_iter()
+            .partition(|(word, _)| knows(word));
+        known.sort_by_key(|(word, _)| *word);
+        let points = known.iter().map(|(_, score)| score.points).sum();
+        let threshold = genius_threshold(self.total_points());
+
+        let obscurity =
+            |word: &str| frequencies.map_or(0.0, |frequencies| frequencies.obscurity(word));
+        unknown.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .points
+                .cmp(&a_score.points)
+                .then(obscurity(a).total_cmp(&obscurity(b)))
+                .then(a.cmp(b))
+        });
+        let mut missing = threshold.saturating_sub(points);
+        let mut to_learn = Vec::new();
+        for (word, score) in unknown {
+            if missing == 0 {
+                break;
+            }
+            missing = missing.saturating_sub(score.points);
+            to_learn.push((word, score));
+        }
+
+        GeniusReach {
+            known,
+            points,
+            threshold,
+            to_learn,
+        }
+    }
+}
+
+/// e.g. `the 12 answers you know are worth 40 points, 15 short of Genius at
+/// 55` followed by the answers to learn.
+impl fmt::Display for GeniusReach<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the {} answers you know are worth {} points",
+            self.known.len(),
+            self.points
+        )?;
+        if self.is_reachable() {
+            return writeln!(f, ", enough for Genius at {}", self.threshold);
+        }
+        writeln!(
+            f,
+            ", {} short of Genius at {}, also find these {} answers:",
+            self.shortfall(),
+            self.threshold,
+            self.to_learn.len()
+        )?;
+        for (word, score) in &self.to_learn {
+            writeln!(f, "{word} ({})", score.points)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+//! A player's lifetime statistics, aggregated over their stored sessions (see
+//! [`SessionStore`](crate::session::SessionStore)).
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::{date::PuzzleDate, game::GameResult, genius::Rank, session::Session};
+
+// the longest bar of the rank histogram, in characters.
+const MAX_BAR: usize = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    pub puzzles: usize,
+    /// The number of puzzles by the rank reached on them.
+    pub ranks: BTreeMap<Rank, usize>,
+    /// Consecutive days up to today (or yesterday, while today's Genius is
+    /// still open) with a Genius rank or better on at least one puzzle.
+    pub current_genius_streak: usize,
+    pub longest_genius_streak: usize,
+    pub pangrams_found: usize,
+    pub pangrams: usize,
+    pub words_found: usize,
+    pub answers: usize,
+}
+
+impl PlayerStats {
+    /// Aggregate the `played` sessions with the solutions of their puzzles.
+    /// Sessions without a date don't count towards streaks.
+    pub fn new<'a, 'r: 'a>(
+        played: impl IntoIterator<Item = (&'a Session, &'a GameResult<'r>)>,
+        today: PuzzleDate,
+    ) -> anyhow::Result<PlayerStats> {
+        let mut stats = PlayerStats {
+            puzzles: 0,
+            ranks: BTreeMap::new(),
+            current_genius_streak: 0,
+            longest_genius_streak: 0,
+            pangrams_found: 0,
+            pangrams: 0,
+            words_found: 0,
+            answers: 0,
+        };
+        let mut genius_days = BTreeSet::new();
+        for (session, result) in played {
+            let entries = result.entries();
+            let found: Vec<_> = entries
+                .iter()
+                .filter(|(word, _)| session.is_found(word))
+                .collect();
+            let points = found.iter().map(|(_, score)| score.points).sum();
+            let rank = Rank::of(points, result.total_points());
+
+            stats.puzzles += 1;
+            *stats.ranks.entry(rank).or_default() += 1;
+            stats.pangrams += entries.iter().filter(|(_, score)| score.is_pangram).count();
+            stats.pangrams_found += found.iter().filter(|(_, score)| score.is_pangram).count();
+            stats.words_found += found.len();
+            stats.answers += entries.len();
+            if let Some(date) = session.date
+                && rank >= Rank::Genius
+            {
+                genius_days.insert(date);
+            }
+        }
+
+        // runs of consecutive days, oldest first.
+        let mut runs: Vec<(PuzzleDate, usize)> = Vec::new();
+        for day in genius_days {
+            match runs.last_mut() {
+                Some((last, length)) if day.previous()? == *last => {
+                    *last = day;
+                    *length += 1;
+                }
+                _ => runs.push((day, 1)),
+            }
+        }
+        stats.longest_genius_streak = runs.iter().map(|&(_, length)| length).max().unwrap_or(0);
+        stats.current_genius_streak = match runs.last() {
+            Some(&(last, length)) if last == today || last == today.previous()? => length,
+            _ => 0,
+        };
+        Ok(stats)
+    }
+
+    /// The average rank, as its position from Beginner (0) to Queen Bee (9).
+    pub fn average_rank(&self) -> Option<f64> {
+        let positions: usize = self
+            .ranks
+            .iter()
+            .map(|(rank, count)| *rank as usize * count)
+            .sum();
+        (self.puzzles > 0).then(|| positions as f64 / self.puzzles as f64)
+    }
+
+    pub fn genius_count(&self) -> usize {
+        self.ranks
+            .range(Rank::Genius..)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// The fraction of the pangrams found, 0 without any.
+    pub fn pangram_rate(&self) -> f64 {
+        ratio(self.pangrams_found, self.pangrams)
+    }
+
+    pub fn average_words_found(&self) -> f64 {
+        ratio(self.words_found, self.puzzles)
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// A dashboard, e.g.
+/// ```text
+/// 12 puzzles played, average rank Great (6.4)
+/// Genius on 5 of them, current streak 2 days, longest 3 days
+/// pangrams found: 83.3% of 18
+/// words found: 31.2 per puzzle, of 42.5 answers on average
+///
+/// Beginner    #           1
+/// Great       ######      6
+/// Genius      ####        4
+/// Queen Bee   #           1
+/// ```
+impl fmt::Display for PlayerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.puzzles == 1 { "" } else { "s" };
+        write!(f, "{} puzzle{plural} played", self.puzzles)?;
+        let Some(average) = self.average_rank() else {
+            return writeln!(f);
+        };
+        let rank = Rank::ALL[(average.round() as usize).min(Rank::ALL.len() - 1)];
+        writeln!(f, ", average rank {rank} ({average:.1})")?;
+        let days = |count: usize| if count == 1 { "day" } else { "days" };
+        writeln!(
+            f,
+            "Genius on {} of them, current streak {} {}, longest {} {}",
+            self.genius_count(),
+            self.current_genius_streak,
+            days(self.current_genius_streak),
+            self.longest_genius_streak,
+            days(self.longest_genius_streak)
+        )?;
+        writeln!(
+            f,
+            "pangrams found: {:.1}% of {}",
+            100.0 * self.pangram_rate(),
+            self.pangrams
+        )?;
+        writeln!(
+            f,
+            "words found: {:.1} per puzzle, of {:.1} answers on average\n",
+            self.average_words_found(),
+            ratio(self.answers, self.puzzles)
+        )?;
+
+        let most = self.ranks.values().copied().max().unwrap_or(0);
+        let count_width = most.to_string().len();
+        let label_width = Rank::ALL
+            .iter()
+            .map(|rank| rank.name().len())
+            .max()
+            .unwrap_or(0);
+        for (rank, count) in &self.ranks {
+            // scaled down for players with many puzzles of one rank.
+            let bar = if most > MAX_BAR {
+                (count * MAX_BAR).div_ceil(most)
+            } else {
+                *count
+            };
+            writeln!(
+                f,
+                "{:<label_width$}  {:<bar_width$}  {count:>count_width$}",
+                rank.name(),
+                "#".repeat(bar),
+                bar_width = most.min(MAX_BAR)
+            )?;
+        }
+        Ok(())
+    }
+}
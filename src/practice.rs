@@ -0,0 +1,62 @@
+//! Random practice puzzles beyond the daily NYT one, built around a pangram of
+//! the dictionary. Puzzles are seeded by date, so everyone practicing with the
+//! same word list on a day gets the same puzzle and can compare notes.
+
+use crate::{
+    constraints::Constraints,
+    date::PuzzleDate,
+    dictionary::Dictionary,
+    fuzz::{self, Rng},
+    game::{GameResult, PuzzleSpec},
+};
+
+/// How many random puzzles are tried before settling for one that does not
+/// meet all constraints.
+const ATTEMPTS: usize = 200;
+
+/// The seed of the practice puzzle of `date`, e.g. 20240601 for 2024-06-01.
+pub fn seed(date: PuzzleDate) -> u64 {
+    date.to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0, |seed, digit| seed * 10 + u64::from(digit))
+}
+
+/// The practice puzzle of `seed` and its solution: the letters of a random
+/// pangram the `constraints` allow, with a random center letter, tried until
+/// the puzzle meets all `constraints`. If none does, the first one meeting the
+/// most of them.
+pub fn practice_puzzle<'a>(
+    dict: &'a Dictionary,
+    seed: u64,
+    constraints: &Constraints,
+    solve: impl Fn(&PuzzleSpec) -> anyhow::Result<GameResult<'a>>,
+) -> anyhow::Result<(PuzzleSpec, GameResult<'a>)> {
+    let pangrams: Vec<_> = fuzz::pangrams(dict)
+        .into_iter()
+        .filter(|word| {
+            fuzz::distinct_letters(word)
+                .is_some_and(|letters| constraints.allows_pangram(word, &letters))
+        })
+        .collect();
+    anyhow::ensure!(
+        !pangrams.is_empty(),
+        "the dictionary has no words with seven distinct letters to build a puzzle around, with {constraints}"
+    );
+
+    let mut rng = Rng::new(seed);
+    let mut best: Option<(usize, PuzzleSpec, GameResult<'a>)> = None;
+    for _ in 0..ATTEMPTS {
+        let puzzle = fuzz::random_puzzle(&mut rng, &pangrams, false);
+        let result = solve(&puzzle)?;
+        if constraints.are_satisfied_by(&puzzle, &result) {
+            return Ok((puzzle, result));
+        }
+        let satisfied = constraints.satisfied(&puzzle, &result);
+        if best.as_ref().is_none_or(|(best, _, _)| satisfied > *best) {
+            best = Some((satisfied, puzzle, result));
+        }
+    }
+    let (_, puzzle, result) = best.expect("at least one puzzle was tried");
+    Ok((puzzle, result))
+}
@@ -0,0 +1,105 @@
+//! Post-processing of dictionaries approximating the curation of NYT's editor,
+//! whose answer lists are much smaller than any scrabble word list.
+
+use std::{collections::HashSet, fmt, path::Path};
+
+use crate::{
+    dictionary::{self, Dictionary},
+    frequency::WordFrequencies,
+    game::Word,
+};
+
+/// The curation rules to apply, each of which can be toggled individually.
+#[derive(Default)]
+pub struct NytProfile {
+    /// Drop words whose only S is a plural ending, like `FACETS` next to `FACET`.
+    pub drop_plurals: bool,
+    /// Drop words more obscure than the threshold (between 0 and 1, see
+    /// [`WordFrequencies::obscurity`]).
+    pub max_obscurity: Option<(WordFrequencies, f64)>,
+    /// Words to drop regardless, such as offensive ones.
+    pub blocklist: HashSet<Word>,
+}
+
+/// How many words each rule of a profile dropped.
+#[derive(Debug, Default)]
+pub struct ProfileStats {
+    pub kept: usize,
+    pub plural: usize,
+    pub obscure: usize,
+    pub blocked: usize,
+}
+
+impl fmt::Display for ProfileStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "kept {} words, dropped {} plural, {} obscure and {} blocked words",
+            self.kept, self.plural, self.obscure, self.blocked
+        )
+    }
+}
+
+impl NytProfile {
+    /// Load a blocklist with one word per line, ignoring empty lines and
+    /// lines starting with `#`. Words are uppercased.
+    pub fn load_blocklist(path: &Path) -> anyhow::Result<HashSet<Word>> {
+        dictionary::read_word_file(path)
+    }
+
+    /// Drop all words from `dict` that violate one of the enabled rules.
+    pub fn apply(&self, dict: &mut Dictionary) -> ProfileStats {
+        let mut stats = ProfileStats::default();
+        // plurals are recognized by their singular, which must be looked up
+        // before anything is dropped.
+        let plurals: HashSet<Word> = if self.drop_plurals {
+            dict.iter()
+                .filter(|word| is_plural(word, dict))
+                .cloned()
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        dict.retain(|word| {
+            if self.blocklist.contains(word) {
+                stats.blocked += 1;
+                false
+            } else if plurals.contains(word) {
+                stats.plural += 1;
+                false
+            } else if self
+                .max_obscurity
+                .as_ref()
+                .is_some_and(|(frequencies, max)| frequencies.obscurity(word) > *max)
+            {
+                stats.obscure += 1;
+                false
+            } else {
+                stats.kept += 1;
+                true
+            }
+        });
+
+        log::info!("applied NYT profile: {stats}");
+        stats
+    }
+}
+
+/// Whether the word's only S is a plural ending of another word, e.g. `FACETS`,
+/// `BOXES` or `BERRIES`, but not `GLASS` or `SLATES`.
+fn is_plural(word: &str, words: &Dictionary) -> bool {
+    if word.matches('S').count() != 1 {
+        return false;
+    }
+    let Some(stem) = word.strip_suffix('S') else {
+        return false;
+    };
+    words.contains(stem)
+        || stem
+            .strip_suffix('E')
+            .is_some_and(|stem| words.contains(stem))
+        || stem
+            .strip_suffix("IE")
+            .is_some_and(|stem| words.contains(&format!("{stem}Y")))
+}
@@ -0,0 +1,175 @@
+//! A JSON file describing one puzzle, to pass puzzles between the practice
+//! generator, the archive and the solver. Only `center` and `letters` are
+//! required, e.g.
+//! ```json
+//! {
+//!   "center": "C",
+//!   "letters": "ALTEFI",
+//!   "date": "2023-12-30",
+//!   "id": 20301,
+//!   "editor": "Sam Ezersky",
+//!   "answers": ["ACETIC", "FACET", "FELICITATE"]
+//! }
+//! ```
+//! `date`, `id` and `editor` describe official puzzles, which are only treated
+//! as such with both a date and an id. `answers` are the puzzle's answers if
+//! known, uppercase, e.g. the official ones.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    archive::ArchivedPuzzle,
+    date::PuzzleDate,
+    game::{Game, GameResult, PuzzleMetadata, Word},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PuzzleFile {
+    /// The center letter, e.g. `C`.
+    pub center: String,
+    /// The six other letters, e.g. `ALTEFI`.
+    pub letters: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<PuzzleDate>,
+    /// The NYT's id of the puzzle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answers: Option<Vec<Word>>,
+}
+
+impl PuzzleFile {
+    /// The file of `game`, with the date, id and editor of official games.
+    pub fn new(game: &Game) -> PuzzleFile {
+        let mut letters = game.letters().map(char::from);
+        let center = letters.next().map(String::from).unwrap_or_default();
+        let metadata = game.metadata();
+        PuzzleFile {
+            center,
+            letters: letters.collect(),
+            date: metadata.map(|metadata| metadata.date),
+            id: metadata.map(|metadata| metadata.id),
+            editor: metadata.and_then(|metadata| metadata.editor.clone()),
+            answers: None,
+        }
+    }
+
+    /// Include `answers`, sorted alphabetically.
+    pub fn with_answers(mut self, answers: impl IntoIterator<Item = Word>) -> PuzzleFile {
+        let mut answers: Vec<Word> = answers.into_iter().collect();
+        answers.sort_unstable();
+        self.answers = Some(answers);
+        self
+    }
+
+    /// Include the answers of `result`.
+    pub fn with_result(self, result: &GameResult) -> PuzzleFile {
+        self.with_answers(result.entries().into_iter().map(|(word, _)| word.clone()))
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<PuzzleFile> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        serde_json::from_reader(file)
+            .with_context(|| format!("{} is not a valid puzzle file", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, self)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(writer))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// The game described by the file, validating its letters.
+    pub fn game(&self) -> anyhow::Result<Game> {
+        let game = Game::parse(&self.center, &self.letters)?;
+        Ok(match (self.date, self.id) {
+            (Some(date), Some(id)) => {
+                game.with_metadata(PuzzleMetadata::new(date, id, self.editor.clone()))
+            }
+            _ => game,
+        })
+    }
+}
+
+/// How a solution differs from the answers listed in a puzzle file, both
+/// sorted alphabetically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerComparison<'a> {
+    pub listed: usize,
+    /// Listed answers the solution lacks.
+    pub missing: Vec<&'a Word>,
+    /// Answers of the solution that aren't listed.
+    pub unlisted: Vec<&'a Word>,
+}
+
+impl PuzzleFile {
+    /// Compare `result` with the listed answers, if the file has any.
+    pub fn compare<'a>(&'a self, result: &GameResult<'a>) -> Option<AnswerComparison<'a>> {
+        let answers = self.answers.as_ref()?;
+        let entries = result.entries();
+        let mut missing: Vec<&Word> = answers
+            .iter()
+            .filter(|answer| !entries.iter().any(|(word, _)| word == answer))
+            .collect();
+        missing.sort_unstable();
+        let mut unlisted: Vec<&Word> = entries
+            .iter()
+            .map(|(word, _)| *word)
+            .filter(|word| !answers.contains(word))
+            .collect();
+        unlisted.sort_unstable();
+        Some(AnswerComparison {
+            listed: answers.len(),
+            missing,
+            unlisted,
+        })
+    }
+}
+
+/// e.g. `24 listed answers, 1 missing from the solution: FACE, 2 found but not
+/// listed: CAFE, FACETE`.
+impl fmt::Display for AnswerComparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |words: &[&Word]| {
+            let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+            words.join(", ")
+        };
+        write!(
+            f,
+            "{} listed answers, {} missing from the solution",
+            self.listed,
+            self.missing.len()
+        )?;
+        if !self.missing.is_empty() {
+            write!(f, ": {}", join(&self.missing))?;
+        }
+        write!(f, ", {} found but not listed", self.unlisted.len())?;
+        if !self.unlisted.is_empty() {
+            write!(f, ": {}", join(&self.unlisted))?;
+        }
+        Ok(())
+    }
+}
+
+/// The archived puzzle with its official answers.
+impl From<&ArchivedPuzzle> for PuzzleFile {
+    fn from(puzzle: &ArchivedPuzzle) -> Self {
+        PuzzleFile::new(&puzzle.game()).with_answers(puzzle.answers.iter().cloned())
+    }
+}
@@ -0,0 +1,49 @@
+//! Containment queries by letter set: the archived puzzles a word fits, and the
+//! dictionary pangrams of a set of letters.
+
+use crate::{
+    alphabet::LetterSet,
+    archive::{Archive, ArchivedPuzzle},
+    game::{PangramIndex, Rules, Word},
+};
+
+/// An archived puzzle whose letters spell a word.
+pub struct PuzzleMatch<'a> {
+    pub puzzle: &'a ArchivedPuzzle,
+    /// Whether the word was one of the official answers. If not, it would have
+    /// been accepted by the rules, but NYT didn't list it.
+    pub official: bool,
+    pub pangram: bool,
+}
+
+/// The archived puzzles `word` was or would have been an answer of, oldest
+/// first: it contains the center letter, no letters other than the puzzle's and
+/// is long enough. Words with other characters don't fit any puzzle.
+pub fn puzzles_with_answer<'a>(archive: &'a Archive, word: &str) -> Vec<PuzzleMatch<'a>> {
+    let Some(word_letters) = LetterSet::of_word(word) else {
+        return Vec::new();
+    };
+    if word.chars().count() < Rules::SPELLING_BEE.min_length {
+        return Vec::new();
+    }
+    archive
+        .range(None, None)
+        .filter(|puzzle| word_letters.contains(puzzle.center_letter))
+        .filter_map(|puzzle| {
+            let puzzle_letters: LetterSet = puzzle.game().letters().collect();
+            word_letters.is_subset(puzzle_letters).then(|| PuzzleMatch {
+                puzzle,
+                official: puzzle.answers.iter().any(|answer| answer == word),
+                pangram: word_letters == puzzle_letters,
+            })
+        })
+        .collect()
+}
+
+/// The dictionary words using exactly `letters`, i.e. the pangrams of a puzzle
+/// with these letters, in alphabetical order.
+pub fn pangrams_of<'a>(index: &PangramIndex<'a>, letters: LetterSet) -> Vec<&'a Word> {
+    let mut pangrams = index.words_with_letters(letters).to_vec();
+    pangrams.sort_unstable();
+    pangrams
+}
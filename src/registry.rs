@@ -0,0 +1,212 @@
+//! Strategies selectable by name at runtime. The built-in strategies are
+//! registered by [`StrategyRegistry::default`], and other crates can add their
+//! own with [`StrategyRegistry::register`].
+
+use std::{sync::Arc, time::Duration};
+
+#[cfg(feature = "parallel")]
+use crate::game::{ParallelBruteForce, ParallelLetterMap};
+use crate::{
+    dictionary::Dictionary,
+    game::{
+        BruteForce, ComplementIndex, GameResult, GameSolver, LetterMap, PangramIndex,
+        PartialResult, PuzzleSpec, RarestLetterMap, SolveStats, SolveStrategy, Word,
+    },
+    scoring::Scoring,
+};
+
+/// A solver whose strategy is only known at runtime.
+pub trait DynSolver<'a>: Send + Sync {
+    fn solve_with_stats(&self, puzzle: &PuzzleSpec)
+    -> anyhow::Result<(GameResult<'a>, SolveStats)>;
+
+    fn solve(&self, puzzle: &PuzzleSpec) -> anyhow::Result<GameResult<'a>> {
+        Ok(self.solve_with_stats(puzzle)?.0)
+    }
+
+    /// See [`GameSolver::solve_with_deadline`]. Solvers without a deadline
+    /// solve completely.
+    fn solve_with_deadline(
+        &self,
+        puzzle: &PuzzleSpec,
+        _budget: Duration,
+    ) -> anyhow::Result<PartialResult<'a>> {
+        let (result, stats) = self.solve_with_stats(puzzle)?;
+        Ok(PartialResult { result, stats })
+    }
+
+    /// See [`SolveStrategy::insert`].
+    fn insert(&mut self, _word: &'a Word) -> bool {
+        false
+    }
+
+    /// See [`SolveStrategy::remove`].
+    fn remove(&mut self, _word: &Word) -> bool {
+        false
+    }
+}
+
+impl<'a, S> DynSolver<'a> for GameSolver<S>
+where
+    S: SolveStrategy<'a> + Send + Sync,
+{
+    fn solve_with_stats(
+        &self,
+        puzzle: &PuzzleSpec,
+    ) -> anyhow::Result<(GameResult<'a>, SolveStats)> {
+        GameSolver::solve_with_stats(self, puzzle)
+    }
+
+    fn solve_with_deadline(
+        &self,
+        puzzle: &PuzzleSpec,
+        budget: Duration,
+    ) -> anyhow::Result<PartialResult<'a>> {
+        GameSolver::solve_with_deadline(self, puzzle, budget)
+    }
+
+    fn insert(&mut self, word: &'a Word) -> bool {
+        GameSolver::insert(self, word)
+    }
+
+    fn remove(&mut self, word: &Word) -> bool {
+        GameSolver::remove(self, word)
+    }
+}
+
+/// Builds a solver over a dictionary, running on `threads` threads (0 for one
+/// per CPU) and scoring answers with the given scoring.
+pub type BuildSolver = for<'a> fn(
+    &'a Dictionary,
+    usize,
+    Arc<dyn Scoring>,
+) -> anyhow::Result<Box<dyn DynSolver<'a> + 'a>>;
+
+/// A strategy as listed by the registry.
+#[derive(Clone, Copy)]
+pub struct RegisteredStrategy {
+    /// Unique name to select the strategy by, e.g. `letter-map`.
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether the strategy only finds the pangrams.
+    pub pangrams_only: bool,
+    pub build: BuildSolver,
+}
+
+/// Build a [`GameSolver`] with strategy `S`, e.g. for [`RegisteredStrategy::build`]:
+/// `build: |dict, threads, scoring| registry::solver::<MyStrategy>(dict, threads, scoring)`.
+pub fn solver<'a, S>(
+    dict: &'a Dictionary,
+    threads: usize,
+    scoring: Arc<dyn Scoring>,
+) -> anyhow::Result<Box<dyn DynSolver<'a> + 'a>>
+where
+    S: SolveStrategy<'a> + Send + Sync + 'a,
+{
+    Ok(Box::new(
+        GameSolver::<S>::new(dict, threads)?.with_scoring(scoring),
+    ))
+}
+
+const BUILTIN: &[RegisteredStrategy] = &[
+    RegisteredStrategy {
+        name: "brute-force",
+        description: "Checks every word of the dictionary.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<BruteForce>(dict, threads, scoring),
+    },
+    #[cfg(feature = "parallel")]
+    RegisteredStrategy {
+        name: "parallel-brute-force",
+        description: "Checks the words long enough for the rules on multiple threads, longest first.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<ParallelBruteForce>(dict, threads, scoring),
+    },
+    RegisteredStrategy {
+        name: "letter-map",
+        description: "Checks the words containing the center letter.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<LetterMap>(dict, threads, scoring),
+    },
+    #[cfg(feature = "parallel")]
+    RegisteredStrategy {
+        name: "parallel-letter-map",
+        description: "Checks the words containing the center letter on multiple threads.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<ParallelLetterMap>(dict, threads, scoring),
+    },
+    RegisteredStrategy {
+        name: "rarest-letter-map",
+        description: "Checks the words whose rarest letter is a puzzle letter at most as rare as the center letter.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<RarestLetterMap>(dict, threads, scoring),
+    },
+    RegisteredStrategy {
+        name: "pangram-index",
+        description: "Looks up the words with exactly the puzzle's letters.",
+        pangrams_only: true,
+        build: |dict, threads, scoring| solver::<PangramIndex>(dict, threads, scoring),
+    },
+    RegisteredStrategy {
+        name: "complement-index",
+        description: "Looks up the words whose letters are a subset of the puzzle's letters.",
+        pangrams_only: false,
+        build: |dict, threads, scoring| solver::<ComplementIndex>(dict, threads, scoring),
+    },
+];
+
+/// The available strategies, in registration order.
+#[derive(Clone)]
+pub struct StrategyRegistry {
+    strategies: Vec<RegisteredStrategy>,
+}
+
+impl StrategyRegistry {
+    /// A registry without any strategies.
+    pub fn empty() -> Self {
+        StrategyRegistry {
+            strategies: Vec::new(),
+        }
+    }
+
+    /// Add a strategy, failing if one with the same name is already registered.
+    pub fn register(&mut self, strategy: RegisteredStrategy) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.get(strategy.name).is_none(),
+            "a strategy named '{}' is already registered",
+            strategy.name
+        );
+        self.strategies.push(strategy);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RegisteredStrategy> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.name == name)
+    }
+
+    /// The strategy named `name`, with the available names in the error otherwise.
+    pub fn find(&self, name: &str) -> anyhow::Result<&RegisteredStrategy> {
+        self.get(name).ok_or_else(|| {
+            let names: Vec<&str> = self.iter().map(|strategy| strategy.name).collect();
+            anyhow::anyhow!(
+                "unknown strategy '{name}', expected one of {}",
+                names.join(", ")
+            )
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredStrategy> {
+        self.strategies.iter()
+    }
+}
+
+/// The built-in strategies (the parallel ones with the `parallel` feature).
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        StrategyRegistry {
+            strategies: BUILTIN.to_vec(),
+        }
+    }
+}
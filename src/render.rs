@@ -0,0 +1,100 @@
+//! Renders the hive of letters, with the center letter in the middle and the
+//! outer letters placed clockwise around it, starting at the top.
+
+use std::fmt::Write;
+
+use crate::game::{GameProcessed, Letter};
+
+// positions of the cells in the ASCII art, as (column, row) of each hexagon's top left corner.
+const ASCII_CENTER_CELL: (usize, usize) = (4, 2);
+const ASCII_OUTER_CELLS: [(usize, usize); 6] = [(4, 0), (8, 1), (8, 3), (4, 4), (0, 3), (0, 1)];
+const ASCII_WIDTH: usize = 13;
+const ASCII_HEIGHT: usize = 7;
+
+const SVG_RADIUS: f64 = 50.0;
+const SVG_CENTER_FILL: &str = "#f7da21";
+const SVG_OUTER_FILL: &str = "#e6e6e6";
+
+pub fn hive_ascii(game: &GameProcessed) -> String {
+    let mut canvas = vec![vec![' '; ASCII_WIDTH]; ASCII_HEIGHT];
+    for (letter, (x, y)) in cells(game, ASCII_CENTER_CELL, ASCII_OUTER_CELLS) {
+        // neighbouring hexagons share edges, which are drawn identically by both.
+        for (dx, dy, c) in [
+            (1, 0, '_'),
+            (2, 0, '_'),
+            (3, 0, '_'),
+            (0, 1, '/'),
+            (2, 1, letter.into()),
+            (4, 1, '\\'),
+            (0, 2, '\\'),
+            (1, 2, '_'),
+            (2, 2, '_'),
+            (3, 2, '_'),
+            (4, 2, '/'),
+        ] {
+            canvas[y + dy][x + dx] = c;
+        }
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn hive_svg(game: &GameProcessed) -> String {
+    // flat-topped hexagons, whose neighbours lie at a distance of sqrt(3) * radius.
+    let distance = 3f64.sqrt() * SVG_RADIUS;
+    let outer_positions = [-90.0, -30.0, 30.0, 90.0, 150.0, 210.0].map(|degrees: f64| {
+        let angle = degrees.to_radians();
+        (distance * angle.cos(), distance * angle.sin())
+    });
+
+    let extent = distance + SVG_RADIUS;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.1} {:.1} {:.1} {:.1}">"#,
+        -extent,
+        -extent,
+        2.0 * extent,
+        2.0 * extent
+    );
+    svg.push('\n');
+    if let Some(metadata) = game.metadata() {
+        svg.push_str(&format!("  <title>Spelling Bee {metadata}</title>\n"));
+    }
+    for (letter, (x, y)) in cells(game, (0.0, 0.0), outer_positions) {
+        let fill = if letter == game.center_letter() {
+            SVG_CENTER_FILL
+        } else {
+            SVG_OUTER_FILL
+        };
+        // shrink the hexagons a bit to leave a gap between them.
+        let points = (0..6)
+            .map(|i| {
+                let angle = (60.0 * i as f64).to_radians();
+                let r = SVG_RADIUS * 0.92;
+                format!("{:.1},{:.1}", x + r * angle.cos(), y + r * angle.sin())
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(svg, r#"  <polygon points="{points}" fill="{fill}"/>"#).unwrap();
+        writeln!(
+            svg,
+            r#"  <text x="{x:.1}" y="{y:.1}" text-anchor="middle" dominant-baseline="central" font-family="sans-serif" font-size="40" font-weight="bold">{letter}</text>"#
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Pairs every letter with its position, the center letter coming first.
+fn cells<P: Copy>(
+    game: &GameProcessed,
+    center: P,
+    outer: [P; 6],
+) -> impl Iterator<Item = (Letter, P)> {
+    std::iter::once((game.center_letter(), center))
+        .chain(game.non_center_letters().into_iter().zip(outer))
+}
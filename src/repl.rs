@@ -0,0 +1,410 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+    path::Path,
+};
+
+use anstyle::Style;
+use anyhow::Context;
+use nyt_spelling_bee_solver::{
+    annotate::AnnotatedResult,
+    constraints::Constraints,
+    date::PuzzleDate,
+    dictionary::Dictionary,
+    enrich::DefinitionLookup,
+    game::{
+        Game, GameResult, GameSolver, GuessingError, Letter, ParallelLetterMap, PuzzleSpec, Word,
+    },
+    hints::{self, Hints, RemainingAnswers, WordHint},
+    practice,
+    session::{HintLevel, Reveal, Session, SessionStore},
+    suggest,
+    validate::ValidationOptions,
+};
+
+use crate::{
+    output::{self, OutputFormat},
+    style::Theme,
+};
+
+const HELP: &str = "\
+commands:
+  game <center> <letters>  set the puzzle, e.g. `game c altefi`
+  practice [<date>|<seed>] set a random practice puzzle, by default today's
+  solve                    print all answers
+  hints                    print the spoiler-free hints
+  hint                     print the escalated hint of each answer
+  hint <n>                 reveal more of the n-th answer: its length, first
+                           letter, first two letters, definition, the word
+  check <word>             check whether a word is a valid answer
+  prefix <pattern>...      track how many remaining answers start with each
+                           pattern, `_` standing for any letter, e.g. `fa f_c`;
+                           updated after each answer found
+  prefix                   print the counts of the tracked patterns
+  prefix clear             stop tracking patterns
+  pangrams                 print the pangrams
+  found                    print the answers found so far
+  tag <word> <tag>         tag an answer, e.g. `tag facet looked up`
+  tags <word>              print the tags of an answer
+  note <text>              add a note to the session
+  notes                    print the notes
+  session save <path>      save the puzzle and progress to a JSON file
+  session load <path>      resume a saved session
+  help                     print this message
+  quit                     exit";
+
+/// Interactive session that keeps the dictionary and solver index alive
+/// between queries.
+pub struct Repl<'a> {
+    dict: &'a Dictionary,
+    solver: GameSolver<ParallelLetterMap<'a>>,
+    puzzle: Option<(PuzzleSpec, GameResult<'a>)>,
+    // the progress on `puzzle`.
+    session: Option<Session>,
+    lookup: Option<&'a DefinitionLookup<'a>>,
+    // the definitions looked up for escalated hints so far.
+    definitions: HashMap<&'a Word, Option<String>>,
+    // the patterns of `prefix`, uppercase, in the order they were added.
+    prefixes: Vec<String>,
+    // where the session is stored after every command.
+    store: Option<SessionStore>,
+    // what practice puzzles have to meet.
+    constraints: Constraints,
+    theme: Theme,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(dict: &'a Dictionary, threads: usize, theme: Theme) -> anyhow::Result<Self> {
+        Ok(Repl {
+            dict,
+            solver: GameSolver::new(dict, threads)?,
+            puzzle: None,
+            session: None,
+            lookup: None,
+            definitions: HashMap::new(),
+            prefixes: Vec::new(),
+            store: None,
+            constraints: Constraints::from(&ValidationOptions::default()),
+            theme,
+        })
+    }
+
+    /// Show the definitions of answers when escalating their hints.
+    pub fn with_definitions(mut self, lookup: &'a DefinitionLookup<'a>) -> Self {
+        self.lookup = Some(lookup);
+        self
+    }
+
+    /// Store the progress on every puzzle played in `store`.
+    pub fn with_store(mut self, store: SessionStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Generate practice puzzles meeting `constraints` instead of those of NYT
+    /// puzzles.
+    pub fn with_constraints(mut self, constraints: Constraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Resume the session saved at `path`.
+    pub fn load_session(&mut self, path: &Path) -> anyhow::Result<()> {
+        let session = Session::load(path)?;
+        let puzzle = PuzzleSpec::from(session.game());
+        let result = self.solver.solve(&puzzle)?;
+        self.puzzle = Some((puzzle, result));
+        self.session = Some(session);
+        self.prefixes.clear();
+        Ok(())
+    }
+
+    /// Set the practice puzzle of `seed`, see [`practice::seed`].
+    pub fn practice(&mut self, seed: u64, out: &mut impl Write) -> anyhow::Result<()> {
+        let (puzzle, result) =
+            practice::practice_puzzle(self.dict, seed, &self.constraints, |puzzle| {
+                self.solver.solve(puzzle)
+            })?;
+        let mut letters = puzzle.game().letters().map(char::from);
+        let center = letters.next().unwrap_or_default();
+        writeln!(
+            out,
+            "practice puzzle {seed}: center {center}, letters {}, {} answers",
+            letters.collect::<String>(),
+            result.entries().len()
+        )?;
+        self.session = Some(Session::new(puzzle.game()));
+        self.puzzle = Some((puzzle, result));
+        self.prefixes.clear();
+        Ok(())
+    }
+
+    pub fn run(&mut self, input: impl BufRead, out: &mut impl Write) -> anyhow::Result<()> {
+        writeln!(out, "type `help` for a list of commands")?;
+        write!(out, "> ")?;
+        out.flush()?;
+        for line in input.lines() {
+            let result = self.execute(&line?, out);
+            if let (Some(store), Some(session)) = (&self.store, &self.session)
+                && let Err(e) = store.save(session)
+            {
+                writeln!(out, "error: failed to store the session: {e:#}")?;
+            }
+            match result {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(e) => writeln!(out, "error: {e:#}")?,
+            }
+            write!(out, "> ")?;
+            out.flush()?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Execute a single command, returning whether the session continues.
+    fn execute(&mut self, line: &str, out: &mut impl Write) -> anyhow::Result<bool> {
+        let mut args = line.split_whitespace();
+        let Some(command) = args.next() else {
+            return Ok(true);
+        };
+        let args: Vec<&str> = args.collect();
+
+        match (command, args.as_slice()) {
+            ("game", [center, letters @ ..]) => {
+                let game = Game::parse(center, &letters.concat())?;
+                let session = Session::new(&game);
+                let puzzle = PuzzleSpec::from(game);
+                let result = self.solver.solve(&puzzle)?;
+                writeln!(out, "{} answers", result.entries().len())?;
+                self.puzzle = Some((puzzle, result));
+                self.session = Some(session);
+                self.prefixes.clear();
+            }
+            ("practice", []) => self.practice(practice::seed(PuzzleDate::current()?), out)?,
+            ("practice", [date_or_seed]) => {
+                let seed = match date_or_seed.parse() {
+                    Ok(seed) => seed,
+                    Err(_) => practice::seed(date_or_seed.parse()?),
+                };
+                self.practice(seed, out)?;
+            }
+            ("solve", []) => {
+                let (puzzle, result) = self.puzzle()?;
+                output::write_result(
+                    &result.into(),
+                    puzzle,
+                    &self.dict.metadata(),
+                    OutputFormat::Text,
+                    &self.theme,
+                    None,
+                    out,
+                )?;
+                self.reveal(Reveal::Answers);
+            }
+            ("hints", []) => {
+                let (_, result) = self.puzzle()?;
+                write!(out, "{}", Hints::new(result))?;
+                self.reveal(Reveal::Hints);
+            }
+            ("hint", []) => {
+                let (_, result) = self.puzzle()?;
+                let order = hints::escalation_order(result);
+                for (i, word) in order.into_iter().enumerate() {
+                    // resumed sessions don't include the definitions.
+                    if self.session()?.hint_level(word) == Some(HintLevel::Definition) {
+                        self.look_up_definition(word);
+                    }
+                    let hint = self.word_hint(word)?;
+                    let style = if hint.found {
+                        self.theme.found
+                    } else {
+                        Style::new()
+                    };
+                    writeln!(out, "{style}{:>3}. {hint}{style:#}", i + 1)?;
+                }
+            }
+            ("hint", [n]) => {
+                let (_, result) = self.puzzle()?;
+                let order = hints::escalation_order(result);
+                let word = n
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| order.get(n.checked_sub(1)?).copied())
+                    .with_context(|| format!("no answer {n}, there are {}", order.len()))?;
+                let session = self.session_mut()?;
+                if !session.is_found(word) && session.escalate_hint(word) == HintLevel::Definition {
+                    self.look_up_definition(word);
+                }
+                writeln!(out, "{n}. {}", self.word_hint(word)?)?;
+            }
+            ("check", [word]) => {
+                let (puzzle, result) = self.puzzle()?;
+                let check = puzzle.check(word, self.dict)?;
+                let suggestions = match check {
+                    Err(GuessingError::UnknownWord) => suggest::near_misses(word, result),
+                    _ => Vec::new(),
+                };
+                output::write_check(word, &check, &suggestions, out)?;
+                if check.is_ok() {
+                    let total = result.entries().len();
+                    if let Some(session) = &mut self.session {
+                        session.find(word);
+                        writeln!(out, "{} of {total} answers found", session.found().count())?;
+                    }
+                    let word = word.to_uppercase();
+                    // only when a count changed.
+                    if self
+                        .prefixes
+                        .iter()
+                        .any(|pattern| hints::starts_with_pattern(&word, pattern))
+                    {
+                        self.write_prefix_counts(out)?;
+                    }
+                }
+            }
+            ("prefix", []) => self.write_prefix_counts(out)?,
+            ("prefix", ["clear"]) => self.prefixes.clear(),
+            ("prefix", patterns) => {
+                self.puzzle()?;
+                for pattern in patterns {
+                    let pattern = pattern.to_uppercase();
+                    anyhow::ensure!(
+                        pattern.chars().all(|c| c == '_' || c.is_ascii_uppercase()),
+                        "invalid pattern `{pattern}`, use letters and `_` for any letter"
+                    );
+                    if !self.prefixes.contains(&pattern) {
+                        self.prefixes.push(pattern);
+                    }
+                }
+                self.write_prefix_counts(out)?;
+            }
+            ("pangrams", []) => {
+                let (_, result) = self.puzzle()?;
+                for (word, _) in result.entries().iter().filter(|(_, s)| s.is_pangram) {
+                    writeln!(out, "{word}")?;
+                }
+                self.reveal(Reveal::Pangrams);
+            }
+            ("found", []) => {
+                let found = self.theme.found;
+                for word in self.session()?.found() {
+                    writeln!(out, "{found}{word}{found:#}")?;
+                }
+            }
+            ("tag", [word, tag @ ..]) if !tag.is_empty() => {
+                let (Some((_, result)), Some(session)) = (&self.puzzle, &mut self.session) else {
+                    anyhow::bail!("no puzzle set, use `game <center> <letters>` first");
+                };
+                let mut annotated =
+                    AnnotatedResult::new(result, std::mem::take(&mut session.annotations));
+                let tagged = annotated.tag(word, &tag.join(" "));
+                session.annotations = annotated.into_annotations();
+                if !tagged? {
+                    writeln!(out, "{} was already tagged", word.to_uppercase())?;
+                }
+            }
+            ("tags", [word]) => {
+                let tags: Vec<_> = self.session()?.annotations.tags(word).collect();
+                writeln!(out, "{}", tags.join(", "))?;
+            }
+            ("note", [_, ..]) => {
+                self.session_mut()?.notes.push(args.join(" "));
+            }
+            ("notes", []) => {
+                for note in &self.session()?.notes {
+                    writeln!(out, "- {note}")?;
+                }
+            }
+            ("session", ["save", path]) => {
+                self.session()?.save(Path::new(path))?;
+                writeln!(out, "saved session to {path}")?;
+            }
+            ("session", ["load", path]) => {
+                self.load_session(Path::new(path))?;
+                let session = self.session()?;
+                writeln!(
+                    out,
+                    "resumed {}/{}: {} answers found, {} notes",
+                    session.center_letter,
+                    session
+                        .outer_letters
+                        .iter()
+                        .map(Letter::to_string)
+                        .collect::<String>(),
+                    session.found().count(),
+                    session.notes.len()
+                )?;
+            }
+            ("help", []) => writeln!(out, "{HELP}")?,
+            ("quit" | "exit", []) => return Ok(false),
+            _ => anyhow::bail!("unknown command `{line}`, type `help` for a list of commands"),
+        }
+        Ok(true)
+    }
+
+    fn puzzle(&self) -> anyhow::Result<&(PuzzleSpec, GameResult<'a>)> {
+        self.puzzle
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no puzzle set, use `game <center> <letters>` first"))
+    }
+
+    fn session(&self) -> anyhow::Result<&Session> {
+        self.session
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no puzzle set, use `game <center> <letters>` first"))
+    }
+
+    fn session_mut(&mut self) -> anyhow::Result<&mut Session> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("no puzzle set, use `game <center> <letters>` first"))
+    }
+
+    // failed lookups are retried when the hint is shown again, instead of
+    // blocking the escalation while offline.
+    fn look_up_definition(&mut self, word: &'a Word) {
+        if let Some(lookup) = self.lookup
+            && !self.definitions.contains_key(word)
+        {
+            match lookup(word) {
+                Ok(definition) => {
+                    self.definitions.insert(word, definition);
+                }
+                Err(e) => log::warn!("failed to look up the definition of {word}: {e:#}"),
+            }
+        }
+    }
+
+    fn word_hint(&self, word: &'a Word) -> anyhow::Result<WordHint<'_>> {
+        let session = self.session()?;
+        Ok(WordHint {
+            word,
+            level: session.hint_level(word),
+            found: session.is_found(word),
+            definition: self.definitions.get(word).and_then(Option::as_deref),
+        })
+    }
+
+    /// e.g. `FA   2 remaining` for each tracked pattern.
+    fn write_prefix_counts(&self, out: &mut impl Write) -> anyhow::Result<()> {
+        let (_, result) = self.puzzle()?;
+        let session = self.session()?;
+        let remaining = RemainingAnswers::new(result, |word| session.is_found(word));
+        let width = self.prefixes.iter().map(String::len).max().unwrap_or(0);
+        for pattern in &self.prefixes {
+            match remaining.count_matching(pattern) {
+                0 => writeln!(out, "{pattern:<width$}  none remaining")?,
+                count => writeln!(out, "{pattern:<width$}  {count} remaining")?,
+            }
+        }
+        writeln!(out, "{} answers remaining in total", remaining.len())?;
+        Ok(())
+    }
+
+    fn reveal(&mut self, reveal: Reveal) {
+        if let Some(session) = &mut self.session {
+            session.reveal(reveal);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! How many points valid answers are worth.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::game::{Points, Rules};
+
+/// Extra points for answers using every letter.
+const PANGRAM_BONUS: Points = 7;
+
+/// How the points of an answer add up, so that frontends can explain them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct PointsBreakdown {
+    /// Points for the word itself, e.g. 1 for any NYT answer.
+    pub base: Points,
+    /// Extra points for letters beyond the minimum length.
+    pub length_bonus: Points,
+    /// Extra points for using every letter.
+    pub pangram_bonus: Points,
+}
+
+impl PointsBreakdown {
+    pub fn total(&self) -> Points {
+        self.base + self.length_bonus + self.pangram_bonus
+    }
+}
+
+/// E.g. `1 base + 3 length bonus + 7 pangram bonus`, leaving out empty bonuses.
+impl fmt::Display for PointsBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} base", self.base)?;
+        if self.length_bonus > 0 {
+            write!(f, " + {} length bonus", self.length_bonus)?;
+        }
+        if self.pangram_bonus > 0 {
+            write!(f, " + {} pangram bonus", self.pangram_bonus)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait Scoring: Send + Sync {
+    /// Points awarded for `word`, which is a valid answer under `rules`.
+    fn points(&self, word: &str, is_pangram: bool, rules: &Rules) -> PointsBreakdown;
+}
+
+/// The scoring of the NYT Spelling Bee.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NytScoring;
+
+impl Scoring for NytScoring {
+    fn points(&self, word: &str, is_pangram: bool, rules: &Rules) -> PointsBreakdown {
+        // How points are awarded:
+        // 4-letter words are worth 1 point each.
+        // Longer words earn 1 point per letter (this spec is slightly unclear here, but should be interpreted as: "earn 1 **extra** point for every letter other than the first 4").
+        // Each puzzle includes at least one "pangram" which uses every letter. These are worth 7 extra points!
+        PointsBreakdown {
+            base: 1,
+            length_bonus: word.chars().count() - rules.min_length,
+            pangram_bonus: pangram_bonus(is_pangram),
+        }
+    }
+}
+
+/// The sum of the letters' Scrabble tile values, plus the pangram bonus.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrabbleScoring;
+
+impl Scoring for ScrabbleScoring {
+    fn points(&self, word: &str, is_pangram: bool, _rules: &Rules) -> PointsBreakdown {
+        PointsBreakdown {
+            base: word.chars().map(tile_value).sum(),
+            length_bonus: 0,
+            pangram_bonus: pangram_bonus(is_pangram),
+        }
+    }
+}
+
+/// The squared number of letters, plus the pangram bonus, favouring long answers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LengthSquaredScoring;
+
+impl Scoring for LengthSquaredScoring {
+    fn points(&self, word: &str, is_pangram: bool, _rules: &Rules) -> PointsBreakdown {
+        PointsBreakdown {
+            base: word.chars().count().pow(2),
+            length_bonus: 0,
+            pangram_bonus: pangram_bonus(is_pangram),
+        }
+    }
+}
+
+fn pangram_bonus(is_pangram: bool) -> Points {
+    if is_pangram { PANGRAM_BONUS } else { 0 }
+}
+
+// letters without a tile (i.e. outside of A-Z) are worth nothing.
+fn tile_value(letter: char) -> Points {
+    match letter {
+        'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+        'D' | 'G' => 2,
+        'B' | 'C' | 'M' | 'P' => 3,
+        'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+        'K' => 5,
+        'J' | 'X' => 8,
+        'Q' | 'Z' => 10,
+        _ => 0,
+    }
+}
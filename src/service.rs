@@ -0,0 +1,88 @@
+//! Solving as a [`tower::Service`], to embed the solver in existing
+//! axum/hyper stacks and middleware chains. Puzzles are solved on tokio's
+//! blocking thread pool, so the service must be called within a tokio runtime.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use tower::{BoxError, Service, ServiceBuilder, limit::ConcurrencyLimit, timeout::Timeout};
+
+use crate::{
+    dictionary::Dictionary,
+    game::PuzzleSpec,
+    registry::RegisteredStrategy,
+    scoring::Scoring,
+    shared::{SharedResult, SharedSolver},
+};
+
+#[derive(Debug, Clone)]
+pub struct SolveRequest {
+    pub puzzle: PuzzleSpec,
+}
+
+impl From<PuzzleSpec> for SolveRequest {
+    fn from(puzzle: PuzzleSpec) -> Self {
+        SolveRequest { puzzle }
+    }
+}
+
+/// Solves puzzles with one solver, whose index is built once and shared by
+/// the clones of the service.
+#[derive(Clone)]
+pub struct SolveService {
+    solver: SharedSolver,
+}
+
+impl SolveService {
+    /// Build the index of `strategy` for `dict`, which the service and its
+    /// results keep alive.
+    pub fn new(
+        dict: Arc<Dictionary>,
+        strategy: &RegisteredStrategy,
+        threads: usize,
+        scoring: Arc<dyn Scoring>,
+    ) -> anyhow::Result<SolveService> {
+        Ok(SolveService {
+            solver: SharedSolver::new(dict, strategy, threads, scoring)?,
+        })
+    }
+
+    /// The service with at most `concurrency` puzzles solved at once, failing
+    /// solves taking longer than `timeout`. A timed out solve keeps its
+    /// blocking thread until it is done, but the caller doesn't wait for it.
+    pub fn limited(
+        self,
+        concurrency: usize,
+        timeout: Duration,
+    ) -> ConcurrencyLimit<Timeout<SolveService>> {
+        ServiceBuilder::new()
+            .concurrency_limit(concurrency)
+            .timeout(timeout)
+            .service(self)
+    }
+}
+
+impl Service<SolveRequest> for SolveService {
+    type Response = SharedResult;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    // solves are queued on the blocking thread pool, limit them with
+    // `limited` instead.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SolveRequest) -> Self::Future {
+        let solver = self.solver.clone();
+        Box::pin(async move {
+            let result = tokio::task::spawn_blocking(move || solver.solve(&request.puzzle)).await?;
+            Ok(result?)
+        })
+    }
+}
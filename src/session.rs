@@ -0,0 +1,201 @@
+//! A player's progress on a puzzle, saved as JSON so that it can be resumed
+//! later or on another machine.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annotate::{Annotations, FOUND},
+    date::PuzzleDate,
+    game::{Game, Letter, Word},
+};
+
+/// A kind of help the player asked for, which spoils part of the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reveal {
+    Hints,
+    Pangrams,
+    Answers,
+}
+
+/// How much of a remaining answer its escalated hint reveals, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintLevel {
+    Length,
+    FirstLetter,
+    FirstTwoLetters,
+    Definition,
+    Word,
+}
+
+impl HintLevel {
+    /// The level revealing more than this one, the word itself at the end.
+    pub fn next(self) -> HintLevel {
+        match self {
+            HintLevel::Length => HintLevel::FirstLetter,
+            HintLevel::FirstLetter => HintLevel::FirstTwoLetters,
+            HintLevel::FirstTwoLetters => HintLevel::Definition,
+            HintLevel::Definition | HintLevel::Word => HintLevel::Word,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub center_letter: Letter,
+    pub outer_letters: Vec<Letter>,
+    /// The date of an official puzzle, or the day the session started on
+    /// other puzzles. Missing in sessions saved before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<PuzzleDate>,
+    /// The tags of the answers, such as [`FOUND`] for the ones found so far.
+    pub annotations: Annotations,
+    /// The help asked for so far, in order.
+    pub reveals: Vec<Reveal>,
+    /// How far the hint of each answer was escalated, missing in sessions
+    /// saved before hints could be escalated.
+    #[serde(default)]
+    pub hint_levels: BTreeMap<Word, HintLevel>,
+    pub notes: Vec<String>,
+}
+
+impl Session {
+    /// A fresh session on `game`, without any progress.
+    pub fn new(game: &Game) -> Session {
+        let mut letters = game.letters();
+        Session {
+            // a `Game` always has a center letter.
+            center_letter: letters.next().expect("game has no letters"),
+            outer_letters: letters.collect(),
+            date: game
+                .metadata()
+                .map(|metadata| metadata.date)
+                .or_else(|| PuzzleDate::current().ok()),
+            annotations: Annotations::default(),
+            reveals: Vec::new(),
+            hint_levels: BTreeMap::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn game(&self) -> Game {
+        Game::new(self.center_letter, self.outer_letters.clone())
+    }
+
+    /// Record a found answer, returning whether it wasn't found yet.
+    pub fn find(&mut self, word: &str) -> bool {
+        self.annotations.tag(word, FOUND)
+    }
+
+    /// The answers found so far, sorted alphabetically.
+    pub fn found(&self) -> impl Iterator<Item = &Word> {
+        self.annotations.tagged(FOUND)
+    }
+
+    pub fn is_found(&self, word: &str) -> bool {
+        self.annotations.has_tag(word, FOUND)
+    }
+
+    /// Record asking for `reveal`, unless it was already revealed.
+    pub fn reveal(&mut self, reveal: Reveal) {
+        if !self.reveals.contains(&reveal) {
+            self.reveals.push(reveal);
+        }
+    }
+
+    /// Reveal more of `word`, starting with its length, returning the new
+    /// level of its hint.
+    pub fn escalate_hint(&mut self, word: &Word) -> HintLevel {
+        let level = self
+            .hint_level(word)
+            .map_or(HintLevel::Length, HintLevel::next);
+        self.hint_levels.insert(word.clone(), level);
+        level
+    }
+
+    pub fn hint_level(&self, word: &Word) -> Option<HintLevel> {
+        self.hint_levels.get(word).copied()
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Session> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("invalid session in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut out, self)?;
+        writeln!(out)?;
+        out.flush()
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// A directory keeping one session per puzzle and day, to aggregate a
+/// player's statistics over all of them.
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(dir: &Path) -> SessionStore {
+        SessionStore {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Save `session`, replacing the earlier state of the same session.
+    pub fn save(&self, session: &Session) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        session.save(&self.path(session))
+    }
+
+    /// All stored sessions, skipping files that aren't valid sessions.
+    pub fn load_all(&self) -> anyhow::Result<Vec<Session>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read {}", self.dir.display()));
+            }
+        };
+        let mut sessions = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            match Session::load(&path) {
+                Ok(session) => sessions.push(session),
+                Err(e) => log::warn!("skipping {}: {e:#}", path.display()),
+            }
+        }
+        Ok(sessions)
+    }
+
+    // e.g. `2024-06-01-CAEFILT.json`, `undated-CAEFILT.json` for old sessions.
+    fn path(&self, session: &Session) -> PathBuf {
+        let date = session
+            .date
+            .map_or_else(|| "undated".to_string(), |date| date.to_string());
+        let letters: String = std::iter::once(session.center_letter)
+            .chain(session.outer_letters.iter().copied())
+            .map(|letter| letter.to_string())
+            .collect();
+        self.dir.join(format!("{date}-{letters}.json"))
+    }
+}
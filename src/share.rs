@@ -0,0 +1,292 @@
+//! Puzzles from pasted text, such as shared messages or text recognized in
+//! screenshots. The parser is tolerant of case, spacing and punctuation, and
+//! understands these formats (tried in this order):
+//!
+//! - labelled letters: `Center: C, Letters: A L T E F I` (also `centre`,
+//!   `middle`, `outer` and either order)
+//! - a marked center letter: `[C] A L T E F I`, `(C) ALTEFI` or the forum
+//!   format `**C** A L T E F I`
+//! - the center letter alone on one side of a `/` or `+`: `L A T E F I / C`,
+//!   `c + altefi` or this crate's `C/ALTEFI`
+//! - a line of just the seven letters, center first: `CALTEFI` or `C ALTEFI`
+//!
+//! Letters shared as emoji, like 🇨 🇦 🇱, count as the letters they show and
+//! other emoji, like the 🐝 of share texts, are ignored. The letters found must
+//! make a NYT puzzle: a center letter and six other distinct letters.
+
+use std::cmp::Reverse;
+
+use anyhow::Context;
+
+use crate::game::{Game, InvalidPuzzle};
+
+// the regional indicator symbols 🇦 to 🇿, which show as letters on their own.
+const REGIONAL_INDICATORS: std::ops::RangeInclusive<char> = '\u{1F1E6}'..='\u{1F1FF}';
+// the characters separating the center letter from the others.
+const SEPARATORS: &[char] = &['/', '+'];
+
+const CENTER_LABELS: &[&str] = &[
+    "center letter",
+    "centre letter",
+    "middle letter",
+    "center",
+    "centre",
+    "middle",
+];
+const LETTERS_LABELS: &[&str] = &[
+    "outer letters",
+    "other letters",
+    "letters",
+    "outer",
+    "others",
+];
+// pairs of (opening, closing) markup around the center letter.
+const CENTER_MARKERS: &[(&str, &str)] = &[("**", "**"), ("*", "*"), ("[", "]"), ("(", ")")];
+
+/// Find the puzzle in `text`.
+pub fn parse_game(text: &str) -> anyhow::Result<Game> {
+    let text = &emoji_letters(text);
+    let game = if let Some(game) = labelled(text) {
+        game
+    } else if let Some(game) = text.lines().find_map(marked) {
+        game
+    } else if let Some(game) = text.lines().find_map(separated) {
+        game
+    } else {
+        text.lines().find_map(plain).context(
+            "found no puzzle letters in the text, expected e.g. 'Center: C, Letters: ALTEFI', \
+             'C + ALTEFI' or 'CALTEFI' with the center first",
+        )?
+    }?;
+    check_letters(&game)?;
+    Ok(game)
+}
+
+/// Whether `game` has six distinct letters besides its center letter.
+fn check_letters(game: &Game) -> Result<(), InvalidPuzzle> {
+    let mut seen = Vec::new();
+    for letter in game.letters() {
+        if seen.contains(&letter) {
+            // the center letter comes first.
+            return Err(if seen[0] == letter {
+                InvalidPuzzle::CenterInOuterLetters
+            } else {
+                InvalidPuzzle::DuplicateLetter(letter)
+            });
+        }
+        seen.push(letter);
+    }
+    match seen.len() - 1 {
+        6 => Ok(()),
+        count => Err(InvalidPuzzle::LetterCount(count)),
+    }
+}
+
+/// `text` with the letter emoji replaced by the letters they show.
+fn emoji_letters(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if REGIONAL_INDICATORS.contains(&c) {
+                let offset = c as u32 - *REGIONAL_INDICATORS.start() as u32;
+                // the range has exactly the 26 letters.
+                char::from(b'A' + offset as u8)
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// `Center: C, Letters: ALTEFI`, with the labels in any order.
+fn labelled(text: &str) -> Option<anyhow::Result<Game>> {
+    let lower = text.to_lowercase();
+    // lowercasing may change byte offsets of non-ASCII text, which can't be labelled.
+    if lower.len() != text.len() {
+        return None;
+    }
+    let center = find_label(&lower, CENTER_LABELS)?;
+    let letters = find_label(&lower, LETTERS_LABELS)?;
+
+    // a field ends where the other label starts or at the end of its line.
+    let field = |end: usize, other_start: usize| {
+        let line = text[end..].lines().next().unwrap_or_default();
+        let len = match other_start.checked_sub(end) {
+            Some(distance) => line.len().min(distance),
+            None => line.len(),
+        };
+        // unlike in the other formats, digits are reported rather than skipped.
+        line[..len]
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+    };
+    let center_letters = field(center.1, letters.0);
+    let outer_letters = field(letters.1, center.0);
+    let mut center = center_letters.chars();
+    let game = match (center.next(), center.next()) {
+        (Some(center), None) => Game::parse(&center.to_string(), &outer_letters),
+        _ => Err(anyhow::anyhow!(
+            "the center must be a single letter, got '{center_letters}'"
+        )),
+    };
+    Some(game)
+}
+
+/// The (start, end) byte offsets of the first of `labels` in `text`, preferring
+/// the longest label starting there.
+fn find_label(text: &str, labels: &[&str]) -> Option<(usize, usize)> {
+    labels
+        .iter()
+        .filter_map(|label| text.find(label).map(|start| (start, start + label.len())))
+        .min_by_key(|&(start, end)| (start, Reverse(end)))
+}
+
+/// `[C] ALTEFI`, with the center letter surrounded by one of the markers.
+fn marked(line: &str) -> Option<anyhow::Result<Game>> {
+    CENTER_MARKERS.iter().find_map(|(open, close)| {
+        let start = line.find(open)?;
+        let rest = &line[start + open.len()..];
+        let end = rest.find(close)?;
+        let center = rest[..end].trim();
+        let mut chars = center.chars();
+        let (Some(center), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+        if !center.is_alphabetic() {
+            return None;
+        }
+        let outer = alphabetic(&line[..start]) + &alphabetic(&rest[end + close.len()..]);
+        Some(Game::parse(&center.to_string(), &outer))
+    })
+}
+
+/// `ALTEFI / C` or `C + ALTEFI`, with the center letter alone on either side.
+fn separated(line: &str) -> Option<anyhow::Result<Game>> {
+    if line.chars().any(char::is_numeric) {
+        return None;
+    }
+    let (left, right) = line.split_once(SEPARATORS)?;
+    let (left, right) = (alphabetic(left), alphabetic(right));
+    // other slashes, like in `w/o`, are skipped.
+    match (left.chars().count(), right.chars().count()) {
+        (1, 2..) => Some(Game::parse(&left, &right)),
+        (2.., 1) => Some(Game::parse(&right, &left)),
+        (0, 6) | (6, 0) => Some(Err(InvalidPuzzle::MissingCenter.into())),
+        _ => None,
+    }
+}
+
+/// A line of just seven distinct letters, the center first.
+fn plain(line: &str) -> Option<anyhow::Result<Game>> {
+    if line.chars().any(char::is_numeric) {
+        return None;
+    }
+    let letters = alphabetic(line);
+    let mut chars: Vec<char> = letters.chars().collect();
+    if chars.len() != 7 {
+        return None;
+    }
+    chars.sort_unstable();
+    chars.dedup();
+    if chars.len() != 7 {
+        return None;
+    }
+    let mut letters = letters.chars();
+    let center = letters.next()?;
+    Some(Game::parse(&center.to_string(), letters.as_str()))
+}
+
+// the letters of `text`, dropping spaces, separators and other punctuation.
+fn alphabetic(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphabetic()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the center letter and the others, which `render` writes in the order given.
+    const CENTER: char = 'C';
+    const OUTER: [char; 6] = ['A', 'L', 'T', 'E', 'F', 'I'];
+
+    // the puzzle as shared in each format.
+    fn render() -> Vec<String> {
+        let spaced = OUTER.map(String::from).join(" ");
+        let joined: String = OUTER.iter().collect();
+        let emoji = |letter: char| {
+            char::from_u32(*REGIONAL_INDICATORS.start() as u32 + (letter as u32 - 'A' as u32))
+                .unwrap()
+        };
+        let emoji_outer: Vec<String> = OUTER.iter().map(|&l| emoji(l).to_string()).collect();
+        vec![
+            format!("Center: {CENTER}, Letters: {spaced}"),
+            format!("Outer letters: {joined}\nCentre letter: {CENTER}"),
+            format!(
+                "middle: {}, others: {}",
+                CENTER.to_ascii_lowercase(),
+                joined.to_lowercase()
+            ),
+            format!("[{CENTER}] {spaced}"),
+            format!("({CENTER}) {joined}"),
+            format!("**{CENTER}** {spaced}"),
+            format!("*{CENTER}* {spaced}"),
+            format!("{spaced} / {CENTER}"),
+            format!(
+                "{} + {}",
+                CENTER.to_ascii_lowercase(),
+                joined.to_lowercase()
+            ),
+            format!("{CENTER}/{joined}"),
+            format!("{CENTER}{joined}"),
+            format!("{CENTER} {spaced}"),
+            format!("{} {}", emoji(CENTER), emoji_outer.join(" ")),
+            format!(
+                "Spelling Bee 🐝 2024-01-02\n{} + {}\nI found 25 words!",
+                emoji(CENTER),
+                emoji_outer.join(" ")
+            ),
+        ]
+    }
+
+    #[test]
+    fn every_format_parses_back() {
+        for text in render() {
+            let game = parse_game(&text).unwrap_or_else(|e| panic!("{text}: {e}"));
+            let mut letters = game.letters();
+            assert_eq!(letters.next().map(char::from), Some(CENTER), "{text}");
+            let mut outer: Vec<char> = letters.map(char::from).collect();
+            let mut expected = OUTER.to_vec();
+            outer.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(outer, expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn malformed_text_is_rejected() {
+        for text in [
+            "",
+            "🐝",
+            "no puzzle in here",
+            "Center: CA, Letters: LTEFI",
+            "Center: C, Letters: ALTEF",
+            "Center: C, Letters: ALTEF1",
+            "[C] A L T E F F",
+            "ALTEFI / ",
+            "C/ALTEFIS",
+            "C + ALTEFC",
+            "C/ALTÉFI",
+        ] {
+            assert!(parse_game(text).is_err(), "{text:?} was accepted");
+        }
+    }
+
+    #[test]
+    fn text_without_letters_names_the_formats() {
+        let error = parse_game("no puzzle in here").unwrap_err().to_string();
+        assert!(
+            error.starts_with("found no puzzle letters in the text"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! A solver and results that own a reference count of their dictionary
+//! instead of borrowing it, so that they are `'static` and `Send + Sync`. This
+//! lets them live in long-lived state, e.g. of a server or a TUI, without
+//! leaking the dictionary.
+
+use std::sync::Arc;
+
+use crate::{
+    dictionary::Dictionary,
+    game::{AnswerOrder, GameResult, PuzzleSpec, SolveStats},
+    registry::{DynSolver, RegisteredStrategy},
+    scoring::Scoring,
+};
+
+/// A solver sharing its dictionary, cheap to clone.
+#[derive(Clone)]
+pub struct SharedSolver {
+    // borrows from `dict`, which is declared last so that it is dropped last.
+    solver: Arc<dyn DynSolver<'static>>,
+    dict: Arc<Dictionary>,
+}
+
+impl SharedSolver {
+    /// Build the index of `strategy` for `dict`, see [`RegisteredStrategy::build`].
+    pub fn new(
+        dict: Arc<Dictionary>,
+        strategy: &RegisteredStrategy,
+        threads: usize,
+        scoring: Arc<dyn Scoring>,
+    ) -> anyhow::Result<SharedSolver> {
+        // SAFETY: the dictionary is never mutated behind the `Arc` and lives as
+        // long as any clone of it: the one in the solver, and the ones in the
+        // results holding its words. The `'static` borrows never escape either:
+        // `build` works for any lifetime, so it can't keep the borrow anywhere
+        // else, and `SharedResult::result` shortens it to that of the result.
+        let words: &'static Dictionary = unsafe { &*Arc::as_ptr(&dict) };
+        Ok(SharedSolver {
+            solver: Arc::from((strategy.build)(words, threads, scoring)?),
+            dict,
+        })
+    }
+
+    pub fn dictionary(&self) -> &Arc<Dictionary> {
+        &self.dict
+    }
+
+    pub fn solve(&self, puzzle: &PuzzleSpec) -> anyhow::Result<SharedResult> {
+        Ok(self.solve_with_stats(puzzle)?.0)
+    }
+
+    pub fn solve_with_stats(
+        &self,
+        puzzle: &PuzzleSpec,
+    ) -> anyhow::Result<(SharedResult, SolveStats)> {
+        let (result, stats) = self.solver.solve_with_stats(puzzle)?;
+        let result = SharedResult {
+            result,
+            dict: Arc::clone(&self.dict),
+        };
+        Ok((result, stats))
+    }
+}
+
+/// A [`GameResult`] keeping the dictionary its words belong to alive.
+#[derive(Clone)]
+pub struct SharedResult {
+    result: GameResult<'static>,
+    dict: Arc<Dictionary>,
+}
+
+impl SharedResult {
+    /// The result, whose words are borrowed from `self` rather than the
+    /// dictionary, as they only live as long as some result or solver.
+    pub fn result(&self) -> &GameResult<'_> {
+        &self.result
+    }
+
+    /// See [`GameResult::with_order`].
+    pub fn with_order(self, order: AnswerOrder) -> Self {
+        SharedResult {
+            result: self.result.with_order(order),
+            dict: self.dict,
+        }
+    }
+
+    pub fn dictionary(&self) -> &Arc<Dictionary> {
+        &self.dict
+    }
+}
+
+// long-lived state such as a tokio task requires both.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<SharedSolver>();
+    assert_send_sync::<SharedResult>();
+};
+
+// the borrows of the dictionary are checked by running these under Miri,
+// without the thread pool, whose epochs Miri rejects:
+// `cargo +nightly miri test --lib --no-default-features shared`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{Game, Word},
+        registry::StrategyRegistry,
+        scoring::NytScoring,
+    };
+
+    fn solver(strategy: &RegisteredStrategy) -> SharedSolver {
+        let dict: Dictionary = ["FACET", "CAFE", "LATTICE", "TALL", "FELICITATE"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        SharedSolver::new(Arc::new(dict), strategy, 1, Arc::new(NytScoring)).unwrap()
+    }
+
+    fn puzzle() -> PuzzleSpec {
+        PuzzleSpec::from(Game::parse("C", "ALTEFI").unwrap())
+    }
+
+    fn words(result: &SharedResult) -> Vec<Word> {
+        let result = result.result();
+        result
+            .entries()
+            .into_iter()
+            .map(|(word, _)| word.clone())
+            .collect()
+    }
+
+    #[test]
+    fn results_outlive_their_solver() {
+        for strategy in StrategyRegistry::default().iter() {
+            let solver = solver(strategy);
+            let result = solver.solve(&puzzle()).unwrap();
+            let copy = result.clone();
+            drop(solver);
+            let expected: &[&str] = if strategy.pangrams_only {
+                &["FELICITATE"]
+            } else {
+                &["CAFE", "FACET", "FELICITATE", "LATTICE"]
+            };
+            assert_eq!(words(&result), expected, "{}", strategy.name);
+            drop(result);
+            assert_eq!(words(&copy), expected, "{}", strategy.name);
+        }
+    }
+
+    #[test]
+    fn results_outlive_their_solver_on_other_threads() {
+        let registry = StrategyRegistry::default();
+        let solver = solver(registry.find("letter-map").unwrap());
+        let result = std::thread::spawn(move || solver.solve(&puzzle()).unwrap())
+            .join()
+            .unwrap();
+        let ordered = result.with_order(AnswerOrder::PointsDesc);
+        assert_eq!(words(&ordered)[0], "FELICITATE");
+    }
+}
@@ -0,0 +1,80 @@
+//! Printable puzzle sheets for solving on paper: the hive, the hints and a blank
+//! line per answer, as an HTML page.
+
+use std::fmt::Write;
+
+use crate::{
+    game::{GameProcessed, GameResult},
+    hints::Hints,
+    render,
+};
+
+const STYLE: &str = "
+body { font-family: sans-serif; max-width: 48em; margin: 2em auto; }
+h1 { text-align: center; }
+.hive { display: block; width: 14em; margin: 0 auto; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #999; padding: 0.2em 0.6em; text-align: right; }
+.answers { columns: 3; list-style: none; padding: 0; }
+.answers li { border-bottom: 1px solid #333; height: 2em; margin-right: 2em; break-inside: avoid; }
+@media print { body { margin: 0; } }
+";
+
+/// A page titled `title` with the hive, the hints about `result` and as many
+/// blank answer lines as it has answers.
+pub fn sheet_html(game: &GameProcessed, result: &GameResult, title: &str) -> String {
+    let hints = Hints::new(result);
+    let grid = result.grid();
+    let title = escape(title);
+
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>\n<html>\n<head>").unwrap();
+    writeln!(html, r#"<meta charset="utf-8">"#).unwrap();
+    writeln!(html, "<title>{title}</title>\n<style>{STYLE}</style>").unwrap();
+    writeln!(html, "</head>\n<body>\n<h1>{title}</h1>").unwrap();
+
+    // the SVG scales with the width given by the stylesheet.
+    let svg = render::hive_svg(game).replacen("<svg ", r#"<svg class="hive" "#, 1);
+    html.push_str(&svg);
+
+    let mut summary = String::new();
+    hints.write_summary(&mut summary).unwrap();
+    writeln!(html, "<p>{}</p>", escape(summary.trim_end())).unwrap();
+
+    let lengths = grid.lengths();
+    html.push_str("<table>\n<tr><th></th>");
+    for length in &lengths {
+        write!(html, "<th>{length}</th>").unwrap();
+    }
+    html.push_str("<th>Σ</th></tr>\n");
+    for letter in grid.letters() {
+        write!(html, "<tr><th>{letter}</th>").unwrap();
+        for &length in &lengths {
+            match grid.count(letter, length) {
+                0 => html.push_str("<td>-</td>"),
+                count => write!(html, "<td>{count}</td>").unwrap(),
+            }
+        }
+        writeln!(html, "<td>{}</td></tr>", grid.row_total(letter)).unwrap();
+    }
+    html.push_str("<tr><th>Σ</th>");
+    for &length in &lengths {
+        write!(html, "<td>{}</td>", grid.column_total(length)).unwrap();
+    }
+    writeln!(html, "<td>{}</td></tr>\n</table>", grid.total()).unwrap();
+
+    let mut two_letter_list = String::new();
+    hints.write_two_letter_list(&mut two_letter_list).unwrap();
+    writeln!(html, "<pre>{}</pre>", escape(&two_letter_list)).unwrap();
+
+    html.push_str(r#"<ol class="answers">"#);
+    html.push_str(&"<li></li>".repeat(grid.total()));
+    html.push_str("</ol>\n</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,115 @@
+//! Well-known word lists that can be downloaded by name, such as ENABLE, and
+//! are cached locally after the first download.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use jiff::Timestamp;
+
+use crate::{dictionary::DictionaryMetadata, fetch::HttpFetcher};
+
+/// A downloadable word list.
+#[derive(Debug, Clone, Copy)]
+pub struct DictionarySource {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub url: &'static str,
+    /// Under which terms the list may be used.
+    pub license: &'static str,
+    // the downloaded text -> one word per line.
+    extract: fn(&str) -> String,
+}
+
+const SOURCES: &[DictionarySource] = &[
+    DictionarySource {
+        name: "enable",
+        description: "Enhanced North American Benchmark Lexicon, used by many word games.",
+        url: "https://raw.githubusercontent.com/dolph/dictionary/master/enable1.txt",
+        license: "public domain",
+        extract: str::to_string,
+    },
+    DictionarySource {
+        name: "sowpods",
+        description: "Collins Scrabble Words, the tournament list outside North America.",
+        url: "https://raw.githubusercontent.com/jesstess/Scrabble/master/scrabble/sowpods.txt",
+        license: "copyright HarperCollins, for personal use",
+        extract: str::to_string,
+    },
+    DictionarySource {
+        name: "twl06",
+        description: "Tournament Word List 2006, the North American Scrabble list.",
+        url: "https://raw.githubusercontent.com/cviebrock/wordlists/master/TWL06.txt",
+        license: "copyright Hasbro, for personal use",
+        extract: str::to_string,
+    },
+    DictionarySource {
+        name: "12dicts",
+        description: "2of12inf from 12dicts, common words found in at least two of twelve dictionaries.",
+        url: "https://raw.githubusercontent.com/en-wl/wordlist/master/alt12dicts/2of12inf.txt",
+        license: "public domain",
+        extract: strip_12dicts_markers,
+    },
+];
+
+impl DictionarySource {
+    /// The built-in sources.
+    pub fn all() -> &'static [DictionarySource] {
+        SOURCES
+    }
+
+    pub fn find(name: &str) -> Option<&'static DictionarySource> {
+        SOURCES.iter().find(|source| source.name == name)
+    }
+
+    /// The word list with one word per line, from the text at `url`.
+    pub fn extract(&self, text: &str) -> String {
+        (self.extract)(text)
+    }
+
+    /// The path of the word list in `cache_dir`, which is downloaded first
+    /// unless it was already.
+    pub fn fetch(&self, http: &impl HttpFetcher, cache_dir: &Path) -> anyhow::Result<PathBuf> {
+        let path = cache_dir.join(format!("{}.txt", self.name));
+        if path.exists() {
+            log::info!("using cached {} word list {}", self.name, path.display());
+            return Ok(path);
+        }
+        let text = http.get_text(self.url)?;
+        std::fs::create_dir_all(cache_dir)
+            .with_context(|| format!("failed to create {}", cache_dir.display()))?;
+        std::fs::write(&path, self.extract(&text))
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        log::info!(
+            "downloaded the {} word list ({}) to {}",
+            self.name,
+            self.license,
+            path.display()
+        );
+        Ok(path)
+    }
+
+    /// The provenance of the word list at `path`, as downloaded by `fetch`,
+    /// which was fetched when the file was last modified.
+    pub fn metadata(&self, path: &Path) -> DictionaryMetadata {
+        let fetched_at = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| Timestamp::try_from(modified).ok());
+        DictionaryMetadata {
+            source: Some(self.url.to_string()),
+            fetched_at,
+            license: Some(self.license.to_string()),
+            word_count: 0,
+        }
+    }
+}
+
+/// 12dicts marks words with trailing symbols, like `%` for inflections
+/// that aren't in every dictionary.
+fn strip_12dicts_markers(text: &str) -> String {
+    text.lines()
+        .map(|line| line.trim_end_matches(|c: char| !c.is_alphabetic()))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{word}\n"))
+        .collect()
+}
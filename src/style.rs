@@ -0,0 +1,108 @@
+//! Colors of the terminal output. Styles are always written, and stripped by
+//! `anstream` unless `--color` and the terminal allow them.
+
+use std::str::FromStr;
+
+use anstyle::{AnsiColor, Style};
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ColorMode {
+    /// Color if stdout is a terminal and `NO_COLOR` isn't set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl From<ColorMode> for anstream::ColorChoice {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Auto => anstream::ColorChoice::Auto,
+            ColorMode::Always => anstream::ColorChoice::Always,
+            ColorMode::Never => anstream::ColorChoice::Never,
+        }
+    }
+}
+
+/// The styles of the highlighted parts of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub pangram: Style,
+    /// Answers the player already found.
+    pub found: Style,
+    pub header: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            // gold.
+            pangram: AnsiColor::Yellow.on_default().bold(),
+            found: Style::new().dimmed(),
+            header: Style::new().bold(),
+        }
+    }
+}
+
+/// Overrides of the default theme, e.g. `pangram=bright-magenta bold,found=italic`.
+/// Each part's style is its effects (`bold`, `dim`, `italic`, `underline`) and
+/// at most one color (`red`, or `bright-red` and so on), or `plain`.
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut theme = Theme::default();
+        for part in spec.split(',').filter(|part| !part.trim().is_empty()) {
+            let (name, style) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected `part=style`, got `{}`", part.trim()))?;
+            let style = parse_style(style)?;
+            match name.trim() {
+                "pangram" => theme.pangram = style,
+                "found" => theme.found = style,
+                "header" => theme.header = style,
+                name => {
+                    return Err(format!(
+                        "unknown part `{name}`, expected pangram, found or header"
+                    ));
+                }
+            }
+        }
+        Ok(theme)
+    }
+}
+
+fn parse_style(spec: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+    for word in spec.split_whitespace() {
+        style = match word {
+            "plain" => style,
+            "bold" => style.bold(),
+            "dim" => style.dimmed(),
+            "italic" => style.italic(),
+            "underline" => style.underline(),
+            color => style.fg_color(Some(parse_color(color)?.into())),
+        };
+    }
+    Ok(style)
+}
+
+fn parse_color(name: &str) -> Result<AnsiColor, String> {
+    let (bright, base) = match name.strip_prefix("bright-") {
+        Some(base) => (true, base),
+        None => (false, name),
+    };
+    let color = match base {
+        "black" => AnsiColor::Black,
+        "red" => AnsiColor::Red,
+        "green" => AnsiColor::Green,
+        "yellow" => AnsiColor::Yellow,
+        "blue" => AnsiColor::Blue,
+        "magenta" => AnsiColor::Magenta,
+        "cyan" => AnsiColor::Cyan,
+        "white" => AnsiColor::White,
+        _ => return Err(format!("unknown style `{name}`")),
+    };
+    Ok(color.bright(bright))
+}
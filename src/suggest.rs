@@ -0,0 +1,36 @@
+//! Suggestions for guesses that are not in the dictionary.
+
+use crate::game::{GameResult, Word};
+
+/// The answers within edit distance 1 of `guess` (one letter inserted,
+/// deleted or replaced), in alphabetical order.
+pub fn near_misses<'a>(guess: &str, result: &GameResult<'a>) -> Vec<&'a Word> {
+    let guess: Vec<char> = guess.trim().to_uppercase().chars().collect();
+    result
+        .entries()
+        .into_iter()
+        .map(|(word, _)| word)
+        .filter(|word| {
+            let word: Vec<char> = word.chars().collect();
+            within_one_edit(&guess, &word)
+        })
+        .collect()
+}
+
+fn within_one_edit(a: &[char], b: &[char]) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if longer.len() - shorter.len() > 1 {
+        return false;
+    }
+    let prefix = shorter
+        .iter()
+        .zip(longer)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if shorter.len() == longer.len() {
+        // at most one replaced letter, but identical words are no edit.
+        prefix < shorter.len() && shorter[prefix + 1..] == longer[prefix + 1..]
+    } else {
+        shorter[prefix..] == longer[prefix + 1..]
+    }
+}
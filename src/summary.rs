@@ -0,0 +1,103 @@
+//! Summary statistics of a result: how long the answers are and what they're
+//! worth on average.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::Serialize;
+
+use crate::game::{GameResult, Word};
+
+// the longest bar of the length histogram, in characters.
+const MAX_BAR: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultSummary<'a> {
+    /// The number of answers by length, in ascending order of length.
+    pub lengths: BTreeMap<usize, usize>,
+    /// 0 without answers.
+    pub average_points: f64,
+    /// The longest answers, several if they are equally long, alphabetically.
+    pub longest: Vec<&'a Word>,
+    /// The minimum length of answers of the rules.
+    pub min_length: usize,
+    /// The number of answers of the minimum length.
+    pub shortest_words: usize,
+}
+
+impl<'a> GameResult<'a> {
+    pub fn summary(&self) -> ResultSummary<'a> {
+        let entries = self.entries();
+        let mut lengths: BTreeMap<usize, usize> = BTreeMap::new();
+        for (word, _) in &entries {
+            *lengths.entry(word.chars().count()).or_default() += 1;
+        }
+        let average_points = if entries.is_empty() {
+            0.0
+        } else {
+            self.total_points() as f64 / entries.len() as f64
+        };
+        let max_length = lengths.keys().next_back().copied().unwrap_or(0);
+        let mut longest: Vec<&Word> = entries
+            .iter()
+            .filter(|(word, _)| word.chars().count() == max_length)
+            .map(|(word, _)| *word)
+            .collect();
+        longest.sort_unstable();
+
+        ResultSummary {
+            average_points,
+            longest,
+            min_length: self.min_length(),
+            shortest_words: lengths.get(&self.min_length()).copied().unwrap_or(0),
+            lengths,
+        }
+    }
+}
+
+/// A histogram of the lengths followed by the other statistics, e.g.
+/// ```text
+/// 4 letters  ##########  10
+/// 5 letters  ######       6
+/// 8 letters  #            1
+/// 3.12 points per word, longest FACETIAE (8), 10 4-letter words
+/// ```
+impl fmt::Display for ResultSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self.lengths.values().copied().max().unwrap_or(0);
+        let count_width = most.to_string().len();
+        let label_width = self
+            .lengths
+            .keys()
+            .next_back()
+            .map_or(1, |length| length.to_string().len());
+        for (length, count) in &self.lengths {
+            // scaled down for puzzles with many answers of one length.
+            let bar = if most > MAX_BAR {
+                (count * MAX_BAR).div_ceil(most)
+            } else {
+                *count
+            };
+            writeln!(
+                f,
+                "{length:>label_width$} letters  {:<bar_width$}  {count:>count_width$}",
+                "#".repeat(bar),
+                bar_width = most.min(MAX_BAR)
+            )?;
+        }
+
+        write!(f, "{:.2} points per word, longest ", self.average_points)?;
+        match self.longest.first() {
+            Some(first) => {
+                let words: Vec<&str> = self.longest.iter().map(|word| word.as_str()).collect();
+                write!(f, "{} ({})", words.join(", "), first.chars().count())?;
+            }
+            None => write!(f, "none")?,
+        }
+        let plural = if self.shortest_words == 1 { "" } else { "s" };
+        writeln!(
+            f,
+            ", {} {}-letter word{plural}",
+            self.shortest_words, self.min_length
+        )
+    }
+}
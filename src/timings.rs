@@ -0,0 +1,185 @@
+//! How long each phase of a command took, collected while it runs and
+//! reported once at the end, as a table or as JSON for other tools.
+
+use std::{
+    fmt,
+    io::Write,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// The phases of solving a puzzle, in the order they usually run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// Downloading puzzles or word lists.
+    Fetch,
+    /// Reading and parsing a word list.
+    Parse,
+    /// Building a strategy's index of the dictionary.
+    Index,
+    Solve,
+    /// Looking up definitions and frequencies of the answers.
+    Enrich,
+    /// Writing the output.
+    Format,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Fetch => "fetch",
+            Phase::Parse => "parse",
+            Phase::Index => "index",
+            Phase::Solve => "solve",
+            Phase::Enrich => "enrich",
+            Phase::Format => "format",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum TimingsFormat {
+    /// A table on stderr.
+    #[default]
+    Text,
+    /// A JSON object on stderr.
+    Json,
+}
+
+/// One timed step of a phase.
+#[derive(Debug, Clone, Serialize)]
+pub struct Timing {
+    pub phase: Phase,
+    /// What was done, e.g. the name of the strategy.
+    pub label: String,
+    /// When the step started, relative to the start of the command.
+    pub start_ms: f64,
+    pub ms: f64,
+}
+
+/// The timings of one command. Steps may be timed from several threads.
+#[derive(Debug)]
+pub struct Timings {
+    start: Instant,
+    /// The number of threads of the parallel strategies.
+    threads: usize,
+    steps: Mutex<Vec<Timing>>,
+}
+
+impl Timings {
+    pub fn new(threads: usize) -> Timings {
+        Timings {
+            start: Instant::now(),
+            threads,
+            steps: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Run `f` and record how long it took as a step of `phase`.
+    pub fn time<T>(&self, phase: Phase, label: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.record(phase, label, start, start.elapsed());
+        value
+    }
+
+    fn record(&self, phase: Phase, label: &str, start: Instant, duration: Duration) {
+        let timing = Timing {
+            phase,
+            label: label.to_string(),
+            start_ms: millis(start - self.start),
+            ms: millis(duration),
+        };
+        self.steps.lock().expect("timings poisoned").push(timing);
+    }
+
+    /// Write the timings recorded so far.
+    pub fn write(&self, format: TimingsFormat, out: &mut impl Write) -> anyhow::Result<()> {
+        match format {
+            TimingsFormat::Text => write!(out, "{self}")?,
+            TimingsFormat::Json => {
+                serde_json::to_writer(&mut *out, &self.report())?;
+                writeln!(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn report(&self) -> TimingsReport {
+        let mut steps = self.steps.lock().expect("timings poisoned").clone();
+        steps.sort_by(|a, b| a.start_ms.total_cmp(&b.start_ms));
+        TimingsReport {
+            threads: self.threads,
+            total_ms: millis(self.start.elapsed()),
+            steps,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TimingsReport {
+    threads: usize,
+    /// The wall time of the whole command, including untimed work.
+    total_ms: f64,
+    /// In the order they started.
+    steps: Vec<Timing>,
+}
+
+/// A table of the steps in the order they started, followed by the total of
+/// each phase, e.g.
+/// ```text
+/// phase   step                      ms
+/// parse   load dictionary       84.312
+/// index   rarest-letter-map     11.907
+/// solve   rarest-letter-map      0.412
+/// ```
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let report = self.report();
+        let width = report
+            .steps
+            .iter()
+            .map(|step| step.label.len())
+            .chain(["step".len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:<6}  {:<width$}  {:>10}", "phase", "step", "ms")?;
+        for step in &report.steps {
+            writeln!(
+                f,
+                "{:<6}  {:<width$}  {:>10.3}",
+                step.phase, step.label, step.ms
+            )?;
+        }
+
+        let mut phases: Vec<(Phase, f64)> = Vec::new();
+        for step in &report.steps {
+            match phases.iter_mut().find(|(phase, _)| *phase == step.phase) {
+                Some((_, ms)) => *ms += step.ms,
+                None => phases.push((step.phase, step.ms)),
+            }
+        }
+        phases.sort_by_key(|(phase, _)| *phase);
+        writeln!(f)?;
+        for (phase, ms) in phases {
+            writeln!(f, "{:<6}  {:<width$}  {ms:>10.3}", phase, "total")?;
+        }
+        let plural = if report.threads == 1 { "" } else { "s" };
+        writeln!(
+            f,
+            "{:<6}  {:<width$}  {:>10.3}",
+            "wall",
+            format!("{} thread{plural}", report.threads),
+            report.total_ms
+        )
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
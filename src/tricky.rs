@@ -0,0 +1,127 @@
+//! Heuristics for how likely a human solver is to miss an answer.
+
+use std::{collections::HashMap, io::Write};
+
+use crate::{
+    dictionary::Dictionary,
+    frequency::WordFrequencies,
+    game::{GameResult, Word},
+};
+
+// weights of the individual heuristics, reasons are only given for clear signals.
+const OBSCURITY_WEIGHT: f64 = 3.0;
+const OBSCURITY_REASON_THRESHOLD: f64 = 0.6;
+const DOUBLE_LETTER_WEIGHT: f64 = 1.0;
+const LETTER_REUSE_WEIGHT: f64 = 2.0;
+const LETTER_REUSE_REASON_THRESHOLD: f64 = 0.3;
+const UNUSUAL_START_WEIGHT: f64 = 1.5;
+const UNUSUAL_START_REASON_THRESHOLD: f64 = 0.5;
+
+pub struct TrickyWord<'a> {
+    pub word: &'a Word,
+    pub score: f64,
+    pub reasons: Vec<&'static str>,
+}
+
+pub struct TrickinessScorer<'a> {
+    frequencies: Option<&'a WordFrequencies>,
+    // how many dictionary words start with each two letter prefix.
+    prefix_counts: HashMap<&'a str, usize>,
+    max_prefix_count: usize,
+}
+
+impl<'a> TrickinessScorer<'a> {
+    pub fn new(dict: &'a Dictionary, frequencies: Option<&'a WordFrequencies>) -> Self {
+        let mut prefix_counts: HashMap<&str, usize> = HashMap::new();
+        for word in dict.iter() {
+            *prefix_counts.entry(prefix(word)).or_default() += 1;
+        }
+        let max_prefix_count = prefix_counts.values().copied().max().unwrap_or(0);
+        TrickinessScorer {
+            frequencies,
+            prefix_counts,
+            max_prefix_count,
+        }
+    }
+
+    pub fn score<'w>(&self, word: &'w Word) -> TrickyWord<'w> {
+        let mut score = 0.0;
+        let mut reasons = Vec::new();
+
+        if let Some(frequencies) = self.frequencies {
+            let obscurity = frequencies.obscurity(word);
+            score += OBSCURITY_WEIGHT * obscurity;
+            if obscurity > OBSCURITY_REASON_THRESHOLD {
+                reasons.push("rare word");
+            }
+        }
+
+        let letters: Vec<char> = word.chars().collect();
+        if letters.windows(2).any(|pair| pair[0] == pair[1]) {
+            score += DOUBLE_LETTER_WEIGHT;
+            reasons.push("double letter");
+        }
+
+        let mut distinct = letters.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        let reuse = 1.0 - distinct.len() as f64 / letters.len() as f64;
+        score += LETTER_REUSE_WEIGHT * reuse;
+        if reuse >= LETTER_REUSE_REASON_THRESHOLD {
+            reasons.push("reuses letters");
+        }
+
+        let prefix_count = self.prefix_counts.get(prefix(word)).copied().unwrap_or(0);
+        let unusual_start =
+            1.0 - ((prefix_count + 1) as f64).ln() / ((self.max_prefix_count + 1) as f64).ln();
+        score += UNUSUAL_START_WEIGHT * unusual_start;
+        if unusual_start > UNUSUAL_START_REASON_THRESHOLD {
+            reasons.push("unusual start");
+        }
+
+        TrickyWord {
+            word,
+            score,
+            reasons,
+        }
+    }
+
+    /// All answers, the ones most likely to be missed first.
+    pub fn rank<'w>(&self, result: &GameResult<'w>) -> Vec<TrickyWord<'w>> {
+        let mut ranked: Vec<_> = result
+            .entries()
+            .into_iter()
+            .map(|(word, _)| self.score(word))
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.word.cmp(b.word)));
+        ranked
+    }
+}
+
+fn prefix(word: &str) -> &str {
+    let end = word.char_indices().nth(2).map_or(word.len(), |(i, _)| i);
+    &word[..end]
+}
+
+pub fn write_tricky(
+    ranked: &[TrickyWord],
+    limit: usize,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    writeln!(out, "\nTricky words (most likely missed first):")?;
+    let shown = &ranked[..limit.min(ranked.len())];
+    let width = shown
+        .iter()
+        .map(|tricky| tricky.word.len())
+        .max()
+        .unwrap_or(0);
+    for tricky in shown {
+        writeln!(
+            out,
+            "  {:<width$}  {}",
+            tricky.word,
+            tricky.reasons.join(", ")
+        )?;
+    }
+    Ok(())
+}
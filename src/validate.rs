@@ -0,0 +1,212 @@
+//! Checks whether a (custom) puzzle satisfies the constraints of NYT puzzles.
+
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    alphabet::{LetterSet, MAX_LETTERS},
+    game::{GameResult, Letter, PangramIndex, PuzzleSpec, Word},
+};
+
+pub struct ValidationOptions {
+    /// Letters NYT puzzles never contain, by default just `S`.
+    pub forbidden_letters: Vec<Letter>,
+    pub min_answers: usize,
+    pub max_answers: usize,
+}
+
+/// The constraints of NYT puzzles: no `S` and 20 to 80 answers.
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            forbidden_letters: vec![Letter::new('S').expect("S is a letter")],
+            min_answers: 20,
+            max_answers: 80,
+        }
+    }
+}
+
+struct Check {
+    passed: bool,
+    description: String,
+}
+
+pub struct ValidationReport {
+    checks: Vec<Check>,
+    // unknown if the letters are too invalid to solve the puzzle.
+    has_pangram: Option<bool>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Whether the puzzle was solved and has no pangram.
+    pub fn lacks_pangram(&self) -> bool {
+        self.has_pangram == Some(false)
+    }
+
+    fn check(&mut self, passed: bool, description: String) {
+        self.checks.push(Check {
+            passed,
+            description,
+        });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "ok  " } else { "FAIL" };
+            writeln!(f, "[{status}] {}", check.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the letters of the puzzle, and if they can be solved, its solution.
+/// `solve` is only called if the letters are valid.
+pub fn validate<'a>(
+    puzzle: &PuzzleSpec,
+    options: &ValidationOptions,
+    solve: impl FnOnce() -> anyhow::Result<GameResult<'a>>,
+) -> anyhow::Result<ValidationReport> {
+    let mut report = ValidationReport {
+        checks: Vec::new(),
+        has_pangram: None,
+    };
+    let letters: Vec<Letter> = puzzle.game().letters().collect();
+
+    report.check(
+        letters.len() == 7,
+        if letters.len() == 7 {
+            "has 7 letters".to_string()
+        } else {
+            format!("has {} letters, expected 7", letters.len())
+        },
+    );
+
+    let distinct: HashSet<_> = letters.iter().collect();
+    report.check(
+        distinct.len() == letters.len(),
+        if distinct.len() == letters.len() {
+            "all letters are distinct".to_string()
+        } else {
+            "some letters occur more than once".to_string()
+        },
+    );
+
+    let forbidden: String = letters
+        .iter()
+        .filter(|letter| options.forbidden_letters.contains(letter))
+        .map(|&letter| char::from(letter))
+        .collect();
+    report.check(
+        forbidden.is_empty(),
+        if forbidden.is_empty() {
+            "contains no forbidden letters".to_string()
+        } else {
+            format!("contains forbidden letters {forbidden}")
+        },
+    );
+
+    // the solution is meaningless if letters occur more than once.
+    if distinct.len() != letters.len() {
+        return Ok(report);
+    }
+    let result = solve()?;
+    let entries = result.entries();
+
+    let pangrams: Vec<&str> = entries
+        .iter()
+        .filter(|(_, score)| score.is_pangram)
+        .map(|(word, _)| word.as_str())
+        .collect();
+    report.has_pangram = Some(!pangrams.is_empty());
+    report.check(
+        !pangrams.is_empty(),
+        if pangrams.is_empty() {
+            "has no pangram".to_string()
+        } else {
+            format!("has pangrams {}", pangrams.join(", "))
+        },
+    );
+
+    report.check(
+        (options.min_answers..=options.max_answers).contains(&entries.len()),
+        format!(
+            "has {} answers, expected between {} and {}",
+            entries.len(),
+            options.min_answers,
+            options.max_answers
+        ),
+    );
+
+    Ok(report)
+}
+
+/// Replacing an outer letter of a puzzle by another letter, which gives the
+/// puzzle pangrams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PangramSwap<'a> {
+    pub replaced: Letter,
+    pub replacement: Letter,
+    /// Alphabetically.
+    pub pangrams: Vec<&'a Word>,
+}
+
+/// e.g. `replace I with R: FRACTAL`
+impl fmt::Display for PangramSwap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pangrams: Vec<&str> = self.pangrams.iter().map(|word| word.as_str()).collect();
+        write!(
+            f,
+            "replace {} with {}: {}",
+            self.replaced,
+            self.replacement,
+            pangrams.join(", ")
+        )
+    }
+}
+
+/// The swaps of one outer letter for another letter that isn't forbidden which
+/// give the puzzle a pangram, those with the most pangrams first. Each swapped
+/// set of letters is looked up in `index` instead of solving the puzzle.
+pub fn nearest_pangrams<'a>(
+    puzzle: &PuzzleSpec,
+    options: &ValidationOptions,
+    index: &PangramIndex<'a>,
+) -> Vec<PangramSwap<'a>> {
+    let letters: LetterSet = puzzle.game().letters().collect();
+    let replacements: Vec<Letter> = (0..MAX_LETTERS as u8)
+        .filter_map(Letter::from_index)
+        .filter(|&letter| !letters.contains(letter) && !options.forbidden_letters.contains(&letter))
+        .collect();
+
+    let mut swaps = Vec::new();
+    // the center letter comes first.
+    for replaced in puzzle.game().letters().skip(1) {
+        let rest = letters.difference(LetterSet::from_iter([replaced]));
+        for &replacement in &replacements {
+            let mut pangrams = index
+                .words_with_letters(rest.union(LetterSet::from_iter([replacement])))
+                .to_vec();
+            if pangrams.is_empty() {
+                continue;
+            }
+            pangrams.sort_unstable();
+            swaps.push(PangramSwap {
+                replaced,
+                replacement,
+                pangrams,
+            });
+        }
+    }
+    swaps.sort_by(|a, b| {
+        b.pangrams
+            .len()
+            .cmp(&a.pangrams.len())
+            .then((a.replaced, a.replacement).cmp(&(b.replaced, b.replacement)))
+    });
+    swaps
+}
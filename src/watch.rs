@@ -0,0 +1,53 @@
+//! Waiting for a file to change, by polling its modification time and size.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct FileWatcher {
+    path: PathBuf,
+    /// The file's modification time and size when last checked, `None` if it
+    /// didn't exist.
+    stamp: Option<(SystemTime, u64)>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path) -> FileWatcher {
+        FileWatcher {
+            path: path.to_path_buf(),
+            stamp: stamp(path),
+        }
+    }
+
+    /// Block until the file was modified, created or removed and stayed
+    /// unchanged for one poll interval, so that an editor's save is complete.
+    pub fn wait(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = stamp(&self.path);
+            if current != self.stamp {
+                self.stamp = current;
+                break;
+            }
+        }
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = stamp(&self.path);
+            if current == self.stamp {
+                return;
+            }
+            self.stamp = current;
+        }
+    }
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
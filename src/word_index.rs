@@ -0,0 +1,159 @@
+//! A prefix trie of the dictionary's words, for frontends autocompleting
+//! guesses as they are typed: the words starting with a prefix, how many there
+//! are, and those made only of a puzzle's letters.
+
+use crate::{
+    alphabet::LetterSet,
+    dictionary::Dictionary,
+    game::{Letter, Word},
+};
+
+// words are spelled from the root, one letter per edge.
+const ROOT: usize = 0;
+
+#[derive(Debug, Default)]
+struct Node<'a> {
+    // sorted by letter.
+    children: Vec<(Letter, usize)>,
+    // the word spelled by the path to this node, if it is one.
+    word: Option<&'a Word>,
+    // the number of words in this node's subtree, including its own.
+    count: usize,
+}
+
+impl Node<'_> {
+    fn child(&self, letter: Letter) -> Option<usize> {
+        self.children
+            .binary_search_by_key(&letter, |&(child, _)| child)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+/// The words of a dictionary in a trie. Words with characters other than
+/// letters can't be answers, so they aren't indexed.
+#[derive(Debug)]
+pub struct WordIndex<'a> {
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> WordIndex<'a> {
+    pub fn new(words: impl IntoIterator<Item = &'a Word>) -> Self {
+        let mut index = WordIndex {
+            nodes: vec![Node::default()],
+        };
+        for word in words {
+            index.insert(word);
+        }
+        index
+    }
+
+    /// Add `word`, returning whether it wasn't indexed yet.
+    pub fn insert(&mut self, word: &'a Word) -> bool {
+        let Some(letters) = letters(word) else {
+            return false;
+        };
+        let mut path = vec![ROOT];
+        for letter in letters {
+            let node = *path.last().expect("the path starts at the root");
+            let child = match self.nodes[node]
+                .children
+                .binary_search_by_key(&letter, |&(child, _)| child)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(i, (letter, child));
+                    child
+                }
+            };
+            path.push(child);
+        }
+
+        let end = *path.last().expect("the path starts at the root");
+        if self.nodes[end].word.is_some() {
+            return false;
+        }
+        self.nodes[end].word = Some(word);
+        for node in path {
+            self.nodes[node].count += 1;
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[ROOT].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The words starting with `prefix` (in any case), alphabetically.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<&'a Word> {
+        self.find(prefix)
+            .map(|node| self.collect(node, None))
+            .unwrap_or_default()
+    }
+
+    /// The number of words starting with `prefix`, without visiting them.
+    pub fn count_with_prefix(&self, prefix: &str) -> usize {
+        self.find(prefix).map_or(0, |node| self.nodes[node].count)
+    }
+
+    /// The words starting with `prefix` that consist only of the `allowed`
+    /// letters, e.g. a puzzle's, alphabetically. Branches with other letters
+    /// aren't traversed.
+    pub fn words_with_letters(
+        &self,
+        prefix: &str,
+        allowed: impl IntoIterator<Item = Letter>,
+    ) -> Vec<&'a Word> {
+        let allowed: LetterSet = allowed.into_iter().collect();
+        let prefix_allowed = letters(prefix)
+            .is_some_and(|mut letters| letters.all(|letter| allowed.contains(letter)));
+        match self.find(prefix) {
+            Some(node) if prefix_allowed => self.collect(node, Some(allowed)),
+            _ => Vec::new(),
+        }
+    }
+
+    // the node of `prefix`, if any word starts with it.
+    fn find(&self, prefix: &str) -> Option<usize> {
+        letters(prefix)?.try_fold(ROOT, |node, letter| self.nodes[node].child(letter))
+    }
+
+    // the words in the subtree of `node` reached through `allowed` letters,
+    // or any if `None`, in alphabetical order.
+    fn collect(&self, node: usize, allowed: Option<LetterSet>) -> Vec<&'a Word> {
+        let mut words = Vec::with_capacity(self.nodes[node].count);
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            words.extend(node.word);
+            // reversed, so that the smallest letter is visited first.
+            stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .filter(|&&(letter, _)| allowed.is_none_or(|allowed| allowed.contains(letter)))
+                    .map(|&(_, child)| child),
+            );
+        }
+        words
+    }
+}
+
+impl<'a> From<&'a Dictionary> for WordIndex<'a> {
+    fn from(dict: &'a Dictionary) -> Self {
+        WordIndex::new(dict.iter())
+    }
+}
+
+// the letters of `word`, if it consists only of letters.
+fn letters(word: &str) -> Option<impl Iterator<Item = Letter> + '_> {
+    word.chars()
+        .all(|c| Letter::new(c).is_ok())
+        .then(|| word.chars().filter_map(|c| Letter::new(c).ok()))
+}
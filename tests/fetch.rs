@@ -0,0 +1,165 @@
+//! The network code paths driven by canned responses of a `MockFetcher`.
+
+use nyt_spelling_bee_solver::{
+    dictionary::{Dictionary, ParseOptions, WORD_LIST_URL},
+    fetch::MockFetcher,
+    language::Language,
+    nyt::{GAME_STATE_URL, NytClient, NytPuzzle, PUZZLE_PAGE_URL},
+};
+
+const PUZZLE_PAGE: &str = r#"<html><script type="text/javascript">window.gameData = {
+    "today": {"id": 20345, "printDate": "2024-01-02", "editor": "Sam Ezersky",
+        "displayWeekday": "Tuesday", "centerLetter": "c",
+        "outerLetters": ["a", "l", "t", "e", "f", "i"],
+        "answers": ["felicitate", "facet"], "pangrams": ["felicitate"]},
+    "yesterday": {"id": 20344, "printDate": "2024-01-01", "centerLetter": "t",
+        "outerLetters": ["a", "l", "c", "e", "f", "i"]}
+}</script></html>"#;
+
+const GAME_STATE: &str = r#"{"states": [
+    {"game_data": {"answers": ["facet", "felicitate"]}}
+]}"#;
+
+fn client(fetcher: MockFetcher) -> NytClient<MockFetcher> {
+    NytClient::new(fetcher, Some("cookie".to_string()))
+}
+
+fn today() -> NytPuzzle {
+    client(MockFetcher::new().with_response(PUZZLE_PAGE_URL, 200, PUZZLE_PAGE))
+        .today()
+        .unwrap()
+}
+
+// the full chain of an error, context included.
+fn error_text(error: anyhow::Error) -> String {
+    format!("{error:#}")
+}
+
+#[test]
+fn today_is_read_from_the_puzzle_page() {
+    let puzzle = today();
+    assert_eq!(puzzle.id, 20345);
+    assert_eq!(puzzle.print_date.to_string(), "2024-01-02");
+    assert_eq!(puzzle.editor.as_deref(), Some("Sam Ezersky"));
+    let letters: String = puzzle.game().unwrap().letters().map(char::from).collect();
+    assert_eq!(letters, "CALTEFI");
+    assert_eq!(puzzle.answers, ["felicitate", "facet"]);
+    assert_eq!(puzzle.metadata().weekday, "Tuesday");
+}
+
+#[test]
+fn puzzles_include_yesterday() {
+    let fetcher = MockFetcher::new().with_response(PUZZLE_PAGE_URL, 200, PUZZLE_PAGE);
+    let ids: Vec<u64> = client(fetcher)
+        .puzzles()
+        .unwrap()
+        .iter()
+        .map(|puzzle| puzzle.id)
+        .collect();
+    assert_eq!(ids, [20345, 20344]);
+}
+
+#[test]
+fn failed_page_requests_are_errors() {
+    let fetcher = MockFetcher::new().with_response(PUZZLE_PAGE_URL, 503, "unavailable");
+    let error = error_text(client(fetcher).today().unwrap_err());
+    assert!(error.contains("responded with status 503"), "{error}");
+}
+
+#[test]
+fn pages_without_game_data_are_rejected() {
+    let fetcher = MockFetcher::new().with_response(PUZZLE_PAGE_URL, 200, "<html></html>");
+    let error = error_text(client(fetcher).today().unwrap_err());
+    assert!(
+        error.starts_with("puzzle page does not contain the game data"),
+        "{error}"
+    );
+}
+
+#[test]
+fn malformed_game_data_is_rejected() {
+    let page = "window.gameData = {\"today\": {\"id\": \"not a number\"}}";
+    let fetcher = MockFetcher::new().with_response(PUZZLE_PAGE_URL, 200, page);
+    let error = error_text(client(fetcher).today().unwrap_err());
+    assert!(
+        error.starts_with("failed to parse the puzzle page's game data"),
+        "{error}"
+    );
+}
+
+#[test]
+fn found_words_are_uppercased() {
+    let fetcher = MockFetcher::new().with_response(GAME_STATE_URL, 200, GAME_STATE);
+    let words = client(fetcher).found_words(&today()).unwrap();
+    assert_eq!(words, ["FACET", "FELICITATE"]);
+}
+
+#[test]
+fn found_words_of_unstarted_puzzles_are_empty() {
+    let fetcher = MockFetcher::new().with_response(GAME_STATE_URL, 200, r#"{"states": []}"#);
+    assert!(client(fetcher).found_words(&today()).unwrap().is_empty());
+}
+
+#[test]
+fn rejected_session_cookies_are_reported() {
+    for status in [401, 403] {
+        let fetcher = MockFetcher::new().with_response(GAME_STATE_URL, status, "");
+        let error = error_text(client(fetcher).found_words(&today()).unwrap_err());
+        assert_eq!(
+            error, "NYT rejected the session cookie, it may have expired",
+            "{status}"
+        );
+    }
+}
+
+#[test]
+fn found_words_require_a_session_cookie() {
+    let fetcher = MockFetcher::new().with_response(GAME_STATE_URL, 200, GAME_STATE);
+    let error = NytClient::new(fetcher, None)
+        .found_words(&today())
+        .unwrap_err();
+    assert_eq!(
+        error_text(error),
+        "fetching found words requires an NYT-S session cookie"
+    );
+}
+
+#[test]
+fn malformed_game_states_are_rejected() {
+    let fetcher = MockFetcher::new().with_response(GAME_STATE_URL, 200, "{\"states\": 3}");
+    let error = error_text(client(fetcher).found_words(&today()).unwrap_err());
+    assert!(error.starts_with("failed to parse game state"), "{error}");
+}
+
+#[test]
+fn scraped_word_lists_are_parsed() {
+    let fetcher =
+        MockFetcher::new().with_response(WORD_LIST_URL, 200, "FACET\nFELICITATE\nPSST\nCAT\n");
+    let dict = Dictionary::scrape(&fetcher, ParseOptions::default()).unwrap();
+    assert_eq!(dict.len(), 3);
+    assert!(dict.contains("FELICITATE"));
+    assert!(dict.contains("PSST"));
+    assert_eq!(dict.metadata().source.as_deref(), Some(WORD_LIST_URL));
+}
+
+#[test]
+fn failed_scrapes_are_errors() {
+    let fetcher = MockFetcher::new();
+    let Err(error) = Dictionary::scrape(&fetcher, ParseOptions::default()) else {
+        panic!("scraping a missing word list succeeded");
+    };
+    assert_eq!(
+        error_text(error),
+        format!("{WORD_LIST_URL} responded with status 404")
+    );
+}
+
+#[test]
+fn only_english_word_lists_are_scraped() {
+    let fetcher = MockFetcher::new().with_response(WORD_LIST_URL, 200, "FACET\n");
+    let options = ParseOptions {
+        language: Language::Spanish,
+        ..ParseOptions::default()
+    };
+    assert!(Dictionary::scrape(&fetcher, options).is_err());
+}
@@ -0,0 +1,50 @@
+//! Rules other than the official ones change which dictionary words are kept,
+//! whichever way the dictionary is loaded.
+
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+// relative to the crate root, sorted so that it can be memory-mapped.
+const DICT: &str = "tests/fixtures/short-words.txt";
+
+// the output of running the command line tool with `args` on the fixture
+// puzzle, with answers of at least 3 letters.
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_nyt-spelling-bee-solver"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["--color", "never"])
+        .args(args)
+        .args(["--dict", DICT, "--min-answer-length", "3"])
+        .env_remove("SPELLING_BEE_DICT_CACHE")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn shorter_answers_are_solved() {
+    for args in [
+        &["solve"][..],
+        &["solve", "--mapped"],
+        &["solve", "--format", "ndjson"],
+    ] {
+        let output = run(args);
+        assert!(output.contains("CAT"), "{args:?}: {output}");
+        assert!(output.contains("FACET"), "{args:?}: {output}");
+    }
+}
+
+#[test]
+fn shorter_answers_are_checked() {
+    for mapped in [&[][..], &["--mapped"]] {
+        let mut args = vec!["check", "cat", "--center", "C", "--letters", "ALTEFI"];
+        args.extend(mapped);
+        assert_eq!(run(&args), "CAT is valid and worth 1 points (1 base)\n");
+    }
+}
@@ -0,0 +1,36 @@
+//! Solutions shared in the forum formats are read back as the same puzzle.
+
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+use nyt_spelling_bee_solver::share;
+
+// relative to the crate root.
+const DICT: &str = "tests/fixtures/words.txt";
+
+// the output of solving the default puzzle in `format`.
+fn solve(format: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_nyt-spelling-bee-solver"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["--color", "never", "solve", "--dict", DICT])
+        .args(["--format", format])
+        .env_remove("SPELLING_BEE_DICT_CACHE")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "solve --format {format} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn forum_formats_parse_back() {
+    for format in ["forum", "forum-discord", "forum-reddit"] {
+        let game = share::parse_game(&solve(format)).unwrap();
+        let letters: String = game.letters().map(char::from).collect();
+        assert_eq!(letters, "CAEFILT", "{format}");
+    }
+}
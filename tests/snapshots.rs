@@ -0,0 +1,103 @@
+//! Snapshots of every output format for a fixed dictionary and puzzle, so that
+//! changes to the formats are reviewed deliberately with `cargo insta review`.
+
+#![cfg(feature = "cli")]
+
+use std::{path::Path, process::Command};
+
+use insta::assert_snapshot;
+use nyt_spelling_bee_solver::{
+    dictionary::{Dictionary, ParseOptions},
+    game::{Game, GameSolver, PuzzleSpec, RarestLetterMap},
+    hints::Hints,
+};
+
+// relative to the crate root, so that the path in the JSON output is stable.
+const DICT: &str = "tests/fixtures/words.txt";
+// the puzzle `solve` solves when none is given.
+const CENTER: &str = "C";
+const LETTERS: &str = "ALTEFI";
+
+// the output of solving the fixture puzzle in `format`.
+fn solve(format: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_nyt-spelling-bee-solver"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["--color", "never", "solve", "--dict", DICT])
+        .args(["--format", format])
+        .env_remove("SPELLING_BEE_DICT_CACHE")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "solve --format {format} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn text() {
+    assert_snapshot!("text", &solve("text"));
+}
+
+#[test]
+fn json() {
+    assert_snapshot!("json", &solve("json"));
+}
+
+#[test]
+fn ndjson() {
+    // streamed in the order of the word list.
+    assert_snapshot!("ndjson", &solve("ndjson"));
+}
+
+#[test]
+fn csv() {
+    assert_snapshot!("csv", &solve("csv"));
+}
+
+#[test]
+fn markdown() {
+    assert_snapshot!("markdown", &solve("markdown"));
+}
+
+#[test]
+fn stems() {
+    assert_snapshot!("stems", &solve("stems"));
+}
+
+#[test]
+fn svg() {
+    assert_snapshot!("svg", &solve("svg"));
+}
+
+#[test]
+fn grid() {
+    assert_snapshot!("grid", &solve("grid"));
+}
+
+#[test]
+fn forum() {
+    assert_snapshot!("forum", &solve("forum"));
+}
+
+#[test]
+fn forum_discord() {
+    assert_snapshot!("forum_discord", &solve("forum-discord"));
+}
+
+#[test]
+fn forum_reddit() {
+    assert_snapshot!("forum_reddit", &solve("forum-reddit"));
+}
+
+#[test]
+fn hints() {
+    // only printed for today's puzzle by the command line tool.
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(DICT);
+    let dict = Dictionary::load(&path, ParseOptions::default()).unwrap();
+    let solver = GameSolver::<RarestLetterMap>::new(&dict, 1).unwrap();
+    let puzzle = PuzzleSpec::from(Game::parse(CENTER, LETTERS).unwrap());
+    let result = solver.solve(&puzzle).unwrap();
+    assert_snapshot!("hints", &Hints::new(&result).to_string());
+}